@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     io,
     path::{Path, PathBuf},
     time::SystemTime,
@@ -43,7 +44,7 @@ pub type EventsRx = mpsc::Receiver<Event>;
 
 pub enum Event {
     ChangeDetected,
-    BuildFinished(Versioned<CompilerOutput>),
+    BuildFinished(Box<Versioned<CompilerOutput>>),
 }
 
 async fn build_and_watch_game_inner(
@@ -90,13 +91,26 @@ async fn build_and_watch_game_inner(
 
         ignored_error = None;
 
+        for diagnostic in &code.diagnostics {
+            tracing::warn!(
+                "Error in game code at `{}.capi`, {}: {}",
+                diagnostic.module,
+                diagnostic.span,
+                diagnostic.message,
+            );
+        }
+
         timestamp.update();
 
         let code = Versioned {
             timestamp: timestamp.0,
             inner: code,
         };
-        if events.send(Event::BuildFinished(code)).await.is_err() {
+        if events
+            .send(Event::BuildFinished(Box::new(code)))
+            .await
+            .is_err()
+        {
             // Receiver dropped. We must be in the process of shutting down.
             return Ok(());
         }
@@ -111,19 +125,75 @@ async fn build_and_watch_game_inner(
     Ok(())
 }
 
+/// # Determine whether the provided path refers to a game source file
+///
+/// Every source file in the game directory is a module of the game, named after
+/// the file. The `main` function is expected in `main.capi`.
+pub fn is_source_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "capi")
+}
+
 async fn build_game_once_with_compiler(
     game_dir: &Path,
     compiler: &mut Compiler,
 ) -> Result<CompilerOutput, BuildGameOnceError> {
-    let path = game_dir.join("main.capi");
-    let source = fs::read_to_string(&path)
-        .await
-        .map_err(|source| BuildGameOnceError { source, path })?;
-    let output = compiler.compile(&source, &GameEngineHost);
+    let modules = read_modules(game_dir).await?;
+    let output = compiler.compile_modules(
+        modules
+            .iter()
+            .map(|(module, source)| (module.as_str(), source.as_str())),
+        &GameEngineHost,
+    );
 
     Ok(output)
 }
 
+async fn read_modules(
+    game_dir: &Path,
+) -> Result<BTreeMap<String, String>, BuildGameOnceError> {
+    let mut modules = BTreeMap::new();
+
+    let mut entries =
+        fs::read_dir(game_dir)
+            .await
+            .map_err(|source| BuildGameOnceError {
+                source,
+                path: game_dir.to_path_buf(),
+            })?;
+
+    loop {
+        let entry = entries.next_entry().await.map_err(|source| {
+            BuildGameOnceError {
+                source,
+                path: game_dir.to_path_buf(),
+            }
+        })?;
+        let Some(entry) = entry else {
+            break;
+        };
+
+        let path = entry.path();
+        if !is_source_file(&path) {
+            continue;
+        }
+        let Some(module) = path.file_stem().and_then(|stem| stem.to_str())
+        else {
+            continue;
+        };
+
+        let source = fs::read_to_string(&path).await.map_err(|source| {
+            BuildGameOnceError {
+                source,
+                path: path.clone(),
+            }
+        })?;
+        modules.insert(module.to_string(), source);
+    }
+
+    Ok(modules)
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error("Error while building `{path}`: {source}")]
 pub struct BuildGameOnceError {
@@ -2,21 +2,74 @@ use std::{fmt::Write, net::SocketAddr, path::PathBuf};
 
 use anyhow::anyhow;
 use clap::Parser;
+use crosscut_game_engine::memory::Memory;
+use crosscut_runtime::StackLimits;
+use tokio::task;
 
-use crate::{export::export, files, headless, server};
+use crate::{
+    export::export, files, fmt::fmt, headless, lsp, native, server, tui,
+};
 
 pub async fn run() -> anyhow::Result<()> {
-    tracing_subscriber::fmt().init();
+    // Standard output is reserved for the language server protocol, if the
+    // `lsp` command is running. Log to standard error instead.
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
 
     let args = Args::parse();
 
     match args.command {
+        Command::Debug { address } => {
+            tui::start(address).await?;
+        }
         Command::Export { path } => {
             check_files()?;
             export(args.games, path).await?;
         }
-        Command::Headless => {
-            headless::run(args.games).await?;
+        Command::Fmt { check } => {
+            fmt(args.games, check).await?;
+        }
+        Command::Headless {
+            frames,
+            seed,
+            memory_size,
+            stack_frames,
+            stack_operands,
+            load_state,
+            save_state,
+            record,
+            replay,
+            input,
+            relay,
+        } => {
+            headless::run(
+                args.games,
+                headless::Options {
+                    frames,
+                    seed,
+                    memory_size,
+                    stack_limits: StackLimits {
+                        frames: stack_frames,
+                        operands: stack_operands,
+                    },
+                    load_state,
+                    save_state,
+                    record,
+                    replay,
+                    input,
+                    relay,
+                },
+            )
+            .await?;
+        }
+        Command::Lsp => {
+            // The language server does its own blocking I/O on stdin and
+            // stdout. Let's keep that away from the async runtime.
+            task::spawn_blocking(lsp::run).await??;
+        }
+        Command::Run { address } => {
+            native::start(address).await?;
         }
         Command::Serve { address } => {
             check_files()?;
@@ -62,11 +115,82 @@ struct Args {
 
 #[derive(clap::Subcommand)]
 enum Command {
+    /// Debug a game in the terminal, through a running `serve` command
+    Debug {
+        /// Address of the server that relays to the game
+        #[arg(short, long, default_value = "127.0.0.1:34480")]
+        address: SocketAddr,
+    },
     Export {
         #[arg(short, long)]
         path: PathBuf,
     },
-    Headless,
+    /// Format the source code of all games
+    Fmt {
+        /// Don't modify any files; fail, if any are not formatted
+        #[arg(long)]
+        check: bool,
+    },
+    /// Run Snake without a display, measuring its frame times
+    Headless {
+        /// Stop after running this many frames
+        #[arg(long)]
+        frames: Option<u64>,
+
+        /// Seed the random number generator, instead of using a random seed
+        ///
+        /// A replay uses the seed it was recorded with, so this can't be
+        /// combined with `--replay`.
+        #[arg(long, conflicts_with = "replay")]
+        seed: Option<u64>,
+
+        /// The size of the game's memory, in bytes
+        #[arg(long, default_value_t = Memory::DEFAULT_SIZE)]
+        memory_size: usize,
+
+        /// The maximum number of stack frames, which limits recursion depth
+        #[arg(long, default_value_t = StackLimits::default().frames)]
+        stack_frames: usize,
+
+        /// The maximum number of operands, across all stack frames
+        #[arg(long, default_value_t = StackLimits::default().operands)]
+        stack_operands: usize,
+
+        /// Restore the game from a state file, before running it
+        #[arg(long)]
+        load_state: Option<PathBuf>,
+
+        /// Save the state of the game to this file, once it stops
+        #[arg(long)]
+        save_state: Option<PathBuf>,
+
+        /// Record the external inputs of the game to this file
+        ///
+        /// Commands from a debugger are not recorded, so this can't be
+        /// combined with `--relay`.
+        #[arg(long, conflicts_with_all = ["replay", "relay"])]
+        record: Option<PathBuf>,
+
+        /// Replay a recording, starting from the state it was recorded in
+        #[arg(long, conflicts_with = "load_state")]
+        replay: Option<PathBuf>,
+
+        /// Provide the input events from this file to the game
+        #[arg(long, conflicts_with = "replay")]
+        input: Option<PathBuf>,
+
+        /// Let the debugger attach through the server at this address
+        #[arg(long, conflicts_with = "replay")]
+        relay: Option<SocketAddr>,
+    },
+    /// Run a language server on stdin and stdout
+    Lsp,
+    /// Run the game in a window, with code from a running `serve` command
+    Run {
+        /// Address of the server to get the code from
+        #[arg(short, long, default_value = "127.0.0.1:34480")]
+        address: SocketAddr,
+    },
     Serve {
         /// Address to serve at
         #[arg(short, long, default_value = "127.0.0.1:34480")]
@@ -123,3 +247,38 @@ fn check_files() -> anyhow::Result<()> {
 
     Err(anyhow!("{}", err))
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::Args;
+
+    #[test]
+    fn reject_recording_of_relayed_session() {
+        let args = Args::try_parse_from([
+            "crosscut",
+            "headless",
+            "--record",
+            "recording.ron",
+            "--relay",
+            "127.0.0.1:34480",
+        ]);
+
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn reject_seed_for_replay() {
+        let args = Args::try_parse_from([
+            "crosscut",
+            "headless",
+            "--seed",
+            "1",
+            "--replay",
+            "recording.ron",
+        ]);
+
+        assert!(args.is_err());
+    }
+}
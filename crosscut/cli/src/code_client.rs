@@ -0,0 +1,77 @@
+use std::net::SocketAddr;
+
+use crosscut_compiler::CompilerOutput;
+use crosscut_protocol::{
+    code::{CodeAtClient, CodeUpdate},
+    wire,
+};
+use tokio::sync::mpsc;
+
+/// # Fetch the game's code from the development server, for as long as it runs
+///
+/// Sends each new version of the code to the provided channel.
+///
+/// Returns an error, if the server can't be reached. Returns `Ok`, if the
+/// receiver of the code has been dropped.
+pub async fn fetch_code(
+    address: SocketAddr,
+    code_tx: mpsc::UnboundedSender<CompilerOutput>,
+) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+    let mut code = CodeAtClient::default();
+
+    loop {
+        let url = format!("http://{address}{}", code.next_request());
+
+        let update = client.get(url).send().await?.error_for_status()?;
+        let update: CodeUpdate = wire::decode(&update.bytes().await?)?;
+        let new_code = match code.apply(update) {
+            Ok(new_code) => new_code,
+            Err(err) => {
+                // We've dropped the code that the update didn't fit, so the
+                // next request gets us the full code.
+                tracing::warn!("Re-requesting full code: {err}");
+                continue;
+            }
+        };
+
+        if code_tx.send(new_code.clone()).is_err() {
+            // The receiver has stopped. We're done here.
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tokio::sync::mpsc;
+
+    use crate::server::{self, Event};
+
+    use super::fetch_code;
+
+    #[tokio::test]
+    async fn fetch_code_from_server() -> anyhow::Result<()> {
+        let games_dir = PathBuf::from("../../games");
+        let address = "[::1]:34482".parse()?;
+
+        let mut events = server::start(games_dir, address).await?;
+
+        // Wait for server to be ready.
+        while let Some(event) = events.recv().await {
+            if let Event::ServerReady = event {
+                break;
+            }
+        }
+
+        let (code_tx, mut code_rx) = mpsc::unbounded_channel();
+        tokio::spawn(fetch_code(address, code_tx));
+
+        let code = code_rx.recv().await.unwrap();
+        assert!(code.syntax_tree.function_by_name("main").is_some());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use std::net::SocketAddr;
+
+use crosscut_game_engine::{command::Command, game_engine::GameEngine};
+use crosscut_protocol::{command::CommandExt, updates::Updates};
+
+use crate::relay_client::{self, MessagesRx, MessagesTx, Peer};
+
+/// # A connection to the debugger, relayed through the server
+///
+/// This does the same for a game that runs in the CLI, as the browser host
+/// does for a game that runs in the page: Apply the commands that the debugger
+/// sends, and keep the debugger updated on the state of the game engine.
+pub struct DebuggerConnection {
+    commands: MessagesRx,
+    updates_tx: MessagesTx,
+    updates: Updates,
+}
+
+impl DebuggerConnection {
+    pub async fn connect(address: SocketAddr) -> anyhow::Result<Self> {
+        let (updates_tx, commands) =
+            relay_client::connect(address, Peer::Game).await?;
+
+        Ok(Self {
+            commands,
+            updates_tx,
+            updates: Updates::default(),
+        })
+    }
+
+    /// # Apply the commands that the debugger sent since the last call
+    pub fn apply_commands(&mut self, game_engine: &mut GameEngine) {
+        while let Ok(command) = self.commands.try_recv() {
+            match Command::deserialize(command) {
+                Ok(command) => {
+                    game_engine.on_command(command);
+                }
+                Err(err) => {
+                    tracing::warn!("Ignoring malformed command: {err}");
+                }
+            }
+        }
+
+        if let Some(snapshot) = game_engine.take_snapshot() {
+            self.updates.queue_snapshot(snapshot);
+        }
+        if game_engine.take_rejected_patch() {
+            self.updates.queue_rejected_patch();
+        }
+    }
+
+    /// # Send the debugger updates about what changed in the game engine
+    pub fn send_updates(&mut self, game_engine: &GameEngine) {
+        self.updates.queue_updates(
+            &game_engine.runtime,
+            game_engine.memory(),
+            game_engine.seed(),
+        );
+
+        for update in self.updates.take_queued_updates() {
+            // If the connection has been lost, we already warned about that.
+            // The game can keep running without a debugger.
+            let _ = self.updates_tx.send(update.serialize());
+        }
+    }
+}
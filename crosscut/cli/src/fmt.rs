@@ -0,0 +1,84 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use crosscut_compiler::format::{format, FormatError};
+use tokio::fs;
+
+use crate::build_game::is_source_file;
+
+/// # Format the source files of all games
+///
+/// If `check` is `true`, files are not modified. Instead, an error is returned,
+/// if any of them are not formatted.
+pub async fn fmt(games_path: PathBuf, check: bool) -> anyhow::Result<()> {
+    let mut games = fs::read_dir(&games_path).await?;
+    let mut unformatted = Vec::new();
+
+    while let Some(game) = games.next_entry().await? {
+        if !game.file_type().await?.is_dir() {
+            continue;
+        }
+
+        let mut files = fs::read_dir(game.path()).await?;
+
+        while let Some(file) = files.next_entry().await? {
+            let path = file.path();
+            if !is_source_file(&path) {
+                continue;
+            }
+
+            if !fmt_file(&path, check).await? {
+                unformatted.push(path);
+            }
+        }
+    }
+
+    if check && !unformatted.is_empty() {
+        let mut message =
+            String::from("The following files are not formatted:\n");
+        for path in unformatted {
+            message.push_str(&format!("- `{}`\n", path.display()));
+        }
+
+        return Err(anyhow!("{message}"));
+    }
+
+    Ok(())
+}
+
+/// # Format a single file
+///
+/// Returns whether the file was already formatted.
+async fn fmt_file(path: &Path, check: bool) -> anyhow::Result<bool> {
+    let source = fs::read_to_string(path).await?;
+
+    let formatted = match format(&source) {
+        Ok(formatted) => formatted,
+        Err(FormatError::Diagnostics { diagnostics }) => {
+            for diagnostic in &diagnostics {
+                eprintln!(
+                    "{}:{}: {}",
+                    path.display(),
+                    diagnostic.span,
+                    diagnostic.message
+                );
+            }
+
+            return Err(anyhow!("Can't format `{}`", path.display()));
+        }
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("Formatting `{}`", path.display()));
+        }
+    };
+
+    if formatted == source {
+        return Ok(true);
+    }
+
+    if !check {
+        fs::write(path, formatted).await?;
+    }
+
+    Ok(false)
+}
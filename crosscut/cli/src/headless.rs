@@ -1,22 +1,132 @@
-use std::{path::PathBuf, time::Instant};
+use std::{
+    fs,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Instant,
+};
 
+use anyhow::Context;
 use crosscut_game_engine::{
-    command::Command, display::NUM_PIXEL_BYTES, game_engine::GameEngine,
+    command::Command,
+    game_engine::{GameEngine, GameEngineConfig},
+    input::InputEvent,
+    recording::Recording,
 };
+use crosscut_runtime::StackLimits;
 use rand::random;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    build_game::build_game_once, debugger_connection::DebuggerConnection,
+};
+
+pub struct Options {
+    /// # Stop after running this many frames
+    pub frames: Option<u64>,
+
+    /// # Seed the random number generator with this, instead of a random seed
+    pub seed: Option<u64>,
+
+    /// # The size of the game's memory, in bytes
+    pub memory_size: usize,
+
+    /// # The limits that the game's stack enforces
+    pub stack_limits: StackLimits,
+
+    /// # Restore the game from this state file, before running it
+    pub load_state: Option<PathBuf>,
 
-use crate::build_game::build_game_once;
+    /// # Save the state of the game to this file, once it stops
+    pub save_state: Option<PathBuf>,
 
-pub async fn run(games_path: PathBuf) -> anyhow::Result<()> {
+    /// # Record the external inputs of the game to this file
+    pub record: Option<PathBuf>,
+
+    /// # Replay the recording from this file, instead of generating inputs
+    pub replay: Option<PathBuf>,
+
+    /// # Provide the input events from this file to the game
+    ///
+    /// See [`ScriptedInput`].
+    pub input: Option<PathBuf>,
+
+    /// # Let the debugger attach through the server at this address
+    pub relay: Option<SocketAddr>,
+}
+
+/// # An input event, to be provided to the game before a specific frame
+///
+/// A file of scripted input contains a list of these, in RON format. For
+/// example:
+///
+/// ``` text
+/// [
+///     (frame: 10, event: KeyDown(Up)),
+///     (frame: 12, event: KeyUp(Up)),
+/// ]
+/// ```
+#[derive(serde::Deserialize)]
+pub struct ScriptedInput {
+    /// # The number of the frame, counting from zero
+    pub frame: u64,
+
+    pub event: InputEvent,
+}
+
+pub async fn run(games_path: PathBuf, options: Options) -> anyhow::Result<()> {
     let code = build_game_once(&games_path.join("snake")).await?;
 
-    let mut pixels = [0; NUM_PIXEL_BYTES];
-    let mut game_engine = GameEngine::new();
+    let mut pixels = Vec::new();
+    let mut game_engine = GameEngine::with_config(GameEngineConfig {
+        memory_size: options.memory_size,
+        stack_limits: options.stack_limits,
+    });
 
     game_engine.on_command(Command::UpdateCode {
-        instructions: code.instructions,
+        instructions: code.instructions.clone(),
     });
 
+    // A recording brings its own seed, as part of its initial state.
+    if options.replay.is_none() {
+        let seed = options.seed.unwrap_or_else(random);
+        game_engine.on_command(Command::SetSeed { seed });
+    }
+
+    if let Some(path) = &options.load_state {
+        game_engine.restore(read_file(path)?);
+    }
+
+    let mut replay = if let Some(path) = &options.replay {
+        let recording: Recording = read_file(path)?;
+        game_engine.restore(recording.initial_state);
+        Some(recording.frames.into_iter())
+    } else {
+        None
+    };
+
+    // A state file or a recording might have replaced the seed we set above.
+    // Print the one that the game actually uses.
+    eprintln!("Seed: {}", game_engine.seed());
+
+    let mut input = if let Some(path) = &options.input {
+        let input: Vec<ScriptedInput> = read_file(path)?;
+        input.into_iter().peekable()
+    } else {
+        Vec::new().into_iter().peekable()
+    };
+
+    if options.record.is_some() {
+        game_engine.start_recording();
+    }
+
+    let mut debugger = if let Some(address) = options.relay {
+        let debugger = DebuggerConnection::connect(address).await?;
+        eprintln!("Debugger can attach at http://{address}/?attach");
+        Some(debugger)
+    } else {
+        None
+    };
+
     let start_of_game = Instant::now();
     let mut start_of_loop;
     let mut start_of_frame = Instant::now();
@@ -24,25 +134,71 @@ pub async fn run(games_path: PathBuf) -> anyhow::Result<()> {
     let mut times_net = Measurements::default();
     let mut times_gross = Measurements::default();
 
+    let mut num_frames = 0;
+
     while !game_engine.runtime.state().has_finished() {
+        if options.frames.is_some_and(|frames| num_frames >= frames) {
+            break;
+        }
+
         start_of_loop = Instant::now();
 
-        while game_engine.push_random(random()) {}
+        if let Some(debugger) = &mut debugger {
+            debugger.apply_commands(&mut game_engine);
+        }
+
+        while let Some(scripted) =
+            input.next_if(|scripted| scripted.frame <= num_frames)
+        {
+            game_engine.on_input(scripted.event);
+        }
+
+        if let Some(replay) = &mut replay {
+            let Some(frame) = replay.next() else {
+                eprintln!("Finished replaying recording.");
+                break;
+            };
+
+            // A replay runs as fast as possible. Any delay between frames
+            // would not affect the result anyway.
+            game_engine.replay_frame(frame, &mut pixels);
+        } else {
+            if !game_engine.run_until_end_of_frame(
+                start_of_game.elapsed().as_secs_f64(),
+                &mut pixels,
+            ) {
+                // Game engine decided that it's not time to run another frame
+                // yet.
+                continue;
+            }
+        }
+        num_frames += 1;
 
-        if !game_engine.run_until_end_of_frame(
-            start_of_game.elapsed().as_secs_f64(),
-            &mut pixels,
-        ) {
-            // Game engine decided that it's not time to run another frame yet.
-            continue;
+        if let Some(debugger) = &mut debugger {
+            debugger.send_updates(&game_engine);
         }
 
         if let Some(effect) = game_engine.runtime.effect().inspect() {
+            if debugger.is_some() {
+                // The game stopped, but the debugger can deal with that. Maybe
+                // it was even the one that stopped it.
+                continue;
+            }
+
             eprintln!("Unhandled effect: {effect:#?}");
+            if let Some(span) = code.source_map.instruction_to_span(
+                &game_engine.runtime.evaluator().next_instruction,
+            ) {
+                eprintln!("At: {span}");
+            }
             eprintln!("Current stack:\n{:#?}", game_engine.runtime.stack());
             break;
         }
 
+        if game_engine.runtime.state().is_out_of_fuel() {
+            eprintln!("Frame overrun: Game did not submit frame in time.");
+        }
+
         let frame_time_net = start_of_loop.elapsed().as_millis();
         let frame_time_gross = start_of_frame.elapsed().as_millis();
 
@@ -73,6 +229,45 @@ pub async fn run(games_path: PathBuf) -> anyhow::Result<()> {
         start_of_frame = Instant::now();
     }
 
+    if let Some(path) = &options.save_state {
+        write_file(path, &game_engine.snapshot())?;
+        eprintln!("Saved state to `{}`.", path.display());
+    }
+    if let Some(path) = &options.record {
+        if let Some(recording) = game_engine.stop_recording() {
+            write_file(path, &recording)?;
+            eprintln!(
+                "Saved recording of {} frames to `{}`.",
+                recording.frames.len(),
+                path.display(),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// # Read a state file or recording
+///
+/// Those are stored in RON format. They are only meaningful for the version of
+/// the game they were saved from.
+fn read_file<T>(path: &Path) -> anyhow::Result<T>
+where
+    T: DeserializeOwned,
+{
+    let value = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read `{}`", path.display()))?;
+    let value = ron::from_str(&value)
+        .with_context(|| format!("Failed to parse `{}`", path.display()))?;
+
+    Ok(value)
+}
+
+fn write_file(path: &Path, value: &impl Serialize) -> anyhow::Result<()> {
+    let value = ron::to_string(value)?;
+    fs::write(path, value)
+        .with_context(|| format!("Failed to write `{}`", path.display()))?;
+
     Ok(())
 }
 
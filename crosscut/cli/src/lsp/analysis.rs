@@ -0,0 +1,268 @@
+use std::collections::BTreeMap;
+
+use crosscut_compiler::{
+    code::{
+        syntax::{Expression, FunctionLocation, Located, MemberLocation},
+        Bindings, Span,
+    },
+    diagnostics::Diagnostic,
+    host::Host,
+    intrinsics::IntrinsicFunction,
+    Compiler, CompilerOutput,
+};
+use crosscut_game_engine::host::GameEngineHost;
+
+/// # The result of compiling all modules of a game
+///
+/// Answers the questions that the language server is asked about the code.
+/// Lines and columns follow the conventions of the compiler. Converting them
+/// to and from what the editor expects is up to the caller.
+pub struct Analysis {
+    output: CompilerOutput,
+    bindings: Bindings,
+}
+
+impl Analysis {
+    /// # Compile the provided modules
+    ///
+    /// Expects a map of module names to their source code.
+    pub fn new(modules: &BTreeMap<String, String>) -> Self {
+        // The language server has no use for the compiler's incremental
+        // updates. Every analysis starts from scratch.
+        let mut compiler = Compiler::default();
+        let output = compiler.compile_modules(
+            modules
+                .iter()
+                .map(|(module, source)| (module.as_str(), source.as_str())),
+            &GameEngineHost,
+        );
+        let bindings = Bindings::resolve(&output.syntax_tree);
+
+        Self { output, bindings }
+    }
+
+    /// # Iterate over the diagnostics that apply to the provided module
+    pub fn diagnostics<'r>(
+        &'r self,
+        module: &'r str,
+    ) -> impl Iterator<Item = &'r Diagnostic> {
+        self.output
+            .diagnostics
+            .iter()
+            .filter(move |diagnostic| diagnostic.module == module)
+    }
+
+    /// # Describe the expression at the provided position
+    pub fn hover(
+        &self,
+        module: &str,
+        line: usize,
+        column: usize,
+    ) -> Option<Hover> {
+        let (expression, span) = self.expression_at(module, line, column)?;
+
+        let label = match expression.fragment {
+            Expression::FieldAccess { field } => format!(".{field}"),
+            Expression::Identifier { name } => name.clone(),
+            Expression::LiteralNumber { value } => value.to_string(),
+            Expression::LocalFunction { .. } => "fn".to_string(),
+            Expression::Record { fields } => {
+                format!("{{ {} }}", fields.join(", "))
+            }
+            Expression::Error { message } => {
+                return Some(Hover {
+                    text: message.clone(),
+                    span,
+                });
+            }
+        };
+
+        let mut text = match self.signature_of(&expression.location) {
+            Some(signature) => format!("```\n{label}: {signature}\n```"),
+            None => format!("```\n{label}\n```"),
+        };
+
+        let called_function = self
+            .output
+            .function_calls
+            .is_call_to_user_defined_function(&expression.location)
+            .and_then(|location| {
+                self.output
+                    .syntax_tree
+                    .find_top_level_parent_function(location)
+            });
+        if let Some(comment) = called_function
+            .as_ref()
+            .and_then(|function| function.comment.as_ref())
+        {
+            text.push_str("\n\n");
+            text.push_str(&comment.lines.join("\n"));
+        }
+
+        Some(Hover { text, span })
+    }
+
+    /// # Find the definition of the identifier at the provided position
+    ///
+    /// Supports calls to named functions and bindings.
+    pub fn definition(
+        &self,
+        module: &str,
+        line: usize,
+        column: usize,
+    ) -> Option<Definition> {
+        let (expression, _) = self.expression_at(module, line, column)?;
+        let syntax_tree = &self.output.syntax_tree;
+
+        if let Some(parameter) = self.bindings.is_binding(&expression.location)
+        {
+            let span = syntax_tree.spans.parameters.get(parameter)?;
+
+            return Some(Definition {
+                module: module.to_string(),
+                span: *span,
+            });
+        }
+
+        let function = self
+            .output
+            .function_calls
+            .is_call_to_user_defined_function(&expression.location)?;
+        let function = syntax_tree.find_top_level_parent_function(function)?;
+        let span = syntax_tree.spans.named_functions.get(&function.index())?;
+
+        Some(Definition {
+            module: function.module.clone(),
+            span: *span,
+        })
+    }
+
+    /// # List the functions that can be called from the provided module
+    ///
+    /// Functions in other modules are qualified with the name of their module,
+    /// and only listed, if the provided module imports them.
+    pub fn completions(&self, module: &str) -> Vec<Completion> {
+        let intrinsics = IntrinsicFunction::all().map(|intrinsic| Completion {
+            label: intrinsic.name().to_string(),
+            kind: CompletionKind::Intrinsic,
+            detail: intrinsic
+                .signature()
+                .map(|signature| signature.to_string()),
+        });
+        let host_functions =
+            GameEngineHost
+                .functions()
+                .into_iter()
+                .map(|function| Completion {
+                    label: function.name,
+                    kind: CompletionKind::HostFunction,
+                    detail: Some(function.signature.to_string()),
+                });
+        let syntax_tree = &self.output.syntax_tree;
+        let user_functions = syntax_tree
+            .named_functions()
+            .filter(|function| {
+                syntax_tree.can_access_module(module, &function.module)
+            })
+            .map(|function| {
+                let label = if function.module == module {
+                    function.name.clone()
+                } else {
+                    format!("{}/{}", function.module, function.name)
+                };
+                let detail = self
+                    .output
+                    .types
+                    .signature_of_function(&function.location())
+                    .map(|signature| signature.to_string());
+
+                Completion {
+                    label,
+                    kind: CompletionKind::UserFunction,
+                    detail,
+                }
+            });
+
+        intrinsics
+            .chain(host_functions)
+            .chain(user_functions)
+            .collect()
+    }
+
+    /// # Find the innermost expression at the provided position
+    fn expression_at(
+        &self,
+        module: &str,
+        line: usize,
+        column: usize,
+    ) -> Option<(Located<&Expression>, Span)> {
+        let syntax_tree = &self.output.syntax_tree;
+
+        syntax_tree
+            .all_functions()
+            .filter(|function| {
+                syntax_tree
+                    .find_top_level_parent_function(&function.location)
+                    .is_some_and(|named_function| {
+                        named_function.module == module
+                    })
+            })
+            .flat_map(|function| {
+                function
+                    .branches()
+                    .flat_map(|branch| branch.expressions().collect::<Vec<_>>())
+                    .collect::<Vec<_>>()
+            })
+            .filter_map(|expression| {
+                let span =
+                    syntax_tree.spans.members.get(&expression.location)?;
+                span.contains(line, column).then_some((expression, *span))
+            })
+            // Local functions are expressions that contain other expressions.
+            // The innermost expression is the one that starts last.
+            .max_by_key(|(_, span)| (span.start.line, span.start.column))
+    }
+
+    fn signature_of(&self, location: &MemberLocation) -> Option<String> {
+        let types = &self.output.types;
+
+        if let Some(parameter) = self.bindings.is_binding(location) {
+            return types
+                .type_of_parameter(parameter)
+                .map(|type_| type_.to_string());
+        }
+
+        if let Some(signature) = types.signature_of_expression(location) {
+            return Some(signature.to_string());
+        }
+
+        types
+            .signature_of_function(&FunctionLocation::from(location.clone()))
+            .map(|signature| format!("fn {signature} end"))
+    }
+}
+
+/// # A description of an expression
+pub struct Hover {
+    pub text: String,
+    pub span: Span,
+}
+
+/// # The place where something is defined
+pub struct Definition {
+    pub module: String,
+    pub span: Span,
+}
+
+/// # A function that could be called at a given place in the code
+pub struct Completion {
+    pub label: String,
+    pub kind: CompletionKind,
+    pub detail: Option<String>,
+}
+
+pub enum CompletionKind {
+    Intrinsic,
+    HostFunction,
+    UserFunction,
+}
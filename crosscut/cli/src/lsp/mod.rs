@@ -0,0 +1,14 @@
+//! # Language server for Crosscut code
+//!
+//! Speaks the Language Server Protocol, to provide editor support for `.capi`
+//! files. The heavy lifting is done by the compiler. This module only
+//! translates between what the compiler knows about the code and what the
+//! editor asks for.
+
+mod analysis;
+mod server;
+
+pub use self::server::run;
+
+#[cfg(test)]
+mod tests;
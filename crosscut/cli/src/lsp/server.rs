@@ -0,0 +1,389 @@
+use std::{collections::BTreeMap, fs};
+
+use crosscut_compiler::code;
+use lsp_server::{
+    Connection, ErrorCode, Message, Notification, Request, Response,
+};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest, Request as _},
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionResponse,
+    Diagnostic, DiagnosticSeverity, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+    HoverProviderCapability, Location, MarkupContent, MarkupKind, OneOf,
+    Position, PublishDiagnosticsParams, Range, ServerCapabilities,
+    TextDocumentPositionParams, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Uri,
+};
+
+use crate::build_game::is_source_file;
+
+use super::analysis::{Analysis, CompletionKind};
+
+/// # Run the language server on stdin and stdout
+pub fn run() -> anyhow::Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+    serve(&connection)?;
+    io_threads.join()?;
+
+    Ok(())
+}
+
+/// # Run the language server on the provided connection
+///
+/// Returns after the client has requested a shutdown.
+pub fn serve(connection: &Connection) -> anyhow::Result<()> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::FULL,
+        )),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        ..ServerCapabilities::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut documents = Documents::default();
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+
+                let response = documents.handle_request(request);
+                connection.sender.send(Message::Response(response))?;
+            }
+            Message::Notification(notification) => {
+                let notifications =
+                    match documents.handle_notification(notification) {
+                        Ok(notifications) => notifications,
+                        Err(err) => {
+                            // Notifications don't get a response, so there's
+                            // nobody to tell about this. But that's no reason
+                            // to stop serving the editor.
+                            tracing::warn!("Ignoring notification: {err}");
+                            continue;
+                        }
+                    };
+
+                for notification in notifications {
+                    connection
+                        .sender
+                        .send(Message::Notification(notification))?;
+                }
+            }
+            Message::Response(_) => {
+                // We never send any requests to the client, so we don't
+                // expect any responses.
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// # The documents that are currently open in the editor
+///
+/// Every document is a module, named after its file. All `.capi` files in the
+/// same directory are compiled together. For those that are not open, the
+/// contents are read from disk.
+#[derive(Default)]
+struct Documents {
+    open: BTreeMap<Uri, String>,
+}
+
+impl Documents {
+    fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            HoverRequest::METHOD => {
+                handle::<HoverRequest>(request, |params| self.hover(params))
+            }
+            GotoDefinition::METHOD => {
+                handle::<GotoDefinition>(request, |params| {
+                    self.definition(params)
+                })
+            }
+            Completion::METHOD => handle::<Completion>(request, |params| {
+                self.completion(params.text_document_position)
+            }),
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported request: `{method}`"),
+            ),
+        }
+    }
+
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> anyhow::Result<Vec<Notification>> {
+        let uri = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = extract::<DidOpenTextDocument>(notification)?;
+                let document = params.text_document;
+
+                self.open.insert(document.uri.clone(), document.text);
+                document.uri
+            }
+            DidChangeTextDocument::METHOD => {
+                let mut params =
+                    extract::<DidChangeTextDocument>(notification)?;
+                let uri = params.text_document.uri;
+
+                // We only support full synchronization, so the last change
+                // contains the whole document.
+                if let Some(change) = params.content_changes.pop() {
+                    self.open.insert(uri.clone(), change.text);
+                }
+
+                uri
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = extract::<DidCloseTextDocument>(notification)?;
+                let uri = params.text_document.uri;
+
+                self.open.remove(&uri);
+
+                // The closed document isn't going to receive any further
+                // updates. Let's make sure its diagnostics don't linger.
+                let mut notifications = self.publish_diagnostics(&uri);
+                notifications.push(publish_diagnostics(uri, Vec::new()));
+                return Ok(notifications);
+            }
+            _ => {
+                // Not a notification we're interested in.
+                return Ok(Vec::new());
+            }
+        };
+
+        Ok(self.publish_diagnostics(&uri))
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let (analysis, module, position) =
+            self.analyze(params.text_document_position_params)?;
+        let hover = analysis.hover(
+            &module,
+            position.line as usize,
+            position.character as usize,
+        )?;
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: hover.text,
+            }),
+            range: Some(span_to_range(hover.span)),
+        })
+    }
+
+    fn definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Option<GotoDefinitionResponse> {
+        let uri = params
+            .text_document_position_params
+            .text_document
+            .uri
+            .clone();
+        let (analysis, module, position) =
+            self.analyze(params.text_document_position_params)?;
+        let definition = analysis.definition(
+            &module,
+            position.line as usize,
+            position.character as usize,
+        )?;
+
+        let (directory, _) = split_uri(&uri)?;
+        let uri = format!("{directory}/{}.capi", definition.module)
+            .parse()
+            .ok()?;
+
+        Some(GotoDefinitionResponse::Scalar(Location {
+            uri,
+            range: span_to_range(definition.span),
+        }))
+    }
+
+    fn completion(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Option<CompletionResponse> {
+        let (analysis, module, _) = self.analyze(params)?;
+
+        let items = analysis
+            .completions(&module)
+            .into_iter()
+            .map(|completion| CompletionItem {
+                label: completion.label,
+                kind: Some(match completion.kind {
+                    CompletionKind::Intrinsic => CompletionItemKind::OPERATOR,
+                    CompletionKind::HostFunction
+                    | CompletionKind::UserFunction => {
+                        CompletionItemKind::FUNCTION
+                    }
+                }),
+                detail: completion.detail,
+                ..CompletionItem::default()
+            })
+            .collect();
+
+        Some(CompletionResponse::Array(items))
+    }
+
+    fn publish_diagnostics(&self, uri: &Uri) -> Vec<Notification> {
+        let Some((directory, _)) = split_uri(uri) else {
+            return Vec::new();
+        };
+        let analysis = Analysis::new(&self.modules(directory));
+
+        // Changes to one module can affect others, so we need to update the
+        // diagnostics of all open documents in the same directory.
+        self.open
+            .keys()
+            .filter_map(|uri| {
+                let (other_directory, module) = split_uri(uri)?;
+                if other_directory != directory {
+                    return None;
+                }
+
+                let diagnostics = analysis
+                    .diagnostics(module)
+                    .map(|diagnostic| Diagnostic {
+                        range: span_to_range(diagnostic.span),
+                        severity: Some(DiagnosticSeverity::ERROR),
+                        message: diagnostic.message.clone(),
+                        ..Diagnostic::default()
+                    })
+                    .collect();
+
+                Some(publish_diagnostics(uri.clone(), diagnostics))
+            })
+            .collect()
+    }
+
+    fn analyze(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Option<(Analysis, String, Position)> {
+        let (directory, module) = split_uri(&params.text_document.uri)?;
+        let analysis = Analysis::new(&self.modules(directory));
+
+        Some((analysis, module.to_string(), params.position))
+    }
+
+    fn modules(&self, directory: &str) -> BTreeMap<String, String> {
+        let mut modules = BTreeMap::new();
+
+        // Whatever we can't read from disk, we'll just have to do without.
+        // Documents that only exist in the editor are still covered below.
+        let files = url::Url::parse(&format!("{directory}/"))
+            .ok()
+            .and_then(|url| url.to_file_path().ok())
+            .and_then(|path| fs::read_dir(path).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| is_source_file(path));
+        for path in files {
+            let Some(module) = path.file_stem().and_then(|stem| stem.to_str())
+            else {
+                continue;
+            };
+            let Ok(source) = fs::read_to_string(&path) else {
+                continue;
+            };
+
+            modules.insert(module.to_string(), source);
+        }
+
+        // The open documents take precedence over what is on disk, as they
+        // might have unsaved changes.
+        for (uri, source) in &self.open {
+            let Some((other_directory, module)) = split_uri(uri) else {
+                continue;
+            };
+            if other_directory == directory {
+                modules.insert(module.to_string(), source.clone());
+            }
+        }
+
+        modules
+    }
+}
+
+fn handle<R>(
+    request: Request,
+    f: impl FnOnce(R::Params) -> R::Result,
+) -> Response
+where
+    R: lsp_types::request::Request,
+{
+    let id = request.id.clone();
+
+    match request.extract::<R::Params>(R::METHOD) {
+        Ok((id, params)) => Response::new_ok(id, f(params)),
+        Err(err) => Response::new_err(
+            id,
+            ErrorCode::InvalidParams as i32,
+            format!("{err:?}"),
+        ),
+    }
+}
+
+fn extract<N>(notification: Notification) -> anyhow::Result<N::Params>
+where
+    N: lsp_types::notification::Notification,
+{
+    let params = notification
+        .extract(N::METHOD)
+        .map_err(|err| anyhow::anyhow!("{err:?}"))?;
+    Ok(params)
+}
+
+fn publish_diagnostics(uri: Uri, diagnostics: Vec<Diagnostic>) -> Notification {
+    Notification::new(
+        PublishDiagnostics::METHOD.to_string(),
+        PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        },
+    )
+}
+
+/// # Split a URI into the directory and the name of the module
+///
+/// Returns `None`, if the URI does not refer to a `.capi` file.
+fn split_uri(uri: &Uri) -> Option<(&str, &str)> {
+    let (directory, file) = uri.as_str().rsplit_once('/')?;
+    let module = file.strip_suffix(".capi")?;
+
+    Some((directory, module))
+}
+
+/// # Convert a span from the compiler into a range, as the editor expects it
+///
+/// ## Implementation Note
+///
+/// The compiler counts columns in characters, while LSP counts them in UTF-16
+/// code units, by default. Those are the same for all characters that are
+/// typically used in code, but can differ for others.
+fn span_to_range(span: code::Span) -> Range {
+    let position = |position: code::Position| Position {
+        line: position.line as u32,
+        character: position.column as u32,
+    };
+
+    Range {
+        start: position(span.start),
+        end: position(span.end),
+    }
+}
@@ -0,0 +1,344 @@
+use std::thread::{self, JoinHandle};
+
+use lsp_server::{Connection, Message, Notification, Request, RequestId};
+use lsp_types::{
+    notification::{
+        DidOpenTextDocument, Exit, Initialized, Notification as _,
+        PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest, Initialize, Shutdown},
+    CompletionParams, CompletionResponse, DidOpenTextDocumentParams,
+    GotoDefinitionParams, GotoDefinitionResponse, HoverContents, HoverParams,
+    InitializeParams, InitializedParams, Position, PublishDiagnosticsParams,
+    TextDocumentIdentifier, TextDocumentItem, TextDocumentPositionParams, Uri,
+};
+
+#[test]
+fn publish_diagnostics_for_syntax_errors() -> anyhow::Result<()> {
+    let mut client = TestClient::start()?;
+
+    client.open(
+        "main",
+        "\
+main: fn
+    br ->
+        :
+    end
+end
+",
+    )?;
+
+    let diagnostics = client.receive_diagnostics()?;
+    assert_eq!(diagnostics.uri, uri("main"));
+
+    let [diagnostic] = diagnostics.diagnostics.as_slice() else {
+        panic!("Expected exactly one diagnostic: {diagnostics:#?}");
+    };
+    assert_eq!(diagnostic.range.start, Position::new(2, 8));
+    assert!(diagnostic.message.contains("Unexpected token"));
+
+    client.shutdown()
+}
+
+#[test]
+fn publish_diagnostics_for_unresolved_identifiers() -> anyhow::Result<()> {
+    let mut client = TestClient::start()?;
+
+    client.open(
+        "main",
+        "\
+main: fn
+    br ->
+        does_not_exist
+    end
+end
+",
+    )?;
+
+    let diagnostics = client.receive_diagnostics()?;
+
+    let [diagnostic] = diagnostics.diagnostics.as_slice() else {
+        panic!("Expected exactly one diagnostic: {diagnostics:#?}");
+    };
+    assert_eq!(diagnostic.range.start, Position::new(2, 8));
+    assert!(diagnostic.message.contains("`does_not_exist`"));
+
+    client.shutdown()
+}
+
+#[test]
+fn ignore_notification_with_malformed_params() -> anyhow::Result<()> {
+    let mut client = TestClient::start()?;
+
+    client
+        .connection
+        .sender
+        .send(Message::Notification(Notification::new(
+            DidOpenTextDocument::METHOD.to_string(),
+            "not a document",
+        )))?;
+
+    // The server is still there, and handles the next notification.
+    client.open(
+        "main",
+        "\
+main: fn
+    br ->
+        does_not_exist
+    end
+end
+",
+    )?;
+    let diagnostics = client.receive_diagnostics()?;
+    assert_eq!(diagnostics.diagnostics.len(), 1);
+
+    client.shutdown()
+}
+
+#[test]
+fn hover_shows_signature_and_comment() -> anyhow::Result<()> {
+    let mut client = TestClient::start_with_modules()?;
+
+    let hover = client
+        .request::<HoverRequest>(HoverParams {
+            text_document_position_params: position("main", 4, 12),
+            work_done_progress_params: Default::default(),
+        })?
+        .expect("Expected hover for call to function");
+
+    let HoverContents::Markup(contents) = hover.contents else {
+        panic!("Expected markup: {hover:#?}");
+    };
+    assert!(contents.value.contains("other/double: S32 -> S32"));
+    assert!(contents.value.contains("Double a number"));
+
+    client.shutdown()
+}
+
+#[test]
+fn go_to_definition_of_function_in_other_module() -> anyhow::Result<()> {
+    let mut client = TestClient::start_with_modules()?;
+
+    let Some(GotoDefinitionResponse::Scalar(location)) =
+        client.request::<GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: position("main", 4, 12),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })?
+    else {
+        panic!("Expected definition of function");
+    };
+
+    assert_eq!(location.uri, uri("other"));
+    assert_eq!(location.range.start, Position::new(1, 0));
+
+    client.shutdown()
+}
+
+#[test]
+fn go_to_definition_of_binding() -> anyhow::Result<()> {
+    let mut client = TestClient::start_with_modules()?;
+
+    let Some(GotoDefinitionResponse::Scalar(location)) =
+        client.request::<GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: position("other", 3, 8),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })?
+    else {
+        panic!("Expected definition of binding");
+    };
+
+    assert_eq!(location.uri, uri("other"));
+    assert_eq!(location.range.start, Position::new(2, 7));
+
+    client.shutdown()
+}
+
+#[test]
+fn complete_intrinsics_host_functions_and_user_functions() -> anyhow::Result<()>
+{
+    let mut client = TestClient::start_with_modules()?;
+
+    let Some(CompletionResponse::Array(items)) =
+        client.request::<Completion>(CompletionParams {
+            text_document_position: position("main", 4, 8),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: None,
+        })?
+    else {
+        panic!("Expected list of completions");
+    };
+
+    let labels = items
+        .iter()
+        .map(|item| item.label.as_str())
+        .collect::<Vec<_>>();
+    for expected in ["add_s32", "submit_frame", "main", "other/double"] {
+        assert!(
+            labels.contains(&expected),
+            "Expected `{expected}` in completions: {labels:?}"
+        );
+    }
+
+    client.shutdown()
+}
+
+struct TestClient {
+    connection: Connection,
+    server: JoinHandle<anyhow::Result<()>>,
+    next_id: i32,
+}
+
+impl TestClient {
+    fn start() -> anyhow::Result<Self> {
+        let (server, client) = Connection::memory();
+        let server = thread::spawn(move || super::server::serve(&server));
+
+        let mut client = Self {
+            connection: client,
+            server,
+            next_id: 0,
+        };
+
+        #[allow(deprecated)] // required field, even if deprecated
+        let params = InitializeParams {
+            root_uri: None,
+            ..InitializeParams::default()
+        };
+        client.request::<Initialize>(params)?;
+        client.notify::<Initialized>(InitializedParams {})?;
+
+        Ok(client)
+    }
+
+    fn start_with_modules() -> anyhow::Result<Self> {
+        let mut client = Self::start()?;
+
+        client.open(
+            "main",
+            "\
+import other
+
+main: fn
+    br ->
+        1 other/double
+    end
+end
+",
+        )?;
+        client.receive_diagnostics()?;
+
+        client.open(
+            "other",
+            "\
+# Double a number
+double: fn
+    br x ->
+        x x add_s32
+    end
+end
+",
+        )?;
+
+        // Opening a module publishes diagnostics for all open modules.
+        client.receive_diagnostics()?;
+        client.receive_diagnostics()?;
+
+        Ok(client)
+    }
+
+    fn open(&mut self, module: &str, text: &str) -> anyhow::Result<()> {
+        self.notify::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri(module),
+                language_id: "capi".to_string(),
+                version: 0,
+                text: text.to_string(),
+            },
+        })
+    }
+
+    fn request<R>(&mut self, params: R::Params) -> anyhow::Result<R::Result>
+    where
+        R: lsp_types::request::Request,
+    {
+        let id = RequestId::from(self.next_id);
+        self.next_id += 1;
+
+        self.connection.sender.send(Message::Request(Request::new(
+            id.clone(),
+            R::METHOD.to_string(),
+            params,
+        )))?;
+
+        match self.connection.receiver.recv()? {
+            Message::Response(response) if response.id == id => {
+                if let Some(err) = response.error {
+                    anyhow::bail!("Error response: {err:?}");
+                }
+
+                let result = response.result.unwrap_or_default();
+                Ok(serde_json::from_value(result)?)
+            }
+            message => {
+                anyhow::bail!("Unexpected message: {message:?}");
+            }
+        }
+    }
+
+    fn notify<N>(&mut self, params: N::Params) -> anyhow::Result<()>
+    where
+        N: lsp_types::notification::Notification,
+    {
+        self.connection.sender.send(Message::Notification(
+            Notification::new(N::METHOD.to_string(), params),
+        ))?;
+
+        Ok(())
+    }
+
+    fn receive_diagnostics(
+        &mut self,
+    ) -> anyhow::Result<PublishDiagnosticsParams> {
+        match self.connection.receiver.recv()? {
+            Message::Notification(notification)
+                if notification.method == PublishDiagnostics::METHOD =>
+            {
+                Ok(serde_json::from_value(notification.params)?)
+            }
+            message => {
+                anyhow::bail!("Unexpected message: {message:?}");
+            }
+        }
+    }
+
+    fn shutdown(mut self) -> anyhow::Result<()> {
+        self.request::<Shutdown>(())?;
+        self.notify::<Exit>(())?;
+
+        self.server
+            .join()
+            .map_err(|_| anyhow::anyhow!("Server thread panicked"))?
+    }
+}
+
+fn position(
+    module: &str,
+    line: u32,
+    character: u32,
+) -> TextDocumentPositionParams {
+    TextDocumentPositionParams {
+        text_document: TextDocumentIdentifier { uri: uri(module) },
+        position: Position { line, character },
+    }
+}
+
+fn uri(module: &str) -> Uri {
+    // This directory doesn't exist, so the server only knows about the modules
+    // that the test opens.
+    format!("file:///crosscut-lsp-test/{module}.capi")
+        .parse()
+        .expect("URI is valid")
+}
@@ -1,9 +1,16 @@
 mod build_game;
 mod cli;
+mod code_client;
+mod debugger_connection;
 mod export;
 mod files;
+mod fmt;
 mod headless;
+mod lsp;
+mod native;
+mod relay_client;
 mod server;
+mod tui;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -0,0 +1,31 @@
+use crosscut_game_engine::input::InputEvent;
+
+/// # The part of the native host that displays frames and provides input
+///
+/// The native host doesn't care how frames end up on the screen, or whether
+/// they end up on one at all. [`Window`] displays them on the desktop, while
+/// `Offscreen` just keeps them in memory, which is what the tests use.
+///
+/// [`Window`]: super::window::Window
+pub trait Backend {
+    /// # Take the events that happened since the last call
+    fn poll_events(&mut self) -> anyhow::Result<Vec<BackendEvent>>;
+
+    /// # Display a frame
+    ///
+    /// The frame has the provided size in pixels (width and height), and four
+    /// bytes (RGBA) per pixel.
+    fn present(
+        &mut self,
+        pixels: &[u8],
+        size: [usize; 2],
+    ) -> anyhow::Result<()>;
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BackendEvent {
+    Input(InputEvent),
+
+    /// # The user wants to stop the game
+    Quit,
+}
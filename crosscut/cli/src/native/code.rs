@@ -0,0 +1,28 @@
+use crosscut_compiler::CompilerOutput;
+use crosscut_game_engine::command::Command;
+use crosscut_protocol::code::InstructionsAtRuntime;
+
+/// # Decides which code the game engine runs
+///
+/// Code with errors is not sent to the game engine, unless there is no code
+/// yet. This is the same behavior as the debugger's.
+#[derive(Default)]
+pub struct CodeAtRuntime {
+    instructions: InstructionsAtRuntime,
+    has_code: bool,
+}
+
+impl CodeAtRuntime {
+    /// # Create the command that updates the game engine's code, if any
+    pub fn on_new_code(&mut self, code: CompilerOutput) -> Option<Command> {
+        if !code.diagnostics.is_empty() && self.has_code {
+            tracing::warn!(
+                "New code has errors. Keeping the previous version running."
+            );
+            return None;
+        }
+
+        self.has_code = true;
+        Some(self.instructions.update(code.instructions))
+    }
+}
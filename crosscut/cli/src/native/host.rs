@@ -0,0 +1,82 @@
+use crosscut_game_engine::{command::Command, game_engine::GameEngine};
+use crosscut_runtime::Effect;
+
+use super::backend::{Backend, BackendEvent};
+
+/// # A host that runs the game engine natively, instead of in the browser
+///
+/// Doesn't care where the frames end up. That is left to the [`Backend`].
+pub struct NativeHost<B> {
+    pub backend: B,
+    pub game_engine: GameEngine,
+    pixels: Vec<u8>,
+
+    /// # The effect that the game was last stopped by
+    ///
+    /// There's no debugger to show this to, so we log it instead. This is
+    /// tracked, so we do that only once per effect.
+    reported_effect: Option<Effect>,
+}
+
+impl<B: Backend> NativeHost<B> {
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            game_engine: GameEngine::new(),
+            pixels: Vec::new(),
+            reported_effect: None,
+        }
+    }
+
+    pub fn on_command(&mut self, command: Command) {
+        let is_code_update = matches!(
+            command,
+            Command::UpdateCode { .. } | Command::PatchInstructions { .. }
+        );
+
+        self.game_engine.on_command(command);
+
+        // Without a debugger, there's no way to continue a game that was
+        // stopped by an effect. Restarting it with the new code is the best we
+        // can do, and gives the developer a chance to see their fix in action.
+        if is_code_update && self.reported_effect.take().is_some() {
+            self.game_engine.on_command(Command::Reset);
+        }
+    }
+
+    /// # Handle events and run a frame, if it's time for that
+    ///
+    /// Returns `false`, if the user wants to stop the game.
+    pub fn step(&mut self, current_time_s: f64) -> anyhow::Result<bool> {
+        for event in self.backend.poll_events()? {
+            match event {
+                BackendEvent::Input(event) => {
+                    self.game_engine.on_input(event);
+                }
+                BackendEvent::Quit => {
+                    return Ok(false);
+                }
+            }
+        }
+
+        if !self
+            .game_engine
+            .run_until_end_of_frame(current_time_s, &mut self.pixels)
+        {
+            // It's not time to run another frame yet.
+            return Ok(true);
+        }
+
+        if let Some(effect) = self.game_engine.runtime.effect().inspect() {
+            if self.reported_effect.as_ref() != Some(effect) {
+                tracing::error!("Game stopped by unhandled effect: {effect:?}");
+                self.reported_effect = Some(*effect);
+            }
+        }
+
+        let size = self.game_engine.resolution().size();
+        self.backend.present(&self.pixels, size)?;
+
+        Ok(true)
+    }
+}
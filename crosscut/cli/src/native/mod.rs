@@ -0,0 +1,12 @@
+mod backend;
+mod code;
+mod host;
+mod start;
+mod window;
+
+pub use self::start::start;
+
+#[cfg(test)]
+mod offscreen;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,30 @@
+use std::{collections::VecDeque, mem};
+
+use super::backend::{Backend, BackendEvent};
+
+/// # A backend that keeps frames in memory, instead of displaying them
+///
+/// Events are not generated by a user, but have to be queued explicitly.
+#[derive(Debug, Default)]
+pub struct Offscreen {
+    /// # The events that are returned by the next call to `poll_events`
+    pub events: VecDeque<BackendEvent>,
+
+    /// # The last frame that was presented, if any
+    pub frame: Option<Vec<u8>>,
+
+    /// # The number of frames that have been presented
+    pub num_frames: u64,
+}
+
+impl Backend for Offscreen {
+    fn poll_events(&mut self) -> anyhow::Result<Vec<BackendEvent>> {
+        Ok(mem::take(&mut self.events).into())
+    }
+
+    fn present(&mut self, pixels: &[u8], _: [usize; 2]) -> anyhow::Result<()> {
+        self.frame = Some(pixels.to_vec());
+        self.num_frames += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use std::{
+    net::SocketAddr,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use crosscut_game_engine::command::Command;
+use rand::random;
+use tokio::{sync::mpsc, task};
+
+use crate::code_client::fetch_code;
+
+use super::{code::CodeAtRuntime, host::NativeHost, window::Window};
+
+/// # Run the game in a window, with code from the development server
+///
+/// ## Implementation Note
+///
+/// Some platforms require windows to be handled on the main thread. This
+/// function blocks, so it must be awaited directly from `main`. Since the async
+/// runtime is multi-threaded, tasks spawned from here still make progress.
+pub async fn start(address: SocketAddr) -> anyhow::Result<()> {
+    let (code_tx, mut code_rx) = mpsc::unbounded_channel();
+    let code = task::spawn(fetch_code(address, code_tx));
+    let mut code_at_runtime = CodeAtRuntime::default();
+
+    let mut host = NativeHost::new(Window::new()?);
+    host.on_command(Command::SetSeed { seed: random() });
+
+    let start_of_game = Instant::now();
+
+    loop {
+        while let Ok(new_code) = code_rx.try_recv() {
+            if let Some(command) = code_at_runtime.on_new_code(new_code) {
+                host.on_command(command);
+            }
+        }
+
+        if code.is_finished() {
+            // This only happens, if fetching the code failed.
+            return code
+                .await?
+                .with_context(|| format!("Lost connection to `{address}`"));
+        }
+
+        if !host.step(start_of_game.elapsed().as_secs_f64())? {
+            break;
+        }
+
+        // Frames are far enough apart, that this doesn't make us miss any. But
+        // it keeps us from burning the CPU while waiting for the next one.
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    Ok(())
+}
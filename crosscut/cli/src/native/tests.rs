@@ -0,0 +1,70 @@
+use crosscut_compiler::Compiler;
+use crosscut_game_engine::{
+    command::Command,
+    host::GameEngineHost,
+    input::{InputEvent, Key},
+};
+
+use super::{backend::BackendEvent, host::NativeHost, offscreen::Offscreen};
+
+#[test]
+fn render_frames_and_forward_input() -> anyhow::Result<()> {
+    let mut host = NativeHost::new(Offscreen::default());
+    host.on_command(Command::UpdateCode {
+        instructions: Compiler::default()
+            .compile(
+                r"
+                    main: fn
+                        br size_x, size_y ->
+                            draw
+                        end
+                    end
+
+                    draw: fn
+                        br ->
+                            1 is_key_held
+                            fn
+                                br 0 ->
+                                    0 0 32 32 255 0 0 255 fill_rect
+                                end
+                                br _ ->
+                                    0 0 32 32 0 0 255 255 fill_rect
+                                end
+                            end
+                            eval
+                            submit_frame
+                            draw
+                        end
+                    end
+                ",
+                &GameEngineHost,
+            )
+            .instructions,
+    });
+
+    assert!(host.step(0.)?);
+    assert_eq!(host.backend.num_frames, 1);
+    assert_eq!(first_pixel(&host), [255, 0, 0, 255]);
+
+    // Not enough time has passed for another frame.
+    assert!(host.step(0.01)?);
+    assert_eq!(host.backend.num_frames, 1);
+
+    host.backend
+        .events
+        .push_back(BackendEvent::Input(InputEvent::KeyDown(Key::Up)));
+    assert!(host.step(0.1)?);
+    assert_eq!(host.backend.num_frames, 2);
+    assert_eq!(first_pixel(&host), [0, 0, 255, 255]);
+
+    host.backend.events.push_back(BackendEvent::Quit);
+    assert!(!host.step(0.2)?);
+    assert_eq!(host.backend.num_frames, 2);
+
+    Ok(())
+}
+
+fn first_pixel(host: &NativeHost<Offscreen>) -> [u8; 4] {
+    let frame = host.backend.frame.as_ref().unwrap();
+    [frame[0], frame[1], frame[2], frame[3]]
+}
@@ -0,0 +1,208 @@
+use std::{mem, num::NonZeroU32, rc::Rc, time::Duration};
+
+use anyhow::anyhow;
+use crosscut_game_engine::{
+    display::NUM_CHANNELS,
+    input::{InputEvent, Key},
+};
+use winit::{
+    application::ApplicationHandler,
+    dpi::LogicalSize,
+    event::{ElementState, KeyEvent, WindowEvent},
+    event_loop::{ActiveEventLoop, EventLoop},
+    keyboard::{KeyCode, PhysicalKey},
+    platform::pump_events::{EventLoopExtPumpEvents, PumpStatus},
+    window::{Window as WinitWindow, WindowId},
+};
+
+use super::backend::{Backend, BackendEvent};
+
+/// # A backend that displays frames in a window on the desktop
+///
+/// ## Implementation Note
+///
+/// winit wants to be in control of the main loop, but the native host has its
+/// own. We pump winit's events from there instead, which isn't supported on
+/// every platform, but on all desktop platforms we care about.
+pub struct Window {
+    event_loop: EventLoop<()>,
+    app: App,
+}
+
+impl Window {
+    pub fn new() -> anyhow::Result<Self> {
+        let event_loop = EventLoop::new()?;
+
+        Ok(Self {
+            event_loop,
+            app: App::default(),
+        })
+    }
+}
+
+impl Backend for Window {
+    fn poll_events(&mut self) -> anyhow::Result<Vec<BackendEvent>> {
+        let status = self
+            .event_loop
+            .pump_app_events(Some(Duration::ZERO), &mut self.app);
+
+        if let Some(err) = self.app.error.take() {
+            return Err(err);
+        }
+        if let PumpStatus::Exit(_) = status {
+            self.app.events.push(BackendEvent::Quit);
+        }
+
+        Ok(mem::take(&mut self.app.events))
+    }
+
+    fn present(
+        &mut self,
+        pixels: &[u8],
+        [frame_width, frame_height]: [usize; 2],
+    ) -> anyhow::Result<()> {
+        let (Some(window), Some(surface)) =
+            (&self.app.window, &mut self.app.surface)
+        else {
+            // The window hasn't been created yet. Nothing to present to.
+            return Ok(());
+        };
+
+        let size = window.inner_size();
+        let (Some(width), Some(height)) =
+            (NonZeroU32::new(size.width), NonZeroU32::new(size.height))
+        else {
+            // The window is minimized.
+            return Ok(());
+        };
+
+        surface
+            .resize(width, height)
+            .map_err(|err| anyhow!("Failed to resize surface: {err}"))?;
+        let mut buffer = surface
+            .buffer_mut()
+            .map_err(|err| anyhow!("Failed to access surface: {err}"))?;
+
+        // Scale the frame to the size of the window, picking the nearest pixel.
+        // The game's pixels are already scaled up to fill the frame, so there's
+        // no point in doing anything smarter.
+        let width = width.get() as usize;
+        let height = height.get() as usize;
+        for (i, target) in buffer.iter_mut().enumerate() {
+            let x = i % width * frame_width / width;
+            let y = i / width * frame_height / height;
+
+            let offset = (y * frame_width + x) * NUM_CHANNELS;
+            let [r, g, b] = [0, 1, 2].map(|i| u32::from(pixels[offset + i]));
+
+            // softbuffer expects `0RGB`, one `u32` per pixel.
+            *target = (r << 16) | (g << 8) | b;
+        }
+
+        buffer
+            .present()
+            .map_err(|err| anyhow!("Failed to present frame: {err}"))?;
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct App {
+    window: Option<Rc<WinitWindow>>,
+    surface: Option<softbuffer::Surface<Rc<WinitWindow>, Rc<WinitWindow>>>,
+    events: Vec<BackendEvent>,
+
+    /// # An error that happened while handling winit's events
+    ///
+    /// Those are handled in callbacks that can't return errors, so we need to
+    /// keep them here until we're back in control.
+    error: Option<anyhow::Error>,
+}
+
+impl App {
+    fn create_window(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+    ) -> anyhow::Result<()> {
+        let attributes = WinitWindow::default_attributes()
+            .with_title("Crosscut")
+            .with_inner_size(LogicalSize::new(768, 768));
+        let window = Rc::new(event_loop.create_window(attributes)?);
+
+        let context = softbuffer::Context::new(window.clone())
+            .map_err(|err| anyhow!("Failed to create context: {err}"))?;
+        let surface = softbuffer::Surface::new(&context, window.clone())
+            .map_err(|err| anyhow!("Failed to create surface: {err}"))?;
+
+        self.window = Some(window);
+        self.surface = Some(surface);
+
+        Ok(())
+    }
+}
+
+impl ApplicationHandler for App {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.window.is_some() {
+            return;
+        }
+
+        if let Err(err) = self.create_window(event_loop) {
+            self.error = Some(err);
+        }
+    }
+
+    fn window_event(
+        &mut self,
+        _: &ActiveEventLoop,
+        _: WindowId,
+        event: WindowEvent,
+    ) {
+        match event {
+            WindowEvent::CloseRequested => {
+                self.events.push(BackendEvent::Quit);
+            }
+            WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        physical_key: PhysicalKey::Code(code),
+                        state,
+                        repeat: false,
+                        ..
+                    },
+                ..
+            } => {
+                let Some(key) = key(code) else {
+                    return;
+                };
+
+                let event = match state {
+                    ElementState::Pressed => InputEvent::KeyDown(key),
+                    ElementState::Released => InputEvent::KeyUp(key),
+                };
+
+                self.events.push(BackendEvent::Input(event));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// # Map a key on the keyboard to a key that games know about
+///
+/// This uses the same alternative keys as the browser host.
+fn key(code: KeyCode) -> Option<Key> {
+    let key = match code {
+        KeyCode::ArrowUp | KeyCode::KeyW => Key::Up,
+        KeyCode::ArrowLeft | KeyCode::KeyA => Key::Left,
+        KeyCode::ArrowDown | KeyCode::KeyS => Key::Down,
+        KeyCode::ArrowRight | KeyCode::KeyD => Key::Right,
+        KeyCode::Space => Key::Space,
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Escape => Key::Escape,
+        _ => return None,
+    };
+
+    Some(key)
+}
@@ -0,0 +1,76 @@
+use std::net::SocketAddr;
+
+use anyhow::Context;
+use futures_util::{SinkExt, StreamExt};
+use tokio::{select, sync::mpsc, task};
+use tokio_tungstenite::tungstenite::Message;
+
+pub type MessagesTx = mpsc::UnboundedSender<Vec<u8>>;
+pub type MessagesRx = mpsc::UnboundedReceiver<Vec<u8>>;
+
+/// # The side of the server's relay that a client connects to
+pub enum Peer {
+    Game,
+    Debugger,
+}
+
+/// # Connect to the server's relay
+///
+/// Returns a channel to send messages to the other side, and a channel that
+/// receives the messages from there. If the connection is lost, the receiving
+/// channel closes, and sent messages are dropped.
+pub async fn connect(
+    address: SocketAddr,
+    peer: Peer,
+) -> anyhow::Result<(MessagesTx, MessagesRx)> {
+    let path = match peer {
+        Peer::Game => "game",
+        Peer::Debugger => "debugger",
+    };
+    let url = format!("ws://{address}/relay/{path}");
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(&url)
+        .await
+        .with_context(|| format!("Failed to connect to `{url}`"))?;
+
+    let (incoming_tx, incoming_rx) = mpsc::unbounded_channel();
+    let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel::<Vec<u8>>();
+
+    task::spawn(async move {
+        loop {
+            select! {
+                message = socket.next() => {
+                    let message = match message {
+                        Some(Ok(Message::Binary(message))) => message,
+                        Some(Ok(Message::Close(_)) | Err(_)) | None => {
+                            tracing::warn!("Lost connection to `{url}`.");
+                            break;
+                        }
+                        Some(Ok(_)) => {
+                            continue;
+                        }
+                    };
+
+                    if incoming_tx.send(message.to_vec()).is_err() {
+                        // The client has stopped.
+                        break;
+                    }
+                }
+                message = outgoing_rx.recv() => {
+                    let Some(message) = message else {
+                        // The client has stopped.
+                        break;
+                    };
+
+                    let message = Message::Binary(message.into());
+                    if socket.send(message).await.is_err() {
+                        tracing::warn!("Lost connection to `{url}`.");
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    Ok((outgoing_tx, incoming_rx))
+}
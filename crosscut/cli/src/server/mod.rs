@@ -1,3 +1,4 @@
+mod relay;
 mod server;
 mod start;
 
@@ -0,0 +1,222 @@
+use std::sync::{Arc, Mutex};
+
+use axum::extract::ws::{Message, WebSocket};
+use crosscut_protocol::{
+    command::SerializedCommandToRuntime,
+    updates::{SerializedUpdate, UpdateFromHost},
+};
+use tokio::{select, sync::broadcast};
+use tracing::warn;
+
+/// # The number of messages that can be queued for a slow peer
+///
+/// If a peer falls further behind than that, it misses messages.
+const CAPACITY: usize = 1024;
+
+/// # Relays messages between a game and the debuggers attached to it
+///
+/// This allows the debugger to attach to a game that doesn't run in the same
+/// page, for example one that runs in `crosscut headless`. Commands from any
+/// debugger go to the game, updates from the game go to all debuggers.
+///
+/// The relay doesn't interpret any messages it forwards, except to remember
+/// the latest updates about the game's state. Those are sent to debuggers that
+/// attach to a running game, which would otherwise only learn about the game's
+/// state once it changes.
+///
+/// Debuggers patch the game's code, based on what they think the game runs.
+/// With more than one debugger, or with messages lost to a slow peer, that can
+/// be wrong. The game rejects patches that don't fit its code, but to keep that
+/// from happening needlessly, the relay asks each debugger that attaches, or
+/// that might have missed messages, to send its full code.
+#[derive(Clone, Debug)]
+pub struct Relay {
+    commands: broadcast::Sender<SerializedCommandToRuntime>,
+    updates: broadcast::Sender<SerializedUpdate>,
+    latest: Arc<Mutex<LatestUpdates>>,
+}
+
+impl Relay {
+    pub fn new() -> Self {
+        let (commands, _) = broadcast::channel(CAPACITY);
+        let (updates, _) = broadcast::channel(CAPACITY);
+
+        Self {
+            commands,
+            updates,
+            latest: Arc::default(),
+        }
+    }
+
+    pub async fn connect_game(self, mut socket: WebSocket) {
+        // Whatever we know about a previous game, doesn't apply to this one.
+        *self.latest.lock().unwrap() = LatestUpdates::default();
+
+        let mut commands = self.commands.subscribe();
+
+        loop {
+            select! {
+                message = socket.recv() => {
+                    let Some(update) = binary(message) else {
+                        break;
+                    };
+                    if update.is_empty() {
+                        continue;
+                    }
+                    self.on_update(update);
+                }
+                command = commands.recv() => {
+                    let command = match command {
+                        Ok(command) => command,
+                        Err(broadcast::error::RecvError::Lagged(num)) => {
+                            warn!("Game missed {num} commands.");
+
+                            // Any of the missed commands could have been a
+                            // patch. Without it, the game's code no longer
+                            // matches what any debugger thinks it is.
+                            let _ = self.updates.send(resend_code());
+                            continue;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => {
+                            break;
+                        }
+                    };
+
+                    let command = Message::Binary(command.into());
+                    if socket.send(command).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    pub async fn connect_debugger(self, mut socket: WebSocket) {
+        // Subscribe while holding the lock, so no update can slip between the
+        // ones we remember and the ones we receive.
+        let (latest, mut updates) = {
+            let latest = self.latest.lock().unwrap();
+            (latest.all(), self.updates.subscribe())
+        };
+
+        // The game might be running code that another debugger sent. This
+        // debugger needs to send its own, before it can patch that.
+        for update in latest.into_iter().chain([resend_code()]) {
+            if socket.send(Message::Binary(update.into())).await.is_err() {
+                return;
+            }
+        }
+
+        loop {
+            select! {
+                message = socket.recv() => {
+                    let Some(command) = binary(message) else {
+                        break;
+                    };
+                    if command.is_empty() {
+                        continue;
+                    }
+
+                    // It's fine, if there's no game to receive this right now.
+                    // The debugger can't expect an answer then.
+                    let _ = self.commands.send(command);
+                }
+                update = updates.recv() => {
+                    let update = match update {
+                        Ok(update) => update,
+                        Err(broadcast::error::RecvError::Lagged(num)) => {
+                            // Any of the missed updates could have been a
+                            // rejected patch.
+                            warn!("Debugger missed {num} updates.");
+                            resend_code()
+                        }
+                        Err(broadcast::error::RecvError::Closed) => {
+                            break;
+                        }
+                    };
+
+                    let update = Message::Binary(update.into());
+                    if socket.send(update).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn on_update(&self, update: SerializedUpdate) {
+        let mut latest = self.latest.lock().unwrap();
+
+        match UpdateFromHost::deserialize(update.clone()) {
+            Ok(UpdateFromHost::State { .. }) => {
+                latest.state = Some(update.clone());
+            }
+            Ok(UpdateFromHost::Memory { .. }) => {
+                latest.memory = Some(update.clone());
+            }
+            Ok(UpdateFromHost::Seed { .. }) => {
+                latest.seed = Some(update.clone());
+            }
+            Ok(UpdateFromHost::ResendCode) => {
+                // We don't know which debugger sent the patch that the game
+                // rejected, so this goes to all of them. But only those that
+                // are attached right now. Nothing to remember.
+            }
+            Ok(UpdateFromHost::Snapshot { .. }) => {
+                // Only the debugger that requested this snapshot cares about
+                // it. Nothing to remember.
+            }
+            Err(err) => {
+                // The debugger is going to complain about this too. We can
+                // still forward it.
+                warn!("Relaying malformed update: {err}");
+            }
+        }
+
+        // It's fine, if no debugger is attached to receive this.
+        let _ = self.updates.send(update);
+    }
+}
+
+impl Default for Relay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Default)]
+struct LatestUpdates {
+    state: Option<SerializedUpdate>,
+    memory: Option<SerializedUpdate>,
+    seed: Option<SerializedUpdate>,
+}
+
+impl LatestUpdates {
+    fn all(&self) -> Vec<SerializedUpdate> {
+        // The seed doesn't depend on anything else, but the memory should
+        // arrive after the state, just like it does from the game.
+        [&self.seed, &self.state, &self.memory]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+}
+
+fn resend_code() -> SerializedUpdate {
+    UpdateFromHost::ResendCode.serialize()
+}
+
+/// # Extract the payload of a binary message
+///
+/// Returns `None`, if the connection has been closed. Other kinds of messages
+/// don't carry anything that is relevant to the relay. For those, this returns
+/// an empty payload, which callers skip. No valid message is empty, as they all
+/// start with the protocol version.
+fn binary(message: Option<Result<Message, axum::Error>>) -> Option<Vec<u8>> {
+    match message? {
+        Ok(Message::Binary(payload)) => Some(payload.to_vec()),
+        Ok(Message::Close(_)) | Err(_) => None,
+        Ok(_) => Some(Vec::new()),
+    }
+}
@@ -1,4 +1,4 @@
-use std::{future, net::SocketAddr, path::PathBuf};
+use std::{collections::VecDeque, future, net::SocketAddr, path::PathBuf};
 
 use axum::{
     extract::{ws::WebSocket, Path, State, WebSocketUpgrade},
@@ -8,7 +8,7 @@ use axum::{
     Router,
 };
 use crosscut_compiler::CompilerOutput;
-use crosscut_protocol::{ron_options, Versioned};
+use crosscut_protocol::{code::CodeUpdate, wire, Versioned};
 use tokio::{
     net::TcpListener,
     sync::{oneshot, watch},
@@ -18,16 +18,48 @@ use tracing::error;
 
 use crate::files::FILES;
 
+use super::relay::Relay;
+
 pub type Code = Versioned<CompilerOutput>;
 
-pub type CodeTx = watch::Sender<Code>;
-type CodeRx = watch::Receiver<Code>;
+/// # The number of versions of the code that the server keeps around
+///
+/// Clients that have one of those get the changes since then. All others get
+/// the full code.
+const CODE_VERSIONS: usize = 16;
+
+type CodeRx = watch::Receiver<VecDeque<Code>>;
+
+pub struct CodeTx {
+    inner: watch::Sender<VecDeque<Code>>,
+}
+
+impl CodeTx {
+    pub fn send(&self, code: Code) -> Result<(), ServerStopped> {
+        if self.inner.is_closed() {
+            return Err(ServerStopped);
+        }
+
+        self.inner.send_modify(|versions| {
+            if versions.len() >= CODE_VERSIONS {
+                versions.pop_front();
+            }
+            versions.push_back(code);
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ServerStopped;
 
 type ReadyTx = oneshot::Sender<()>;
 pub type ReadyRx = oneshot::Receiver<()>;
 
 pub fn start(address: SocketAddr, code: Code) -> (ReadyRx, CodeTx) {
-    let (code_tx, code_rx) = watch::channel(code);
+    let (code_tx, code_rx) = watch::channel(VecDeque::from([code]));
+    let code_tx = CodeTx { inner: code_tx };
     let (ready_tx, ready_rx) = oneshot::channel();
 
     task::spawn(async move {
@@ -52,9 +84,14 @@ async fn start_inner(
         .route("/wait-while-alive", get(serve_wait_while_alive))
         .route("/code", get(serve_code))
         .route("/code/{timestamp}", get(serve_code))
+        .route("/relay/game", get(serve_relay_game))
+        .route("/relay/debugger", get(serve_relay_debugger))
         .route("/", get(serve_index))
         .route("/{*path}", get(serve_static))
-        .with_state(ServerState { code });
+        .with_state(ServerState {
+            code,
+            relay: Relay::new(),
+        });
 
     let listener = TcpListener::bind(address).await?;
 
@@ -71,6 +108,7 @@ async fn start_inner(
 #[derive(Clone, Debug)]
 pub struct ServerState {
     code: CodeRx,
+    relay: Relay,
 }
 
 async fn serve_is_alive() -> StatusCode {
@@ -91,7 +129,7 @@ async fn serve_code(
 ) -> impl IntoResponse {
     loop {
         if let Some(timestamp) = &timestamp {
-            if timestamp.0 >= state.code.borrow().timestamp {
+            if timestamp.0 >= latest(&state.code.borrow()).timestamp {
                 if state.code.changed().await.is_err() {
                     // Sender has been dropped.
                     return StatusCode::INTERNAL_SERVER_ERROR.into_response();
@@ -100,21 +138,37 @@ async fn serve_code(
             }
         }
 
-        let code = &*state.code.borrow();
-        let code = Versioned {
-            timestamp: code.timestamp,
-            inner: &code.inner,
-        };
-
-        return ron_options()
-            .to_string(&code)
-            .unwrap()
-            .as_bytes()
-            .to_vec()
-            .into_response();
+        let versions = &*state.code.borrow();
+        let base = timestamp.and_then(|Path(timestamp)| {
+            versions.iter().find(|code| code.timestamp == timestamp)
+        });
+        let update = CodeUpdate::new(base, latest(versions));
+
+        return wire::encode(&update).into_response();
     }
 }
 
+async fn serve_relay_game(
+    State(state): State<ServerState>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    ws.on_upgrade(|socket| state.relay.connect_game(socket))
+}
+
+async fn serve_relay_debugger(
+    State(state): State<ServerState>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    ws.on_upgrade(|socket| state.relay.connect_debugger(socket))
+}
+
+fn latest(versions: &VecDeque<Code>) -> &Code {
+    versions.back().expect(
+        "Server starts with one version of the code and never removes the \
+            last one.",
+    )
+}
+
 async fn serve_index() -> impl IntoResponse {
     make_file_response(PathBuf::from("index-debugger.html")).await
 }
@@ -41,8 +41,8 @@ async fn start_inner(
     address: SocketAddr,
     events: EventsTx,
 ) -> anyhow::Result<()> {
-    let watcher =
-        Watcher::new(&games_path).context("Creating watcher for game")?;
+    let watcher = Watcher::with_filter(&games_path, build_game::is_source_file)
+        .context("Creating watcher for game")?;
     let mut build_events =
         build_and_watch_game(games_path.join("snake"), watcher.changes);
 
@@ -58,7 +58,7 @@ async fn start_inner(
 
                 match server_task {
                     ServerTask::Uninitialized { address } => {
-                        let (ready_rx, code_tx) = server::start(address, code);
+                        let (ready_rx, code_tx) = server::start(address, *code);
 
                         ready_rx.await?;
                         events.send(Event::ServerReady).await?;
@@ -66,7 +66,7 @@ async fn start_inner(
                         server_task = ServerTask::Initialized { code_tx };
                     }
                     ServerTask::Initialized { code_tx } => {
-                        if code_tx.send(code).is_err() {
+                        if code_tx.send(*code).is_err() {
                             return Ok(());
                         }
                         server_task = ServerTask::Initialized { code_tx };
@@ -1,4 +1,15 @@
-use std::path::PathBuf;
+use std::{net::SocketAddr, path::PathBuf};
+
+use crosscut_compiler::Instructions;
+use crosscut_game_engine::command::Command;
+use crosscut_protocol::{
+    code::CodeUpdate, command::CommandExt, updates::UpdateFromHost, wire,
+};
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    tungstenite::Message, MaybeTlsStream, WebSocketStream,
+};
 
 use super::Event;
 
@@ -18,7 +29,120 @@ async fn basic_build() -> anyhow::Result<()> {
         }
     }
 
-    reqwest::get("http://[::1]:34481/code").await?;
+    let code = reqwest::get("http://[::1]:34481/code")
+        .await?
+        .bytes()
+        .await?;
+    let code: CodeUpdate = wire::decode(&code)?;
+    assert!(matches!(code, CodeUpdate::Full { .. }));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn relay_between_game_and_debugger() -> anyhow::Result<()> {
+    let games_dir = PathBuf::from("../../games");
+    let address = "[::1]:34483".parse()?;
+
+    let mut events = crate::server::start(games_dir, address).await?;
+
+    // Wait for server to be ready.
+    while let Some(event) = events.recv().await {
+        if let Event::ServerReady = event {
+            break;
+        }
+    }
+
+    let mut game = connect(address, "game").await?;
+    let mut debugger = connect(address, "debugger").await?;
+    assert_eq!(receive(&mut debugger).await?, resend_code());
+
+    let update = UpdateFromHost::Seed { seed: 3 }.serialize();
+    game.send(Message::Binary(update.clone().into())).await?;
+    assert_eq!(receive(&mut debugger).await?, update);
+
+    // A debugger that attaches later, still learns about the game's state.
+    let mut late_debugger = connect(address, "debugger").await?;
+    assert_eq!(receive(&mut late_debugger).await?, update);
+    assert_eq!(receive(&mut late_debugger).await?, resend_code());
+
+    let command = Command::Reset.serialize();
+    late_debugger
+        .send(Message::Binary(command.clone().into()))
+        .await?;
+    assert_eq!(receive(&mut game).await?, command);
 
     Ok(())
 }
+
+#[tokio::test]
+async fn resync_debuggers_that_share_a_game() -> anyhow::Result<()> {
+    let games_dir = PathBuf::from("../../games");
+    let address = "[::1]:34485".parse()?;
+
+    let mut events = crate::server::start(games_dir, address).await?;
+
+    // Wait for server to be ready.
+    while let Some(event) = events.recv().await {
+        if let Event::ServerReady = event {
+            break;
+        }
+    }
+
+    let mut game = connect(address, "game").await?;
+
+    // Whatever code the game is running, a debugger that attaches doesn't know
+    // about it. It must send its full code, before it can send patches.
+    let mut debugger_a = connect(address, "debugger").await?;
+    assert_eq!(receive(&mut debugger_a).await?, resend_code());
+    let mut debugger_b = connect(address, "debugger").await?;
+    assert_eq!(receive(&mut debugger_b).await?, resend_code());
+
+    // Both debuggers can control the game.
+    let command = Command::UpdateCode {
+        instructions: Instructions::default(),
+    }
+    .serialize();
+    debugger_a
+        .send(Message::Binary(command.clone().into()))
+        .await?;
+    assert_eq!(receive(&mut game).await?, command);
+
+    let command = Command::Reset.serialize();
+    debugger_b
+        .send(Message::Binary(command.clone().into()))
+        .await?;
+    assert_eq!(receive(&mut game).await?, command);
+
+    // If the game rejects a patch, it can't know which debugger sent it. All
+    // of them need to send their full code.
+    game.send(Message::Binary(resend_code().into())).await?;
+    assert_eq!(receive(&mut debugger_a).await?, resend_code());
+    assert_eq!(receive(&mut debugger_b).await?, resend_code());
+
+    Ok(())
+}
+
+fn resend_code() -> Vec<u8> {
+    UpdateFromHost::ResendCode.serialize()
+}
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn connect(address: SocketAddr, peer: &str) -> anyhow::Result<Socket> {
+    let url = format!("ws://{address}/relay/{peer}");
+    let (socket, _) = tokio_tungstenite::connect_async(url).await?;
+    Ok(socket)
+}
+
+async fn receive(socket: &mut Socket) -> anyhow::Result<Vec<u8>> {
+    loop {
+        let Some(message) = socket.next().await else {
+            anyhow::bail!("Connection closed unexpectedly.");
+        };
+
+        if let Message::Binary(payload) = message? {
+            return Ok(payload.to_vec());
+        }
+    }
+}
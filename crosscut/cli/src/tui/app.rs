@@ -0,0 +1,186 @@
+use crosscut_compiler::{code::syntax::MemberLocation, CompilerOutput};
+use crosscut_debugger::model::{PersistentState, TransientState, UserAction};
+use crosscut_game_engine::command::Command;
+use crosscut_protocol::updates::{SerializedUpdate, UpdateFromHost};
+use ratatui::crossterm::event::KeyCode;
+
+use super::render::{code_lines, CodeLine};
+
+/// # The state of the terminal debugger
+///
+/// Wraps the same model that the browser debugger uses, and adds what's
+/// specific to the terminal: Expressions can't be clicked here, so there's a
+/// selection that breakpoint actions apply to.
+pub struct App {
+    pub persistent: PersistentState,
+    pub transient: TransientState,
+
+    /// # The expression that was selected by the user
+    ///
+    /// If this is `None`, or the expression is not displayed, the innermost
+    /// active expression is selected instead.
+    pub selected: Option<MemberLocation>,
+
+    /// # The error that resulted from the last action, if any
+    pub error: Option<String>,
+}
+
+impl App {
+    pub fn new() -> Self {
+        let persistent = PersistentState::default();
+        let transient = persistent.generate_transient_state();
+
+        Self {
+            persistent,
+            transient,
+            selected: None,
+            error: None,
+        }
+    }
+
+    pub fn on_new_code(&mut self, code: CompilerOutput) -> Option<Command> {
+        let command = self.persistent.on_new_code(code);
+        self.transient = self.persistent.generate_transient_state();
+        command
+    }
+
+    /// # Handle an update from the game
+    ///
+    /// Returns a command for the game, if the update requires one.
+    pub fn on_update(&mut self, update: SerializedUpdate) -> Option<Command> {
+        let update = match UpdateFromHost::deserialize(update) {
+            Ok(update) => update,
+            Err(err) => {
+                self.error =
+                    Some(format!("Ignoring malformed update from game: {err}"));
+                self.persistent.on_decode_error(&err);
+                return None;
+            }
+        };
+
+        if let UpdateFromHost::State { .. } = update {
+            // The game has stopped somewhere else. Follow it there.
+            self.selected = None;
+        }
+
+        let command = self.persistent.on_update_from_host(update);
+        self.transient = self.persistent.generate_transient_state();
+        command
+    }
+
+    /// # Handle a key press
+    ///
+    /// Returns the commands for the game, or `None`, if the user wants to quit.
+    pub fn on_key(&mut self, key: KeyCode) -> Option<Vec<Command>> {
+        let action = match key {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                return None;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.move_selection(-1);
+                return Some(Vec::new());
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.move_selection(1);
+                return Some(Vec::new());
+            }
+            KeyCode::Char('b') => {
+                let mut lines = self.code_lines();
+                let Some(data) = self
+                    .selected_line(&lines)
+                    .and_then(|index| lines.swap_remove(index).expression)
+                else {
+                    return Some(Vec::new());
+                };
+
+                let expression = data.location;
+                if data.has_durable_breakpoint {
+                    UserAction::BreakpointClear { expression }
+                } else {
+                    UserAction::BreakpointSet { expression }
+                }
+            }
+            KeyCode::Char('c') => UserAction::Continue,
+            KeyCode::Char('s') => UserAction::Stop,
+            KeyCode::Char('i') => UserAction::StepIn,
+            KeyCode::Char('o') => UserAction::StepOver,
+            KeyCode::Char('u') => UserAction::StepOut,
+            KeyCode::Char('r') => UserAction::Reset,
+            KeyCode::PageUp => UserAction::ShowMemoryPage {
+                page: self.persistent.memory_page.saturating_sub(1),
+            },
+            KeyCode::PageDown => UserAction::ShowMemoryPage {
+                page: self.persistent.memory_page + 1,
+            },
+            _ => {
+                return Some(Vec::new());
+            }
+        };
+
+        match self.persistent.on_user_action(action, &self.transient) {
+            Ok(commands) => {
+                self.error = None;
+                self.transient = self.persistent.generate_transient_state();
+                Some(commands)
+            }
+            Err(err) => {
+                self.error = Some(err.to_string());
+                Some(Vec::new())
+            }
+        }
+    }
+
+    pub fn code_lines(&self) -> Vec<CodeLine> {
+        code_lines(&self.transient)
+    }
+
+    /// # The index of the selected line within the provided lines
+    pub fn selected_line(&self, lines: &[CodeLine]) -> Option<usize> {
+        let selected = self.selected.as_ref().and_then(|selected| {
+            lines.iter().position(|line| {
+                line.expression
+                    .as_ref()
+                    .is_some_and(|data| data.location == *selected)
+            })
+        });
+
+        selected.or_else(|| {
+            lines.iter().position(|line| {
+                line.expression.as_ref().is_some_and(|data| {
+                    data.state.is_innermost_active_expression()
+                })
+            })
+        })
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        let lines = self.code_lines();
+        let expressions = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                line.expression.as_ref().map(|data| (index, data))
+            })
+            .collect::<Vec<_>>();
+
+        let current = self.selected_line(&lines).and_then(|selected| {
+            expressions.iter().position(|(index, _)| *index == selected)
+        });
+        let next = match current {
+            Some(current) => current
+                .saturating_add_signed(offset)
+                .min(expressions.len().saturating_sub(1)),
+            None => 0,
+        };
+
+        if let Some((_, data)) = expressions.get(next) {
+            self.selected = Some(data.location.clone());
+        }
+    }
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
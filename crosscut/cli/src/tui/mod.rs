@@ -0,0 +1,8 @@
+mod app;
+mod render;
+mod start;
+
+pub use self::start::start;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,401 @@
+use crosscut_debugger::model::{
+    ActiveFunctions, ActiveFunctionsEntry, DebugFunction, DebugMember,
+    DebugMemberData, DebugMemberKind, DebugParameter, TransientState,
+    MEMORY_PAGE_SIZE,
+};
+use crosscut_protocol::host_state::HostState;
+use crosscut_runtime::Effect;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use super::app::App;
+
+/// # The number of bytes in each line of the memory dump
+const BYTES_PER_LINE: usize = 16;
+
+/// # The width of the memory dump, including its border
+///
+/// Each line has an address, and a right-aligned column for each byte.
+const MEMORY_WIDTH: u16 = 2 + 8 + 4 * BYTES_PER_LINE as u16;
+
+const KEYS: [(&str, &str); 10] = [
+    ("c", "Continue"),
+    ("s", "Stop"),
+    ("i", "Step In"),
+    ("o", "Step Over"),
+    ("u", "Step Out"),
+    ("b", "Breakpoint"),
+    ("r", "Reset"),
+    ("↑↓", "Select"),
+    ("PgUp/PgDn", "Memory"),
+    ("q", "Quit"),
+];
+
+pub fn render(app: &App, frame: &mut Frame) {
+    let [main, help] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)])
+            .areas(frame.area());
+    let [code, side] = Layout::horizontal([
+        Constraint::Min(0),
+        Constraint::Length(MEMORY_WIDTH),
+    ])
+    .areas(main);
+    let [status, stack, memory] = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(6),
+        Constraint::Length(2 + (MEMORY_PAGE_SIZE / BYTES_PER_LINE) as u16),
+    ])
+    .areas(side);
+
+    render_code(app, frame, code);
+    render_status(app, frame, status);
+    render_stack(app, frame, stack);
+    render_memory(app, frame, memory);
+
+    let keys = KEYS
+        .into_iter()
+        .flat_map(|(key, action)| {
+            [key.bold(), Span::raw(format!(" {action}  "))]
+        })
+        .collect::<Line>();
+    frame.render_widget(keys, help);
+}
+
+/// # A line of code, as displayed in the active functions
+pub struct CodeLine {
+    pub line: Line<'static>,
+
+    /// # The expression on this line, if it can have a breakpoint
+    pub expression: Option<DebugMemberData>,
+}
+
+impl CodeLine {
+    fn text(line: impl Into<Line<'static>>) -> Self {
+        Self {
+            line: line.into(),
+            expression: None,
+        }
+    }
+}
+
+pub fn code_lines(transient: &TransientState) -> Vec<CodeLine> {
+    let mut lines = Vec::new();
+
+    if let Some(functions) = &transient.code_with_errors {
+        lines.push(CodeLine::text(
+            "This code has errors and is not running."
+                .dark_gray()
+                .italic(),
+        ));
+
+        for function in functions {
+            function_lines(
+                format!("{}: ", function.name),
+                &function.inner,
+                0,
+                &mut lines,
+            );
+        }
+
+        return lines;
+    }
+
+    match &transient.active_functions {
+        ActiveFunctions::Entries { entries } => {
+            for entry in &entries.inner {
+                match entry {
+                    ActiveFunctionsEntry::Function(function) => {
+                        function_lines(
+                            format!("{}: ", function.name),
+                            &function.inner,
+                            0,
+                            &mut lines,
+                        );
+                    }
+                    ActiveFunctionsEntry::Gap => {
+                        lines.push(CodeLine::text(
+                            "Functions that should be displayed here are \
+                            omitted. This is the result of a compiler \
+                            optimization."
+                                .red()
+                                .bold(),
+                        ));
+                    }
+                }
+            }
+        }
+        ActiveFunctions::Message { message } => {
+            lines.push(CodeLine::text(message.to_string()));
+        }
+    }
+
+    lines
+}
+
+fn function_lines(
+    prefix: String,
+    function: &DebugFunction,
+    depth: usize,
+    lines: &mut Vec<CodeLine>,
+) {
+    let indent = indent(depth);
+
+    lines.push(CodeLine::text(format!("{indent}{prefix}fn")));
+
+    for branch in &function.branches {
+        let parameters = branch
+            .parameters
+            .iter()
+            .map(|DebugParameter { name, type_ }| match type_ {
+                Some(type_) => format!("{name}: {type_}"),
+                None => name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        lines.push(CodeLine::text(format!("{indent}    \\ {parameters} ->")));
+
+        for member in &branch.body {
+            member_lines(member, depth + 2, lines);
+        }
+    }
+
+    let signature = function
+        .signature
+        .as_ref()
+        .map(|signature| format!(": {signature}"))
+        .unwrap_or_default();
+    lines.push(CodeLine::text(format!("{indent}end{signature}")));
+}
+
+fn member_lines(member: &DebugMember, depth: usize, lines: &mut Vec<CodeLine>) {
+    let indent = indent(depth);
+
+    let expression = match &member.kind {
+        DebugMemberKind::Comment { lines: comment } => {
+            for line in comment {
+                lines.push(CodeLine::text(
+                    format!("{indent}# {line}").dark_gray().italic(),
+                ));
+            }
+            return;
+        }
+        DebugMemberKind::Error { message } => {
+            lines.push(CodeLine::text(Line::from(vec![
+                Span::raw(indent),
+                message.clone().red(),
+            ])));
+            return;
+        }
+        DebugMemberKind::Function { function } => {
+            function_lines(String::new(), function, depth, lines);
+            return;
+        }
+        DebugMemberKind::Identifier { name } => name,
+        DebugMemberKind::Value { as_string } => as_string,
+    };
+
+    let data = &member.data;
+
+    // The breakpoint marker goes in front of the indentation, so it doesn't
+    // move the code around.
+    let breakpoint = if data.has_durable_breakpoint {
+        "● ".blue()
+    } else {
+        Span::raw("  ")
+    };
+
+    let mut style = Style::new();
+    match data.effect {
+        Some(Effect::Breakpoint) => {
+            style = style.black().on_green();
+        }
+        Some(_) => {
+            style = style.white().on_red();
+        }
+        None => {}
+    }
+    if data.state.is_active() {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+
+    let mut spans = vec![
+        breakpoint,
+        Span::raw(indent[2..].to_string()),
+        Span::styled(expression.clone(), style),
+    ];
+    if let Some(signature) = &data.signature {
+        spans.push(format!(": {signature} .").dark_gray());
+    }
+    if let Some(effect) = &data.effect {
+        let effect = match &data.span {
+            Some(span) => format!("  {effect:?} (at {span})"),
+            None => format!("  {effect:?}"),
+        };
+        spans.push(effect.red().bold());
+    }
+    if !data.diagnostics.is_empty() {
+        spans.push(format!("  {}", data.diagnostics.join(" ")).red());
+    }
+
+    lines.push(CodeLine {
+        line: Line::from(spans),
+        expression: Some(data.clone()),
+    });
+}
+
+fn indent(depth: usize) -> String {
+    // Always leave room for the breakpoint marker.
+    " ".repeat(2 + depth * 4)
+}
+
+fn render_code(app: &App, frame: &mut Frame, area: Rect) {
+    let title = if app.transient.code_with_errors.is_some() {
+        "Code with errors"
+    } else {
+        "Active functions"
+    };
+
+    let lines = app.code_lines();
+    let mut state =
+        ListState::default().with_selected(app.selected_line(&lines));
+
+    let list = List::new(lines.into_iter().map(|line| line.line))
+        .block(Block::bordered().title(title))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_status(app: &App, frame: &mut Frame, area: Rect) {
+    let state = match &app.persistent.host_state {
+        Some(HostState::Running) => "Running".to_string(),
+        Some(HostState::Finished) => "Finished".to_string(),
+        Some(HostState::FrameOverrun) => "Frame overrun".to_string(),
+        Some(HostState::Stopped {
+            effect: Some(effect),
+            ..
+        }) => format!("Stopped ({effect:?})"),
+        Some(HostState::Stopped { effect: None, .. }) => "Stopped".to_string(),
+        None => "Not connected".to_string(),
+    };
+
+    let mut lines = vec![Line::from(vec!["Game: ".bold(), Span::raw(state)])];
+
+    if let Some(seed) = app.persistent.seed {
+        lines.push(Line::from(vec![
+            "Seed: ".bold(),
+            Span::raw(seed.to_string()),
+        ]));
+    }
+
+    if let Some(err) = &app.persistent.protocol_mismatch {
+        lines.push(Line::from(err.to_string().red().bold()));
+        lines.push(Line::from(
+            "Debugger, game, and server must be built from the same version \
+            of Crosscut.",
+        ));
+    }
+
+    let diagnostics = app.persistent.diagnostics();
+    if !diagnostics.is_empty() {
+        lines.push(Line::from("Errors:".bold()));
+
+        if app.persistent.code_with_errors.is_some() {
+            lines.push(Line::from(
+                "The last code without errors is still running."
+                    .dark_gray()
+                    .italic(),
+            ));
+        }
+
+        for diagnostic in diagnostics {
+            lines.push(Line::from(vec![
+                format!("{}.capi:{} ", diagnostic.module, diagnostic.span)
+                    .dark_gray(),
+                diagnostic.message.clone().red(),
+            ]));
+        }
+    }
+
+    if let Some(error) = &app.error {
+        lines.push(Line::from(error.clone().red().bold()));
+    }
+
+    let status = Paragraph::new(lines)
+        .block(Block::bordered().title("Status"))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(status, area);
+}
+
+fn render_stack(app: &App, frame: &mut Frame, area: Rect) {
+    let operands = app
+        .transient
+        .operands
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let bindings = app
+        .transient
+        .bindings
+        .iter()
+        .map(|(name, value)| format!("{name}: {value}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let stack = Paragraph::new(vec![
+        Line::from(vec!["Operands: ".bold(), Span::raw(operands)]),
+        Line::from(vec!["Bindings: ".bold(), Span::raw(bindings)]),
+    ])
+    .block(Block::bordered().title("Current stack frame"))
+    .wrap(Wrap { trim: false });
+    frame.render_widget(stack, area);
+}
+
+fn render_memory(app: &App, frame: &mut Frame, area: Rect) {
+    let Some(memory) = &app.persistent.memory else {
+        let memory = Paragraph::new("No memory available yet.")
+            .block(Block::bordered().title("Memory"));
+        frame.render_widget(memory, area);
+        return;
+    };
+
+    let page = app.persistent.memory_page;
+    let num_pages = memory.inner.len().div_ceil(MEMORY_PAGE_SIZE);
+    let start = page * MEMORY_PAGE_SIZE;
+
+    let lines = memory
+        .inner
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(MEMORY_PAGE_SIZE)
+        .collect::<Vec<_>>()
+        .chunks(BYTES_PER_LINE)
+        .map(|line| {
+            let address =
+                line.first().map(|(address, _)| *address).unwrap_or(0);
+            let values = line
+                .iter()
+                .map(|(_, value)| format!("{value:>4}"))
+                .collect::<String>();
+
+            Line::from(vec![
+                format!("{address:#06x}  ").dark_gray(),
+                Span::raw(values),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let memory = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(format!("Memory (page {} of {num_pages})", page + 1)),
+    );
+    frame.render_widget(memory, area);
+}
@@ -0,0 +1,132 @@
+use std::{net::SocketAddr, thread};
+
+use anyhow::Context;
+use crosscut_compiler::CompilerOutput;
+use crosscut_protocol::command::CommandExt;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    DefaultTerminal,
+};
+use tokio::{select, sync::mpsc, task};
+
+use crate::{
+    code_client::fetch_code,
+    relay_client::{self, MessagesRx, MessagesTx, Peer},
+};
+
+use super::{app::App, render::render};
+
+/// # Debug a game in the terminal, through the relay of the server
+///
+/// The debugger attaches to whatever game is connected to the other side of
+/// the relay, like one running in `crosscut headless --relay`.
+pub async fn start(address: SocketAddr) -> anyhow::Result<()> {
+    let (commands, updates) =
+        relay_client::connect(address, Peer::Debugger).await?;
+
+    let (code_tx, code_rx) = mpsc::unbounded_channel();
+    let code = task::spawn(fetch_code(address, code_tx));
+
+    let (keys_tx, keys_rx) = mpsc::unbounded_channel();
+    thread::spawn(move || read_keys(keys_tx));
+
+    let mut terminal = ratatui::init();
+    let result = run(
+        &mut terminal,
+        address,
+        commands,
+        updates,
+        code,
+        code_rx,
+        keys_rx,
+    )
+    .await;
+    ratatui::restore();
+
+    result
+}
+
+async fn run(
+    terminal: &mut DefaultTerminal,
+    address: SocketAddr,
+    commands: MessagesTx,
+    mut updates: MessagesRx,
+    code: task::JoinHandle<anyhow::Result<()>>,
+    mut code_rx: mpsc::UnboundedReceiver<CompilerOutput>,
+    mut keys_rx: mpsc::UnboundedReceiver<KeyCode>,
+) -> anyhow::Result<()> {
+    let mut app = App::new();
+
+    loop {
+        terminal.draw(|frame| render(&app, frame))?;
+
+        let commands_for_game = select! {
+            new_code = code_rx.recv() => {
+                let Some(new_code) = new_code else {
+                    // This only happens, if fetching the code failed.
+                    return code
+                        .await?
+                        .with_context(|| {
+                            format!("Lost connection to `{address}`")
+                        });
+                };
+
+                app.on_new_code(new_code).into_iter().collect()
+            }
+            update = updates.recv() => {
+                let Some(update) = update else {
+                    anyhow::bail!("Lost connection to `{address}`");
+                };
+
+                app.on_update(update).into_iter().collect()
+            }
+            key = keys_rx.recv() => {
+                let Some(key) = key else {
+                    anyhow::bail!("Stopped receiving input from terminal");
+                };
+
+                let Some(commands) = app.on_key(key) else {
+                    // The user wants to quit.
+                    return Ok(());
+                };
+
+                commands
+            }
+        };
+
+        for command in commands_for_game {
+            // If the connection has been lost, we'll notice when receiving the
+            // next update.
+            let _ = commands.send(command.serialize());
+        }
+    }
+}
+
+/// # Read key presses from the terminal
+///
+/// ## Implementation Note
+///
+/// Reading terminal events blocks, so this runs on its own thread.
+fn read_keys(keys: mpsc::UnboundedSender<KeyCode>) {
+    loop {
+        let event = match event::read() {
+            Ok(event) => event,
+            Err(err) => {
+                tracing::error!("Failed to read from terminal: {err}");
+                break;
+            }
+        };
+
+        let Event::Key(key) = event else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if keys.send(key.code).is_err() {
+            // The debugger has stopped.
+            break;
+        }
+    }
+}
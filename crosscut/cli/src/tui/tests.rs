@@ -0,0 +1,90 @@
+use crosscut_compiler::Compiler;
+use crosscut_game_engine::{
+    command::Command, game_engine::GameEngine, host::GameEngineHost,
+};
+use crosscut_protocol::updates::Updates;
+use ratatui::{backend::TestBackend, crossterm::event::KeyCode, Terminal};
+
+use super::{app::App, render::render};
+
+#[test]
+fn display_active_functions_of_stopped_game() -> anyhow::Result<()> {
+    let app = stopped_at_breakpoint();
+
+    let mut terminal = Terminal::new(TestBackend::new(160, 40))?;
+    terminal.draw(|frame| render(&app, frame))?;
+
+    let screen = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|cell| cell.symbol())
+        .collect::<String>();
+    assert!(screen.contains("main: fn"));
+    assert!(screen.contains("brk"));
+    assert!(screen.contains("Stopped (Breakpoint)"));
+
+    Ok(())
+}
+
+#[test]
+fn set_breakpoint_on_selected_expression() {
+    let mut app = stopped_at_breakpoint();
+
+    // The innermost active expression is selected initially. Select the one
+    // before it.
+    let commands = app.on_key(KeyCode::Up).unwrap();
+    assert!(commands.is_empty());
+
+    let commands = app.on_key(KeyCode::Char('b')).unwrap();
+    assert!(matches!(
+        commands.as_slice(),
+        [Command::PatchInstructions { .. }],
+    ));
+
+    let lines = app.code_lines();
+    let selected = app.selected_line(&lines).unwrap();
+    let expression = lines[selected].expression.as_ref().unwrap();
+    assert!(expression.has_durable_breakpoint);
+    assert!(!expression.state.is_innermost_active_expression());
+}
+
+#[test]
+fn quit_on_request() {
+    let mut app = App::new();
+    assert!(app.on_key(KeyCode::Char('q')).is_none());
+}
+
+fn stopped_at_breakpoint() -> App {
+    let mut app = App::new();
+
+    let code = Compiler::default().compile(
+        r"
+            main: fn
+                br size_x, size_y ->
+                    1
+                    brk
+                end
+            end
+        ",
+        &GameEngineHost,
+    );
+    let command = app.on_new_code(code).unwrap();
+
+    let mut game_engine = GameEngine::new();
+    game_engine.on_command(command);
+    game_engine.run_frame(&mut Vec::new());
+
+    let mut updates = Updates::default();
+    updates.queue_updates(
+        &game_engine.runtime,
+        game_engine.memory(),
+        game_engine.seed(),
+    );
+    for update in updates.take_queued_updates() {
+        app.on_update(update.serialize());
+    }
+
+    app
+}
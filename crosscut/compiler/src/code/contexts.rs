@@ -0,0 +1,163 @@
+use std::{collections::BTreeMap, iter};
+
+use crate::intrinsics::IntrinsicFunction;
+
+use super::{
+    syntax::{Expression, NamedFunction, SyntaxTree},
+    Hash, IdentifierTarget, Identifiers, Index, Signature, Type, Types,
+};
+
+/// # The context that each named function is compiled in
+///
+/// What a function compiles to doesn't only depend on its own code. It also
+/// depends on what the identifiers within that code resolve to, and on the
+/// types that were inferred for it, which in turn depend on the functions it
+/// calls. Tracking this makes it possible to detect functions that need to be
+/// compiled again, even though their code hasn't changed.
+#[derive(Debug, Default)]
+pub struct Contexts {
+    inner: BTreeMap<Index<NamedFunction>, Hash<Context>>,
+}
+
+impl Contexts {
+    /// # Determine the context of each named function
+    pub fn new(
+        syntax_tree: &SyntaxTree,
+        identifiers: &Identifiers,
+        types: &Types,
+    ) -> Self {
+        let inner = syntax_tree
+            .named_functions()
+            .map(|named_function| {
+                let index = named_function.index();
+                let function = named_function.into_located_function();
+
+                let mut context = Context::default();
+
+                for function in iter::once(function.clone())
+                    .chain(function.all_local_functions())
+                {
+                    context.signatures.push(
+                        types
+                            .signature_of_function(&function.location)
+                            .cloned(),
+                    );
+
+                    for branch in function.branches() {
+                        context
+                            .errors
+                            .push(types.has_error_at_branch(&branch.location));
+
+                        for parameter in branch.parameters() {
+                            context.parameters.push(
+                                types
+                                    .type_of_parameter(&parameter.location)
+                                    .cloned(),
+                            );
+                        }
+
+                        for expression in branch.expressions() {
+                            context.signatures.push(
+                                types
+                                    .signature_of_expression(
+                                        &expression.location,
+                                    )
+                                    .cloned(),
+                            );
+                            context.errors.push(
+                                types.has_error_at_expression(
+                                    &expression.location,
+                                ),
+                            );
+
+                            let Expression::Identifier { .. } =
+                                expression.fragment
+                            else {
+                                continue;
+                            };
+
+                            let target = identifiers
+                                .is_resolved(&expression.location)
+                                .map(|target| Target::new(target, syntax_tree));
+
+                            context.targets.push(target);
+                        }
+                    }
+                }
+
+                (index, Hash::new(&context))
+            })
+            .collect();
+
+        Self { inner }
+    }
+
+    /// # Access the context of the named function with the given index
+    pub fn get(&self, index: &Index<NamedFunction>) -> Option<&Hash<Context>> {
+        self.inner.get(index)
+    }
+}
+
+/// # The context that a single named function is compiled in
+///
+/// Covers the function itself, as well as all of its local functions.
+#[derive(Debug, Default, Eq, PartialEq, udigest::Digestable)]
+pub struct Context {
+    /// # The targets of the identifiers, in order
+    ///
+    /// `None` stands in for an identifier that doesn't resolve to anything.
+    targets: Vec<Option<Target>>,
+
+    /// # The inferred signatures of functions and expressions, in order
+    ///
+    /// `None` stands in for a signature that could not be inferred.
+    signatures: Vec<Option<Signature>>,
+
+    /// # The inferred types of the branch parameters, in order
+    ///
+    /// `None` stands in for a type that could not be inferred.
+    parameters: Vec<Option<Type>>,
+
+    /// # Whether inference failed at each branch and expression, in order
+    errors: Vec<bool>,
+}
+
+/// # An identifier target, independent of where functions are located
+///
+/// Named functions can move around, as code is added or removed. That doesn't
+/// change what a call to them compiles to, so they are identified by module
+/// and name instead.
+#[derive(Debug, Eq, PartialEq, udigest::Digestable)]
+enum Target {
+    Binding,
+    HostFunction { name: String, number: u8 },
+    IntrinsicFunction { function: IntrinsicFunction },
+    UserDefinedFunction { module: String, name: String },
+}
+
+impl Target {
+    fn new(target: &IdentifierTarget, syntax_tree: &SyntaxTree) -> Self {
+        match target {
+            IdentifierTarget::Binding(_) => Self::Binding,
+            IdentifierTarget::HostFunction(function) => Self::HostFunction {
+                name: function.name.clone(),
+                number: function.number,
+            },
+            IdentifierTarget::IntrinsicFunction(function) => {
+                Self::IntrinsicFunction {
+                    function: *function,
+                }
+            }
+            IdentifierTarget::UserDefinedFunction(location) => {
+                let function = syntax_tree
+                    .find_top_level_parent_function(location)
+                    .expect("Target of identifier must exist.");
+
+                Self::UserDefinedFunction {
+                    module: function.module.clone(),
+                    name: function.name.clone(),
+                }
+            }
+        }
+    }
+}
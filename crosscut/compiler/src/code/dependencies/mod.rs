@@ -15,7 +15,7 @@ mod tests {
                 Branch, Expression, Function, FunctionLocation, Member,
                 NamedFunction, SyntaxTree,
             },
-            Dependencies, FunctionCalls, IndexMap, Tokens,
+            Bindings, Dependencies, FunctionCalls, IndexMap, Tokens,
         },
         host::NoHost,
     };
@@ -394,9 +394,10 @@ mod tests {
         let syntax_tree = SyntaxTree::parse(tokens);
 
         permutate_syntax_tree(syntax_tree).map(|syntax_tree| {
+            let bindings = Bindings::resolve(&syntax_tree);
             let function_calls = FunctionCalls::resolve(&syntax_tree, &NoHost);
             let dependencies =
-                Dependencies::resolve(&syntax_tree, &function_calls);
+                Dependencies::resolve(&syntax_tree, &bindings, &function_calls);
 
             (syntax_tree, dependencies)
         })
@@ -432,6 +433,7 @@ mod tests {
                 for function in permutate_function(named_function.inner) {
                     let named_function = NamedFunction {
                         comment: named_function.comment.clone(),
+                        module: named_function.module.clone(),
                         name: named_function.name.clone(),
                         inner: function,
                     };
@@ -463,6 +465,7 @@ mod tests {
             permutate_rest_of_function(
                 Function {
                     branches: IndexMap::default(),
+                    trailing_comment: None,
                 },
                 branches.into_iter(),
                 &mut functions,
@@ -3,7 +3,7 @@ use crate::code::{
         Branch, BranchLocation, Function, FunctionLocation, Located,
         NamedFunction, SyntaxTree,
     },
-    FunctionCalls, Index,
+    Bindings, FunctionCalls, Index,
 };
 
 use super::resolve::{
@@ -16,6 +16,8 @@ use super::resolve::{
 ///
 /// - If any function `f` calls a named function `g`, then `f` depends on `g`.
 /// - If any function `f` has a local function `g`, then `f` depends on `g`.
+/// - If that local function `g` captures any bindings from `f`, then `g` also
+///   depends on `f`.
 ///
 /// Dependencies form a graph, not a tree, since:
 ///
@@ -44,24 +46,28 @@ impl Dependencies {
     /// # Resolve the dependencies
     pub fn resolve(
         syntax_tree: &SyntaxTree,
+        bindings: &Bindings,
         function_calls: &FunctionCalls,
     ) -> Self {
-        let clusters =
-            resolve_function_dependencies(syntax_tree, function_calls)
-                .into_iter()
-                .map(|functions| {
-                    let branches = resolve_branch_dependencies(
-                        &functions,
-                        syntax_tree,
-                        function_calls,
-                    );
+        let clusters = resolve_function_dependencies(
+            syntax_tree,
+            bindings,
+            function_calls,
+        )
+        .into_iter()
+        .map(|functions| {
+            let branches = resolve_branch_dependencies(
+                &functions,
+                syntax_tree,
+                function_calls,
+            );
 
-                    DependencyCluster {
-                        functions,
-                        branches,
-                    }
-                })
-                .collect();
+            DependencyCluster {
+                functions,
+                branches,
+            }
+        })
+        .collect();
         Self { clusters }
     }
 
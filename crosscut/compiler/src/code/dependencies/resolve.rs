@@ -7,11 +7,12 @@ use petgraph::{
 
 use crate::code::{
     syntax::{BranchLocation, Expression, FunctionLocation, SyntaxTree},
-    FunctionCalls,
+    Bindings, FunctionCalls,
 };
 
 pub fn resolve_function_dependencies(
     syntax_tree: &SyntaxTree,
+    bindings: &Bindings,
     function_calls: &FunctionCalls,
 ) -> Vec<Vec<FunctionLocation>> {
     let mut dependency_graph = Graph::new();
@@ -26,6 +27,24 @@ pub fn resolve_function_dependencies(
     for function in syntax_tree.all_functions() {
         let depender = graph_indices_by_function_location[&function.location];
 
+        let captures_bindings = bindings
+            .environment_of(&function.location)
+            .bindings(syntax_tree)
+            .next()
+            .is_some();
+
+        if let (FunctionLocation::Local { location }, true) =
+            (&function.location, captures_bindings)
+        {
+            // A local function that captures bindings from its parent depends
+            // on it. The types of those bindings, and with them the type of the
+            // local function, can only be inferred, if both are handled
+            // together.
+            let parent = &location.parent.parent;
+            let dependee = graph_indices_by_function_location[&**parent];
+            dependency_graph.add_edge(depender, dependee, ());
+        }
+
         for branch in function.branches() {
             for expression in branch.expressions() {
                 let dependee = match expression.fragment {
@@ -109,6 +128,15 @@ pub fn resolve_branch_dependencies(
                             // of the branch.
                             return true;
                         }
+                        Expression::FieldAccess { .. }
+                        | Expression::Record { .. } => {
+                            // Neither do records.
+                            return true;
+                        }
+                        Expression::Error { .. } => {
+                            // Same goes for code that could not be parsed.
+                            return true;
+                        }
                     };
 
                     let dependency_is_outside_of_cluster =
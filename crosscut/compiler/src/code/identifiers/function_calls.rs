@@ -22,6 +22,14 @@ impl FunctionCalls {
         let mut to_user_defined_functions = BTreeMap::new();
 
         for function in syntax_tree.all_functions() {
+            // Names that aren't qualified with a module refer to functions in
+            // the same module as the caller. Qualified names can only refer to
+            // functions in modules that the caller's module imports.
+            let module = syntax_tree
+                .find_top_level_parent_function(&function.location)
+                .map(|named_function| named_function.module.clone())
+                .expect("Function must have a top-level parent.");
+
             for branch in function.branches() {
                 for expression in branch.expressions() {
                     if let Expression::Identifier { name } = expression.fragment
@@ -46,7 +54,7 @@ impl FunctionCalls {
                         }
 
                         if let Some(function) =
-                            syntax_tree.function_by_name(name)
+                            syntax_tree.function_by_call(name, &module)
                         {
                             to_user_defined_functions.insert(
                                 expression.location,
@@ -93,7 +101,10 @@ impl FunctionCalls {
 #[cfg(test)]
 mod tests {
     use crate::{
-        code::{syntax::SyntaxTree, Tokens},
+        code::{
+            syntax::{MemberLocation, SyntaxTree},
+            Tokens,
+        },
         host::{Host, HostFunction},
     };
 
@@ -204,9 +215,157 @@ mod tests {
             .is_some());
     }
 
+    #[test]
+    fn resolve_qualified_call_to_function_in_other_module() {
+        // A function in another module can be called by qualifying its name
+        // with the name of that module, if that module is imported.
+
+        let (syntax_tree, function_calls) =
+            resolve_function_calls_in_modules([
+                (
+                    "main",
+                    r"
+                        import other
+
+                        f: fn
+                            br ->
+                                other/g
+                            end
+                        end
+                    ",
+                ),
+                (
+                    "other",
+                    r"
+                        g: fn
+                            br ->
+                            end
+                        end
+                    ",
+                ),
+            ]);
+
+        let call = first_expression_in(&syntax_tree, "f");
+        let g = syntax_tree.function_by_path("g", "other").unwrap();
+
+        assert_eq!(
+            function_calls.is_call_to_user_defined_function(&call),
+            Some(&g.location()),
+        );
+    }
+
+    #[test]
+    fn ignore_qualified_call_to_module_that_is_not_imported() {
+        // Modules need to be imported explicitly. Otherwise, their functions
+        // can't be called.
+
+        let (syntax_tree, function_calls) =
+            resolve_function_calls_in_modules([
+                (
+                    "main",
+                    r"
+                        f: fn
+                            br ->
+                                other/g
+                            end
+                        end
+                    ",
+                ),
+                (
+                    "other",
+                    r"
+                        g: fn
+                            br ->
+                            end
+                        end
+                    ",
+                ),
+            ]);
+
+        let call = first_expression_in(&syntax_tree, "f");
+        assert!(function_calls
+            .is_call_to_user_defined_function(&call)
+            .is_none());
+    }
+
+    #[test]
+    fn resolve_unqualified_call_within_module_of_caller() {
+        // If functions with the same name exist in multiple modules, an
+        // unqualified name refers to the one in the caller's module.
+
+        let (syntax_tree, function_calls) =
+            resolve_function_calls_in_modules([
+                (
+                    "main",
+                    r"
+                        g: fn
+                            br ->
+                            end
+                        end
+                    ",
+                ),
+                (
+                    "other",
+                    r"
+                        f: fn
+                            br ->
+                                g
+                            end
+                        end
+
+                        g: fn
+                            br ->
+                            end
+                        end
+                    ",
+                ),
+            ]);
+
+        let f = syntax_tree.function_by_path("f", "other").unwrap();
+        let call = f
+            .into_located_function()
+            .find_single_branch()
+            .unwrap()
+            .expressions()
+            .map(|expression| expression.location)
+            .next()
+            .unwrap();
+        let g = syntax_tree.function_by_path("other/g", "main").unwrap();
+
+        assert_eq!(
+            function_calls.is_call_to_user_defined_function(&call),
+            Some(&g.location()),
+        );
+    }
+
+    fn first_expression_in(
+        syntax_tree: &SyntaxTree,
+        name: &str,
+    ) -> MemberLocation {
+        syntax_tree
+            .function_by_name(name)
+            .unwrap()
+            .into_located_function()
+            .find_single_branch()
+            .unwrap()
+            .expressions()
+            .map(|expression| expression.location)
+            .next()
+            .unwrap()
+    }
+
     fn resolve_function_calls(input: &str) -> (SyntaxTree, FunctionCalls) {
-        let tokens = Tokens::tokenize(input);
-        let syntax_tree = SyntaxTree::parse(tokens);
+        resolve_function_calls_in_modules([("main", input)])
+    }
+
+    fn resolve_function_calls_in_modules<'r>(
+        modules: impl IntoIterator<Item = (&'r str, &'r str)>,
+    ) -> (SyntaxTree, FunctionCalls) {
+        let mut syntax_tree = SyntaxTree::default();
+        for (module, input) in modules {
+            let tokens = Tokens::tokenize(input);
+            syntax_tree.parse_module(module, tokens);
+        }
         let function_calls = FunctionCalls::resolve(&syntax_tree, &TestHost);
 
         (syntax_tree, function_calls)
@@ -15,6 +15,7 @@ use super::{Bindings, FunctionCalls};
 #[derive(Debug)]
 pub struct Identifiers {
     targets: BTreeMap<MemberLocation, IdentifierTarget>,
+    unresolved: BTreeMap<MemberLocation, String>,
 }
 
 impl Identifiers {
@@ -25,11 +26,13 @@ impl Identifiers {
         function_calls: &FunctionCalls,
     ) -> Self {
         let mut targets = BTreeMap::new();
+        let mut unresolved = BTreeMap::new();
 
         for function in syntax_tree.all_functions() {
             for branch in function.branches() {
                 for expression in branch.expressions() {
-                    if let Expression::Identifier { .. } = expression.fragment {
+                    if let Expression::Identifier { name } = expression.fragment
+                    {
                         let binding = bindings.is_binding(&expression.location);
                         let host_function = function_calls
                             .is_call_to_host_function(&expression.location);
@@ -48,6 +51,15 @@ impl Identifiers {
                             intrinsic_function,
                             user_defined_function,
                         ) {
+                            (None, None, None, None) => {
+                                // The identifier doesn't refer to anything.
+                                // Code generation takes care of that, by
+                                // emitting a build error in its place. We
+                                // still need to report it though.
+                                unresolved
+                                    .insert(expression.location, name.clone());
+                                continue;
+                            }
                             (Some(binding), None, None, None) => {
                                 IdentifierTarget::Binding(binding.clone())
                             }
@@ -86,7 +98,10 @@ impl Identifiers {
             }
         }
 
-        Self { targets }
+        Self {
+            targets,
+            unresolved,
+        }
     }
 
     pub fn is_resolved(
@@ -95,6 +110,15 @@ impl Identifiers {
     ) -> Option<&IdentifierTarget> {
         self.targets.get(location)
     }
+
+    /// # Iterate over the identifiers that don't refer to anything
+    ///
+    /// Yields the location and name of each of those identifiers.
+    pub fn unresolved(&self) -> impl Iterator<Item = (&MemberLocation, &str)> {
+        self.unresolved
+            .iter()
+            .map(|(location, name)| (location, name.as_str()))
+    }
 }
 
 /// # The target that an identifier resolves to
@@ -8,12 +8,14 @@ mod tokens;
 mod types;
 
 mod changes;
+mod contexts;
 mod functions;
 mod hash;
 mod index;
 
 pub use self::{
     changes::{Changes, FunctionInUpdate, FunctionUpdate},
+    contexts::{Context, Contexts},
     dependencies::{Dependencies, DependencyCluster},
     functions::Functions,
     hash::Hash,
@@ -23,6 +25,9 @@ pub use self::{
     index::{Index, IndexMap},
     recursion::Recursion,
     tail_expressions::TailExpressions,
-    tokens::{Token, Tokens},
-    types::{Signature, Type, TypeAnnotations, Types},
+    tokens::{Position, Span, Token, Tokens},
+    types::{
+        InferenceError, InferenceErrorLocation, Signature, Type,
+        TypeAnnotations, Types,
+    },
 };
@@ -99,7 +99,9 @@ mod tests {
     use itertools::Itertools;
 
     use crate::{
-        code::{syntax::SyntaxTree, Dependencies, FunctionCalls, Tokens},
+        code::{
+            syntax::SyntaxTree, Bindings, Dependencies, FunctionCalls, Tokens,
+        },
         host::NoHost,
     };
 
@@ -400,8 +402,10 @@ mod tests {
     fn find_recursion(input: &str) -> (SyntaxTree, Recursion) {
         let tokens = Tokens::tokenize(input);
         let syntax_tree = SyntaxTree::parse(tokens);
+        let bindings = Bindings::resolve(&syntax_tree);
         let function_calls = FunctionCalls::resolve(&syntax_tree, &NoHost);
-        let dependencies = Dependencies::resolve(&syntax_tree, &function_calls);
+        let dependencies =
+            Dependencies::resolve(&syntax_tree, &bindings, &function_calls);
         let recursion =
             Recursion::find(&syntax_tree, &function_calls, &dependencies);
 
@@ -26,11 +26,14 @@ pub use self::{
         ParameterLocation,
     },
     repr::{
+        error::SyntaxError,
         expression::Expression,
         function::{
             Binding, Branch, Comment, Function, Member, NamedFunction,
             Parameter,
         },
+        module::{Import, Module},
+        spans::Spans,
         syntax_tree::SyntaxTree,
         types::SyntaxType,
     },
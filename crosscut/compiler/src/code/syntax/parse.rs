@@ -1,90 +1,221 @@
 use std::result;
 
 use crate::code::{
-    tokens::{Keyword::*, NoMoreTokens, Punctuator::*, Token, Tokens},
+    tokens::{Keyword::*, NoMoreTokens, Punctuator::*, Span, Token, Tokens},
     Index, IndexMap, Signature,
 };
 
 use super::{
-    repr::types::SyntaxType, Binding, Branch, BranchLocation, Comment,
-    Expression, Function, FunctionLocation, Member, MemberLocation,
-    NamedFunction, Parameter,
+    repr::{spans::Spans, types::SyntaxType},
+    Binding, Branch, BranchLocation, Comment, Expression, Function,
+    FunctionLocation, Import, Member, MemberLocation, Module, NamedFunction,
+    Parameter, ParameterLocation, SyntaxError, SyntaxTree,
 };
 
 /// # Parse the provided tokens
 ///
-/// ## Implementation Note
+/// The parser does not stop at unexpected tokens. It records them as errors and
+/// then recovers at the next `fn`, `br`, or `end` boundary, so the rest of the
+/// code can still be parsed.
 ///
-/// This compiler pass currently panics when it encounters an unexpected token.
-/// It would be better, if it encoded the error into its output instead. This is
-/// non-trivial though, compared to other compiler passes that do that.
+/// Errors in the body of a branch end that branch. The rest of the branch is
+/// skipped up to its `end`, and an [`Expression::Error`] takes the place of the
+/// skipped code. Errors that happen anywhere else cause the parser to skip the
+/// whole branch or named function, as it has no way to represent those in the
+/// syntax tree. These are then only tracked in [`SyntaxTree::errors`].
 ///
-/// For example, if an identifier can not be resolved, this only affects that
-/// identifier, and it is quite easy to encode that by having a [`Expression`]
-/// variant for unresolved identifiers.
+/// The named functions are added to the provided syntax tree, as part of the
+/// provided module. This makes sure that their indices are unique across all
+/// modules.
 ///
-/// In the case of parsing, however, an unexpected token would likely result in
-/// the parser not knowing what to do with the following tokens, even it can be
-/// taught to recover eventually. Those tokens would also have to be encoded
-/// into the code representation. Otherwise, viewing that representation (which
-/// would include the error) makes no sense to a user.
-///
-/// I'm inclined to leave this be for now. Panicking certainly works well enough
-/// in the current setup. Once we have a code database, it will no longer do.
-/// But then we also need a completely different parser architecture.
-///
-/// It's probably not worth solving this non-trivial problem for the current
-/// architecture, for little gain, only to re-solve it again for the new
-/// architecture, once that is necessary.
-pub fn parse(mut tokens: Tokens) -> IndexMap<NamedFunction> {
-    let mut named_functions = IndexMap::default();
-
-    loop {
-        let index = named_functions.next_index();
-
-        let function = match parse_named_function(&mut tokens, index) {
-            Ok(function) => function,
-            Err(Error::NoMoreTokens(NoMoreTokens)) => {
-                break;
+/// A module starts with its imports, if any. Those are not accepted after the
+/// first named function.
+pub fn parse(mut tokens: Tokens, module: &str, syntax_tree: &mut SyntaxTree) {
+    let mut output = Output {
+        module: module.to_string(),
+        errors: Vec::new(),
+        spans: Spans::default(),
+    };
+    let mut imports = Vec::new();
+    let mut is_past_imports = false;
+
+    let trailing_comment = loop {
+        if next_after_comments(&tokens).is_none() {
+            match parse_comment(&mut tokens) {
+                Ok(comment) => break comment,
+                Err(err) => {
+                    output
+                        .errors
+                        .push(err.into_syntax_error(&tokens, &output.module));
+                    break None;
+                }
             }
-            Err(err) => {
-                panic!("Parser error: {err:?}");
+        }
+
+        if !is_past_imports
+            && next_after_comments(&tokens) == Some(&Token::Keyword(Import))
+        {
+            match parse_import(&mut tokens, imports.len(), &mut output) {
+                Ok(import) => {
+                    imports.push(import);
+                }
+                Err(err) => {
+                    output
+                        .errors
+                        .push(err.into_syntax_error(&tokens, &output.module));
+
+                    if skip_to_next_named_function(&mut tokens).is_err() {
+                        break None;
+                    }
+                }
             }
-        };
 
-        let actual_index = named_functions.push(function);
+            continue;
+        }
+        is_past_imports = true;
+
+        let index = syntax_tree.named_functions.next_index();
+
+        let function =
+            match parse_named_function(&mut tokens, index, &mut output) {
+                Ok(function) => function,
+                Err(err) => {
+                    output
+                        .errors
+                        .push(err.into_syntax_error(&tokens, &output.module));
+
+                    if skip_to_next_named_function(&mut tokens).is_err() {
+                        break None;
+                    }
+
+                    continue;
+                }
+            };
+
+        let actual_index = syntax_tree.named_functions.push(function);
         assert_eq!(
             index, actual_index,
             "Function has a different index than was initially assumed.",
         );
+    };
+
+    syntax_tree.modules.insert(
+        output.module,
+        Module {
+            imports,
+            trailing_comment,
+        },
+    );
+    syntax_tree.errors.extend(output.errors);
+    syntax_tree.spans.extend(output.spans);
+}
+
+struct Output {
+    module: String,
+    errors: Vec<SyntaxError>,
+    spans: Spans,
+}
+
+/// # Peek at the next token that is not part of a comment
+fn next_after_comments(tokens: &Tokens) -> Option<&Token> {
+    let mut n = 0;
+
+    while let Ok(token) = tokens.peek_nth(n) {
+        if !matches!(token, Token::CommentLine { .. }) {
+            return Some(token);
+        }
+
+        n += 1;
     }
 
-    named_functions
+    None
+}
+
+fn skip_to_next_named_function(
+    tokens: &mut Tokens,
+) -> result::Result<(), NoMoreTokens> {
+    loop {
+        tokens.skip_until_depth(0)?;
+
+        // A named function starts with either a comment, or a name followed
+        // by an introducer. Imports can come before the named functions.
+        match (tokens.peek()?, tokens.peek_nth(1)) {
+            (Token::CommentLine { .. }, _)
+            | (Token::Keyword(Import), _)
+            | (Token::Identifier { .. }, Ok(Token::Punctuator(Introducer))) => {
+                return Ok(());
+            }
+            _ => {
+                tokens.take()?;
+            }
+        }
+    }
 }
 
 fn parse_named_function(
     tokens: &mut Tokens,
     index: Index<NamedFunction>,
+    output: &mut Output,
 ) -> Result<NamedFunction> {
     let comment = parse_comment(tokens)?;
+
+    let start = tokens.next_span();
     let name = parse_function_name(tokens)?;
 
     let location = FunctionLocation::Named { index };
-    let function = parse_function(tokens, location)?;
+    let function = parse_function(tokens, location, output)?;
+
+    output
+        .spans
+        .named_functions
+        .insert(index, start.join(tokens.previous_span()));
 
     Ok(NamedFunction {
         comment,
+        module: output.module.clone(),
         name,
         inner: function,
     })
 }
 
+fn parse_import(
+    tokens: &mut Tokens,
+    index: usize,
+    output: &mut Output,
+) -> Result<Import> {
+    let comment = parse_comment(tokens)?;
+
+    let start = tokens.next_span();
+    match tokens.take()? {
+        Token::Keyword(Import) => {}
+        token => {
+            return Err(Error::unexpected_token(token, tokens));
+        }
+    }
+
+    let module = match tokens.take()? {
+        Token::Identifier { name } => name,
+        token => {
+            return Err(Error::unexpected_token(token, tokens));
+        }
+    };
+
+    output.spans.imports.insert(
+        (output.module.clone(), index),
+        start.join(tokens.previous_span()),
+    );
+
+    Ok(Import { comment, module })
+}
+
 fn parse_comment(tokens: &mut Tokens) -> Result<Option<Comment>> {
     let mut lines = Vec::new();
 
     let mut indentation = None;
 
-    while let Token::CommentLine { line } = tokens.peek()? {
+    // Comments can be at the end of the input, so running out of tokens is not
+    // an error here.
+    while let Ok(Token::CommentLine { line }) = tokens.peek() {
         let offset = indentation.or_else(|| {
             let first_after_whitespace = line.split_whitespace().next()?;
             let whitespace = line.split(first_after_whitespace).next()?;
@@ -114,14 +245,22 @@ fn parse_function_name(tokens: &mut Tokens) -> Result<String> {
     let name = match tokens.take()? {
         Token::Identifier { name } => name,
         token => {
-            return Err(Error::UnexpectedToken { actual: token });
+            return Err(Error::unexpected_token(token, tokens));
         }
     };
 
-    match tokens.take()? {
-        Token::Punctuator(Introducer) => {}
+    // If the name isn't followed by an introducer, the token that follows
+    // might be the name of the next named function. Let's not take it, so we
+    // can recover from there.
+    match tokens.peek()? {
+        Token::Punctuator(Introducer) => {
+            tokens.take()?;
+        }
         token => {
-            return Err(Error::UnexpectedToken { actual: token });
+            return Err(Error::UnexpectedToken {
+                actual: token.clone(),
+                span: tokens.next_span(),
+            });
         }
     }
 
@@ -131,71 +270,104 @@ fn parse_function_name(tokens: &mut Tokens) -> Result<String> {
 fn parse_function(
     tokens: &mut Tokens,
     location: FunctionLocation,
+    output: &mut Output,
 ) -> Result<Function> {
     let mut branches = IndexMap::default();
 
+    let start = tokens.next_span();
     match tokens.take()? {
         Token::Keyword(Fn) => {}
         token => {
-            return Err(Error::UnexpectedToken { actual: token });
+            return Err(Error::unexpected_token(token, tokens));
         }
     }
 
-    loop {
+    let depth = tokens.depth();
+
+    let trailing_comment = loop {
+        if next_after_comments(tokens) == Some(&Token::Keyword(End)) {
+            // Whatever comments are left belong to the function, not to
+            // another branch.
+            break parse_comment(tokens)?;
+        }
+
         let location = BranchLocation {
             parent: Box::new(location.clone()),
             index: branches.next_index(),
         };
 
-        let Some(branch) = parse_branch(tokens, location)? else {
-            break;
+        let branch = match parse_branch(tokens, location, output) {
+            Ok(branch) => branch,
+            Err(Error::NoMoreTokens(err)) => {
+                return Err(err.into());
+            }
+            Err(err) => {
+                // We can't represent the broken branch in the syntax tree.
+                // Let's skip the rest of it and continue with the next one.
+                output
+                    .errors
+                    .push(err.into_syntax_error(tokens, &output.module));
+                tokens.skip_until_depth(depth)?;
+                continue;
+            }
         };
 
         branches.push(branch);
-    }
+    };
 
     match tokens.take()? {
         Token::Keyword(End) => {}
         token => {
-            return Err(Error::UnexpectedToken { actual: token });
+            return Err(Error::unexpected_token(token, tokens));
         }
     }
 
-    Ok(Function { branches })
+    output
+        .spans
+        .functions
+        .insert(location, start.join(tokens.previous_span()));
+
+    Ok(Function {
+        branches,
+        trailing_comment,
+    })
 }
 
 fn parse_branch(
     tokens: &mut Tokens,
     location: BranchLocation,
-) -> Result<Option<Branch>> {
+    output: &mut Output,
+) -> Result<Branch> {
     let comment = parse_comment(tokens)?;
 
-    match tokens.peek()? {
-        Token::Keyword(Br) => {
-            tokens.take()?;
-        }
-        Token::Keyword(End) => {
-            // This would be the `end` of the function, not the branch, and
-            // hence our sign that no more branches can be parsed here.
-            return Ok(None);
-        }
-        _ => {
-            let token = tokens.take()?;
-            return Err(Error::UnexpectedToken { actual: token });
+    let start = tokens.next_span();
+    match tokens.take()? {
+        Token::Keyword(Br) => {}
+        token => {
+            return Err(Error::unexpected_token(token, tokens));
         }
     }
 
-    let parameters = parse_branch_parameters(tokens)?;
-    let body = parse_branch_body(tokens, location)?;
+    let parameters = parse_branch_parameters(tokens, &location, output)?;
+    let body = parse_branch_body(tokens, location.clone(), output)?;
+
+    output
+        .spans
+        .branches
+        .insert(location, start.join(tokens.previous_span()));
 
-    Ok(Some(Branch {
+    Ok(Branch {
         comment,
         parameters,
         body,
-    }))
+    })
 }
 
-fn parse_branch_parameters(tokens: &mut Tokens) -> Result<IndexMap<Parameter>> {
+fn parse_branch_parameters(
+    tokens: &mut Tokens,
+    location: &BranchLocation,
+    output: &mut Output,
+) -> Result<IndexMap<Parameter>> {
     let mut parameters = IndexMap::default();
 
     loop {
@@ -204,8 +376,17 @@ fn parse_branch_parameters(tokens: &mut Tokens) -> Result<IndexMap<Parameter>> {
             break;
         }
 
+        let start = tokens.next_span();
         let parameter = parse_parameter(tokens)?;
-        parameters.push(parameter);
+        let index = parameters.push(parameter);
+
+        output.spans.parameters.insert(
+            ParameterLocation {
+                parent: Box::new(location.clone()),
+                index,
+            },
+            start.join(tokens.previous_span()),
+        );
 
         match tokens.take()? {
             Token::Punctuator(Delimiter) => {
@@ -220,7 +401,7 @@ fn parse_branch_parameters(tokens: &mut Tokens) -> Result<IndexMap<Parameter>> {
                 break;
             }
             token => {
-                return Err(Error::UnexpectedToken { actual: token });
+                return Err(Error::unexpected_token(token, tokens));
             }
         }
     }
@@ -242,7 +423,7 @@ fn parse_parameter(tokens: &mut Tokens) -> Result<Parameter> {
             value: value.into(),
         },
         token => {
-            return Err(Error::UnexpectedToken { actual: token });
+            return Err(Error::unexpected_token(token, tokens));
         }
     };
 
@@ -252,9 +433,14 @@ fn parse_parameter(tokens: &mut Tokens) -> Result<Parameter> {
 fn parse_branch_body(
     tokens: &mut Tokens,
     location: BranchLocation,
+    output: &mut Output,
 ) -> Result<IndexMap<Member>> {
     let mut body = IndexMap::default();
 
+    // We've already taken the `br` that opened this branch. If we drop below
+    // this depth, the branch has ended.
+    let depth = tokens.depth();
+
     loop {
         if let Token::Keyword(End) = tokens.peek()? {
             tokens.take()?;
@@ -265,7 +451,39 @@ fn parse_branch_body(
             parent: Box::new(location.clone()),
             index: body.next_index(),
         };
-        let member = parse_member(tokens, location)?;
+        let start = tokens.next_span();
+        let member = match parse_member(tokens, location.clone(), output) {
+            Ok(member) => member,
+            Err(Error::NoMoreTokens(err)) => {
+                return Err(err.into());
+            }
+            Err(err) => {
+                let error = err.into_syntax_error(tokens, &output.module);
+
+                // After an unexpected token, we can't know where the next
+                // expression starts. Let's skip the rest of the branch, up to
+                // and including its `end`, and leave an error in its place.
+                //
+                // The unexpected token might have been that `end` itself. In
+                // that case, there's nothing left to skip.
+                let message = error.message.clone();
+                output.errors.push(error);
+                if tokens.depth() >= depth {
+                    tokens.skip_until_depth(depth - 1)?;
+                }
+
+                output
+                    .spans
+                    .members
+                    .insert(location, start.join(tokens.previous_span()));
+
+                body.push(Member::Expression {
+                    expression: Expression::Error { message },
+                    signature: None,
+                });
+                break;
+            }
+        };
         body.push(member);
     }
 
@@ -275,11 +493,20 @@ fn parse_branch_body(
 fn parse_member(
     tokens: &mut Tokens,
     location: MemberLocation,
+    output: &mut Output,
 ) -> Result<Member> {
+    let start = tokens.next_span();
+
     let member = if let Some(comment) = parse_comment(tokens)? {
+        output
+            .spans
+            .members
+            .insert(location, start.join(tokens.previous_span()));
+
         Member::Comment(comment)
     } else {
-        let (expression, signature) = parse_expression(tokens, location)?;
+        let (expression, signature) =
+            parse_expression(tokens, location, output)?;
 
         Member::Expression {
             expression,
@@ -293,10 +520,15 @@ fn parse_member(
 fn parse_expression(
     tokens: &mut Tokens,
     location: MemberLocation,
+    output: &mut Output,
 ) -> Result<(Expression, Option<Signature<SyntaxType>>)> {
+    let start = tokens.next_span();
+
     let expression = if let Token::Keyword(Fn) = tokens.peek()? {
-        let location = FunctionLocation::Local { location };
-        parse_function(tokens, location)
+        let location = FunctionLocation::Local {
+            location: location.clone(),
+        };
+        parse_function(tokens, location, output)
             .map(|function| Expression::LocalFunction { function })?
     } else {
         match tokens.take()? {
@@ -304,12 +536,33 @@ fn parse_expression(
             Token::IntegerLiteral { value } => Expression::LiteralNumber {
                 value: value.into(),
             },
+            Token::Punctuator(Terminator) => match tokens.take()? {
+                Token::Identifier { name } => {
+                    Expression::FieldAccess { field: name }
+                }
+                token => {
+                    return Err(Error::unexpected_token(token, tokens));
+                }
+            },
+            Token::Punctuator(RecordStart) => {
+                let fields = parse_record(tokens, |_| Ok(()))?
+                    .into_iter()
+                    .map(|(name, ())| name)
+                    .collect();
+
+                Expression::Record { fields }
+            }
             token => {
-                return Err(Error::UnexpectedToken { actual: token });
+                return Err(Error::unexpected_token(token, tokens));
             }
         }
     };
 
+    output
+        .spans
+        .members
+        .insert(location, start.join(tokens.previous_span()));
+
     let signature = parse_signature_annotation(tokens)?;
 
     Ok((expression, signature))
@@ -335,15 +588,18 @@ fn parse_signature_annotation(
     tokens.take()?;
 
     let terminator = Token::Punctuator(Terminator);
-    let signature = parse_signature(tokens, terminator)?;
+    let (signature, _) = parse_signature(tokens, &[terminator])?;
 
     Ok(Some(signature))
 }
 
+/// # Parse a signature, up to and including one of the provided terminators
+///
+/// Returns the terminator that ended the signature, along with the signature.
 fn parse_signature(
     tokens: &mut Tokens,
-    terminator: Token,
-) -> Result<Signature<SyntaxType>> {
+    terminators: &[Token],
+) -> Result<(Signature<SyntaxType>, Token)> {
     let mut inputs = Vec::new();
 
     loop {
@@ -363,21 +619,20 @@ fn parse_signature(
                 break;
             }
             token => {
-                return Err(Error::UnexpectedToken { actual: token });
+                return Err(Error::unexpected_token(token, tokens));
             }
         }
     }
 
     let mut outputs = Vec::new();
 
-    loop {
+    let terminator = loop {
         // We already handle the terminator at the end of the loop. We have to
         // also handle it here, since it's allowed to not have any outputs,
         // which would make the terminator the first token we encounter in this
         // loop.
-        if *tokens.peek()? == terminator {
-            tokens.take()?;
-            break;
+        if terminators.contains(tokens.peek()?) {
+            break tokens.take()?;
         }
 
         let type_ = parse_type(tokens)?;
@@ -387,41 +642,355 @@ fn parse_signature(
             Token::Punctuator(Delimiter) => {
                 continue;
             }
-            token if token == terminator => {
-                break;
+            token if terminators.contains(&token) => {
+                break token;
             }
             token => {
-                return Err(Error::UnexpectedToken { actual: token });
+                return Err(Error::unexpected_token(token, tokens));
             }
         }
-    }
+    };
 
-    Ok(Signature { inputs, outputs })
+    Ok((Signature { inputs, outputs }, terminator))
 }
 
 fn parse_type(tokens: &mut Tokens) -> Result<SyntaxType> {
     let type_ = match tokens.take()? {
         Token::Identifier { name } => SyntaxType::Identifier { name },
         Token::Keyword(Fn) => {
-            let terminator = Token::Keyword(End);
-            let signature = parse_signature(tokens, terminator)?;
-            SyntaxType::Function { signature }
+            let terminators = [Token::Keyword(Captures), Token::Keyword(End)];
+            let (signature, terminator) =
+                parse_signature(tokens, &terminators)?;
+
+            let mut environment = Vec::new();
+
+            if terminator == Token::Keyword(Captures) {
+                loop {
+                    environment.push(parse_type(tokens)?);
+
+                    match tokens.take()? {
+                        Token::Punctuator(Delimiter) => {
+                            continue;
+                        }
+                        Token::Keyword(End) => {
+                            break;
+                        }
+                        token => {
+                            return Err(Error::unexpected_token(token, tokens));
+                        }
+                    }
+                }
+            }
+
+            SyntaxType::Function {
+                signature,
+                environment,
+            }
+        }
+        Token::Punctuator(RecordStart) => {
+            let fields = parse_record(tokens, |tokens| {
+                match tokens.take()? {
+                    Token::Punctuator(Introducer) => {}
+                    token => {
+                        return Err(Error::unexpected_token(token, tokens));
+                    }
+                }
+
+                parse_type(tokens)
+            })?;
+
+            SyntaxType::Record { fields }
         }
         token => {
-            return Err(Error::UnexpectedToken { actual: token });
+            return Err(Error::unexpected_token(token, tokens));
         }
     };
 
     Ok(type_)
 }
 
+/// # Parse the fields of a record, after its opening `{` has been taken
+///
+/// This is used for both record types and record expressions. The provided
+/// closure parses whatever follows the name of each field.
+fn parse_record<T>(
+    tokens: &mut Tokens,
+    mut parse_field: impl FnMut(&mut Tokens) -> Result<T>,
+) -> Result<Vec<(String, T)>> {
+    let mut fields = Vec::new();
+
+    loop {
+        if let Token::Punctuator(RecordEnd) = tokens.peek()? {
+            tokens.take()?;
+            break;
+        }
+
+        let name = match tokens.take()? {
+            Token::Identifier { name } => name,
+            token => {
+                return Err(Error::unexpected_token(token, tokens));
+            }
+        };
+        if fields.iter().any(|(other, _)| *other == name) {
+            return Err(Error::DuplicateField {
+                name,
+                span: tokens.previous_span(),
+            });
+        }
+
+        let field = parse_field(tokens)?;
+        fields.push((name, field));
+
+        match tokens.take()? {
+            Token::Punctuator(Delimiter) => {
+                continue;
+            }
+            Token::Punctuator(RecordEnd) => {
+                break;
+            }
+            token => {
+                return Err(Error::unexpected_token(token, tokens));
+            }
+        }
+    }
+
+    Ok(fields)
+}
+
 type Result<T> = result::Result<T, Error>;
 
 #[derive(Debug, thiserror::Error)]
 enum Error {
-    #[error(transparent)]
+    #[error("Unexpected end of input")]
     NoMoreTokens(#[from] NoMoreTokens),
 
-    #[error("Unexpected token: {actual:?}")]
-    UnexpectedToken { actual: Token },
+    #[error("Unexpected token: `{actual}`")]
+    UnexpectedToken { actual: Token, span: Span },
+
+    #[error("Duplicate field: `{name}`")]
+    DuplicateField { name: String, span: Span },
+}
+
+impl Error {
+    /// # Create an error for a token that has just been taken
+    fn unexpected_token(actual: Token, tokens: &Tokens) -> Self {
+        Self::UnexpectedToken {
+            actual,
+            span: tokens.previous_span(),
+        }
+    }
+
+    fn into_syntax_error(self, tokens: &Tokens, module: &str) -> SyntaxError {
+        let span = match &self {
+            Self::NoMoreTokens(_) => tokens.next_span(),
+            Self::UnexpectedToken { span, .. }
+            | Self::DuplicateField { span, .. } => *span,
+        };
+
+        SyntaxError {
+            message: self.to_string(),
+            module: module.to_string(),
+            span,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::code::{
+        syntax::{Expression, Member, SyntaxTree},
+        Tokens,
+    };
+
+    #[test]
+    fn recover_from_error_in_branch_body() {
+        let syntax_tree = parse(
+            r"
+                f: fn
+                    br ->
+                        nop
+                        a : b c
+                        nop
+                    end
+                    br ->
+                        nop
+                    end
+                end
+            ",
+        );
+
+        assert_eq!(syntax_tree.errors.len(), 1);
+
+        let f = syntax_tree.function_by_name("f").unwrap();
+        let mut branches = f.inner.branches.values();
+
+        let body = branches.next().unwrap().body.values().collect::<Vec<_>>();
+        assert_eq!(body.len(), 2);
+        assert!(matches!(
+            body[1],
+            Member::Expression {
+                expression: Expression::Error { .. },
+                ..
+            }
+        ));
+
+        let body = branches.next().unwrap().body.values().collect::<Vec<_>>();
+        assert_eq!(body.len(), 1);
+    }
+
+    #[test]
+    fn recover_from_error_in_branch_parameters() {
+        let syntax_tree = parse(
+            r"
+                f: fn
+                    br a b ->
+                        nop
+                    end
+                    br ->
+                        nop
+                    end
+                end
+            ",
+        );
+
+        assert_eq!(syntax_tree.errors.len(), 1);
+
+        let f = syntax_tree.function_by_name("f").unwrap();
+        assert_eq!(f.inner.branches.len(), 1);
+    }
+
+    #[test]
+    fn recover_from_error_between_named_functions() {
+        let syntax_tree = parse(
+            r"
+                f: fn
+                    br ->
+                        nop
+                    end
+                end
+
+                garbage
+
+                g: fn
+                    br ->
+                        nop
+                    end
+                end
+            ",
+        );
+
+        assert_eq!(syntax_tree.errors.len(), 1);
+        assert!(syntax_tree.function_by_name("f").is_some());
+        assert!(syntax_tree.function_by_name("g").is_some());
+    }
+
+    #[test]
+    fn report_unexpected_end_of_input() {
+        let syntax_tree = parse(
+            r"
+                f: fn
+                    br ->
+                        nop
+                    end
+            ",
+        );
+
+        assert_eq!(syntax_tree.errors.len(), 1);
+        assert!(syntax_tree.named_functions.is_empty());
+    }
+
+    #[test]
+    fn error_span_points_to_unexpected_token() {
+        let source = "f: fn br -> a : b c end end";
+        let syntax_tree = parse(source);
+
+        let [error] = syntax_tree.errors.as_slice() else {
+            panic!("Expected exactly one error.");
+        };
+        assert_eq!(
+            &source[error.span.start.offset..error.span.end.offset],
+            "c"
+        );
+    }
+
+    #[test]
+    fn track_spans_of_members() {
+        let source = r"
+                f: fn
+                    br ->
+                        nop
+                        fn br -> end end
+                    end
+                end
+            ";
+        let syntax_tree = parse(source);
+
+        let (nop, local) = syntax_tree
+            .function_by_name("f")
+            .unwrap()
+            .into_located_function()
+            .find_single_branch()
+            .unwrap()
+            .expressions()
+            .map(|expression| syntax_tree.spans.members[&expression.location])
+            .collect_tuple()
+            .unwrap();
+
+        assert_eq!((nop.start.line, nop.start.column), (3, 24));
+        assert_eq!((nop.end.line, nop.end.column), (3, 27));
+        assert_eq!(
+            &source[local.start.offset..local.end.offset],
+            "fn br -> end end"
+        );
+        assert_eq!(nop.to_string(), "4:25");
+    }
+
+    #[test]
+    fn parse_imports_at_start_of_module() {
+        let source = r"
+                # Vectors
+                import vec
+                import color
+
+                f: fn br -> end end
+            ";
+        let syntax_tree = parse(source);
+
+        assert!(syntax_tree.errors.is_empty());
+        assert!(syntax_tree.function_by_name("f").is_some());
+
+        let module = &syntax_tree.modules[SyntaxTree::MAIN_MODULE];
+        assert!(module.imports("vec"));
+        assert!(module.imports("color"));
+
+        let span = syntax_tree.spans.imports
+            [&(SyntaxTree::MAIN_MODULE.to_string(), 0)];
+        assert_eq!(&source[span.start.offset..span.end.offset], "import vec");
+    }
+
+    #[test]
+    fn reject_imports_after_named_functions() {
+        let syntax_tree = parse(
+            r"
+                f: fn
+                    br ->
+                    end
+                end
+
+                import vec
+            ",
+        );
+
+        assert_eq!(syntax_tree.errors.len(), 1);
+        assert!(syntax_tree.modules[SyntaxTree::MAIN_MODULE]
+            .imports
+            .is_empty());
+    }
+
+    fn parse(source: &str) -> SyntaxTree {
+        let tokens = Tokens::tokenize(source);
+        SyntaxTree::parse(tokens)
+    }
 }
@@ -0,0 +1,21 @@
+use crate::code::tokens::Span;
+
+/// # An error that the parser encountered, and then recovered from
+///
+/// The parser doesn't stop at the first error. It records the error and tries
+/// to find its way back to a point from where it can continue parsing. Where
+/// possible, it also leaves an [`Expression::Error`] in the syntax tree, where
+/// the error happened.
+///
+/// [`Expression::Error`]: super::expression::Expression::Error
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct SyntaxError {
+    /// # A message describing the error
+    pub message: String,
+
+    /// # The module in which the error was encountered
+    pub module: String,
+
+    /// # The span of source code where the error was encountered
+    pub span: Span,
+}
@@ -15,6 +15,14 @@ use super::function::Function;
     udigest::Digestable,
 )]
 pub enum Expression {
+    /// # Access a field of a record
+    ///
+    /// Consumes the record and leaves the value of the field.
+    FieldAccess {
+        /// # The name of the field
+        field: String,
+    },
+
     /// # An identifier
     ///
     /// Can refer to a binding or function.
@@ -34,6 +42,27 @@ pub enum Expression {
         /// # The local function
         function: Function,
     },
+
+    /// # Construct a record
+    ///
+    /// Consumes one operand per field, with the value of the first field
+    /// expected to be the one furthest from the top of the stack.
+    Record {
+        /// # The names of the record's fields, in order
+        fields: Vec<String>,
+    },
+
+    /// # An expression that could not be parsed
+    ///
+    /// The parser leaves this in place of the code it had to skip, after
+    /// encountering an error. The error itself is tracked in
+    /// [`SyntaxTree::errors`].
+    ///
+    /// [`SyntaxTree::errors`]: super::syntax_tree::SyntaxTree::errors
+    Error {
+        /// # A message describing the error
+        message: String,
+    },
 }
 
 impl Expression {
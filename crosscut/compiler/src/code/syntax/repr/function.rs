@@ -13,6 +13,11 @@ pub struct NamedFunction {
     /// # The comment about the named function, if any
     pub comment: Option<Comment>,
 
+    /// # The module that the function is defined in
+    ///
+    /// Each source file is a module, named after the file.
+    pub module: String,
+
     /// # The name of the function
     pub name: String,
 
@@ -42,6 +47,9 @@ pub struct Function {
     /// called, its arguments are matched against the parameters of each branch,
     /// until one branch matches. This branch is then evaluated.
     pub branches: IndexMap<Branch>,
+
+    /// # The comment after the last branch, right before the `end`, if any
+    pub trailing_comment: Option<Comment>,
 }
 
 /// # A branch within a function
@@ -1,4 +1,7 @@
+pub mod error;
 pub mod expression;
 pub mod function;
+pub mod module;
+pub mod spans;
 pub mod syntax_tree;
 pub mod types;
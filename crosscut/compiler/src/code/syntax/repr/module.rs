@@ -0,0 +1,40 @@
+use super::function::Comment;
+
+/// # A module
+///
+/// Each source file is a module, named after the file. The functions of a
+/// module are tracked in [`SyntaxTree::named_functions`], alongside those of
+/// all other modules.
+///
+/// [`SyntaxTree::named_functions`]:
+///     super::syntax_tree::SyntaxTree::named_functions
+#[derive(
+    Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize,
+)]
+pub struct Module {
+    /// # The modules that this module imports
+    pub imports: Vec<Import>,
+
+    /// # The comment after the last named function, if any
+    pub trailing_comment: Option<Comment>,
+}
+
+impl Module {
+    /// # Determine, if this module imports the module with the provided name
+    pub fn imports(&self, module: &str) -> bool {
+        self.imports.iter().any(|import| import.module == module)
+    }
+}
+
+/// # An import of another module
+///
+/// A function can only call functions in another module, if its own module
+/// imports that module.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Import {
+    /// # The comment about the import, if any
+    pub comment: Option<Comment>,
+
+    /// # The name of the imported module
+    pub module: String,
+}
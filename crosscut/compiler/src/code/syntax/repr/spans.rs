@@ -0,0 +1,58 @@
+use std::collections::BTreeMap;
+
+use crate::code::{
+    syntax::{
+        BranchLocation, FunctionLocation, MemberLocation, ParameterLocation,
+    },
+    tokens::Span,
+    Index,
+};
+
+use super::function::NamedFunction;
+
+/// # The spans of source code that the syntax nodes were parsed from
+///
+/// ## Implementation Note
+///
+/// The spans are tracked here, instead of within the syntax nodes themselves.
+/// Those are compared and hashed to detect changes between versions of the
+/// code, and a span changes whenever any code before it changes. Having spans
+/// in there would make every function look like it was updated, as soon as a
+/// single line was added at the top.
+#[derive(
+    Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize,
+)]
+pub struct Spans {
+    /// # The spans of the named functions, starting at their name
+    pub named_functions: BTreeMap<Index<NamedFunction>, Span>,
+
+    /// # The spans of all functions, from `fn` to `end`
+    pub functions: BTreeMap<FunctionLocation, Span>,
+
+    /// # The spans of all branches, from `br` to `end`
+    pub branches: BTreeMap<BranchLocation, Span>,
+
+    /// # The spans of all parameters, including their type annotations
+    pub parameters: BTreeMap<ParameterLocation, Span>,
+
+    /// # The spans of all members, not including their type annotations
+    pub members: BTreeMap<MemberLocation, Span>,
+
+    /// # The spans of all imports, from `import` to the module name
+    ///
+    /// Imports are identified by their module, and their position within the
+    /// imports of that module.
+    pub imports: BTreeMap<(String, usize), Span>,
+}
+
+impl Spans {
+    /// # Add the provided spans to these ones
+    pub fn extend(&mut self, other: Self) {
+        self.named_functions.extend(other.named_functions);
+        self.functions.extend(other.functions);
+        self.branches.extend(other.branches);
+        self.parameters.extend(other.parameters);
+        self.members.extend(other.members);
+        self.imports.extend(other.imports);
+    }
+}
@@ -1,4 +1,4 @@
-use std::iter;
+use std::{collections::BTreeMap, iter};
 
 use crate::code::{
     syntax::{
@@ -8,20 +8,53 @@ use crate::code::{
     IndexMap, Tokens,
 };
 
-use super::function::{Binding, Branch, Function, NamedFunction, Parameter};
+use super::{
+    error::SyntaxError,
+    function::{Binding, Branch, Function, NamedFunction, Parameter},
+    module::Module,
+    spans::Spans,
+};
 
 /// # The syntax tree
 ///
 /// See [parent module](super).
 #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
 pub struct SyntaxTree {
+    /// # The modules that make up the code, by name
+    ///
+    /// Every module that was parsed is listed here, even if it's empty.
+    pub modules: BTreeMap<String, Module>,
+
     pub named_functions: IndexMap<NamedFunction>,
+
+    /// # The errors that the parser encountered
+    ///
+    /// The parser recovers from errors, which means the syntax tree can still
+    /// be used, even if this isn't empty. But it might not contain all the
+    /// code that was given to the parser.
+    pub errors: Vec<SyntaxError>,
+
+    /// # The spans of source code that the syntax nodes were parsed from
+    pub spans: Spans,
 }
 
 impl SyntaxTree {
+    /// # The name of the module that contains the `main` function
+    pub const MAIN_MODULE: &str = "main";
+
+    /// # Parse the provided tokens as the main module
+    ///
+    /// This is a shortcut for code that consists of only a single module. Use
+    /// [`SyntaxTree::parse_module`] to parse code made up of multiple modules.
     pub fn parse(tokens: Tokens) -> Self {
-        let named_functions = parse(tokens);
-        Self { named_functions }
+        let mut syntax_tree = Self::default();
+        syntax_tree.parse_module(Self::MAIN_MODULE, tokens);
+        syntax_tree
+    }
+
+    /// # Parse the provided tokens and add them to the tree as a module
+    pub fn parse_module(&mut self, module: &str, tokens: Tokens) {
+        parse(tokens, module, self);
     }
 
     /// # Find the function at the provided location
@@ -92,13 +125,66 @@ impl SyntaxTree {
 
     /// # Find the function with the provided name
     ///
+    /// The name is resolved relative to the main module. See
+    /// [`SyntaxTree::function_by_path`].
+    ///
     /// Returns `None`, if no function with this name can be found.
     pub fn function_by_name(
         &self,
         name: &str,
     ) -> Option<Located<&NamedFunction>> {
+        self.function_by_path(name, Self::MAIN_MODULE)
+    }
+
+    /// # Find the function with the provided path
+    ///
+    /// A path is either the plain name of a function (like `f`), or a name
+    /// that is qualified with the module that the function is defined in
+    /// (like `vec/add`). Plain names refer to functions in the provided module.
+    ///
+    /// This does not check whether the provided module imports the module of
+    /// the function. Use [`SyntaxTree::function_by_call`] for that.
+    ///
+    /// Returns `None`, if no function with this path can be found.
+    pub fn function_by_path(
+        &self,
+        path: &str,
+        module: &str,
+    ) -> Option<Located<&NamedFunction>> {
+        let (module, name) = path.rsplit_once('/').unwrap_or((module, path));
+
         self.named_functions()
-            .find(|function| function.name == name)
+            .find(|function| function.module == module && function.name == name)
+    }
+
+    /// # Find the function that a call from the provided module refers to
+    ///
+    /// Works like [`SyntaxTree::function_by_path`], except that functions in
+    /// other modules are only found, if the provided module imports them.
+    pub fn function_by_call(
+        &self,
+        path: &str,
+        module: &str,
+    ) -> Option<Located<&NamedFunction>> {
+        if let Some((other, _)) = path.rsplit_once('/') {
+            if !self.can_access_module(module, other) {
+                return None;
+            }
+        }
+
+        self.function_by_path(path, module)
+    }
+
+    /// # Determine, if functions in one module can call into another
+    ///
+    /// This is the case for functions in the same module, and for modules that
+    /// the calling module imports.
+    pub fn can_access_module(&self, module: &str, other: &str) -> bool {
+        module == other
+            || self
+                .modules
+                .get(module)
+                .is_some_and(|module| module.imports(other))
     }
 
     /// # Find the top-level parent of a given function
@@ -25,8 +25,17 @@ pub enum SyntaxType {
     Function {
         /// # The signature of the function
         signature: Signature<Self>,
+
+        /// # The types of the values that the function captures
+        environment: Vec<Self>,
     },
 
     /// # An identifier that refers to a type
     Identifier { name: String },
+
+    /// # A record type
+    Record {
+        /// # The names and types of the record's fields, in order
+        fields: Vec<(String, Self)>,
+    },
 }
@@ -4,11 +4,13 @@
 //! ignoring whitespace. They are the first semi-structured code representation
 //! that the compiler produces, laying the groundwork for parsing.
 
+mod span;
 mod token;
 mod tokenize;
 mod tokens;
 
 pub use self::{
+    span::{Position, Span},
     token::{Keyword, Punctuator, Token},
     tokens::{NoMoreTokens, Tokens},
 };
@@ -0,0 +1,118 @@
+use std::fmt;
+
+/// # A span of source code
+///
+/// The start is inclusive, the end is exclusive.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub struct Span {
+    /// # The position of the first character in the span
+    pub start: Position,
+
+    /// # The position right after the last character in the span
+    pub end: Position,
+}
+
+impl Span {
+    /// # Create an empty span at the provided position
+    pub fn at(position: Position) -> Self {
+        Self {
+            start: position,
+            end: position,
+        }
+    }
+
+    /// # Join two spans
+    ///
+    /// The resulting span starts where `self` starts and ends where `other`
+    /// ends.
+    pub fn join(self, other: Self) -> Self {
+        Self {
+            start: self.start,
+            end: other.end,
+        }
+    }
+
+    /// # Determine whether the span contains the provided line and column
+    ///
+    /// Only looks at lines and columns, ignoring the byte offset. This makes it
+    /// possible to check positions that come from outside the compiler (like
+    /// from an editor), where the offset is not known.
+    pub fn contains(&self, line: usize, column: usize) -> bool {
+        let start = (self.start.line, self.start.column);
+        let end = (self.end.line, self.end.column);
+
+        start <= (line, column) && (line, column) < end
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.start)
+    }
+}
+
+/// # A position in source code
+///
+/// Lines and columns start at zero, as that is easiest to work with in code.
+/// The [`fmt::Display`] implementation adds one to both, as is expected in
+/// messages to the developer.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub struct Position {
+    /// # The offset in bytes from the start of the source code
+    pub offset: usize,
+
+    /// # The line, starting at zero
+    pub line: usize,
+
+    /// # The column in characters, starting at zero
+    pub column: usize,
+}
+
+impl Position {
+    /// # Compute the position after the provided character
+    pub fn advance(self, ch: char) -> Self {
+        let offset = self.offset + ch.len_utf8();
+
+        if ch == '\n' {
+            Self {
+                offset,
+                line: self.line + 1,
+                column: 0,
+            }
+        } else {
+            Self {
+                offset,
+                line: self.line,
+                column: self.column + 1,
+            }
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line + 1, self.column + 1)
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 /// # A token
 ///
 /// See [parent module](super).
@@ -30,6 +32,18 @@ pub enum Token {
     Punctuator(Punctuator),
 }
 
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CommentLine { line } => write!(f, "#{line}"),
+            Self::Identifier { name } => write!(f, "{name}"),
+            Self::IntegerLiteral { value } => write!(f, "{value}"),
+            Self::Keyword(keyword) => write!(f, "{keyword}"),
+            Self::Punctuator(punctuator) => write!(f, "{punctuator}"),
+        }
+    }
+}
+
 /// # Keywords
 ///
 /// A keyword is a specific word with special meaning in the language, that is
@@ -39,11 +53,36 @@ pub enum Keyword {
     /// # The `br` keyword
     Br,
 
+    /// # The `captures` keyword
+    ///
+    /// Used in function types, to specify the types of the values that a
+    /// function captures from its environment.
+    Captures,
+
     /// # The `end` keyword
     End,
 
     /// # The `fn` keyword
     Fn,
+
+    /// # The `import` keyword
+    ///
+    /// Used at the top of a module, to import another module.
+    Import,
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let keyword = match self {
+            Self::Br => "br",
+            Self::Captures => "captures",
+            Self::End => "end",
+            Self::Fn => "fn",
+            Self::Import => "import",
+        };
+
+        write!(f, "{keyword}")
+    }
 }
 
 /// # Punctuators
@@ -69,5 +108,31 @@ pub enum Punctuator {
     Transformer,
 
     /// # A token that ends an expression, where necessary, rendered as `.`
+    ///
+    /// Also introduces a field access, in the form of `.x`.
     Terminator,
+
+    /// # The start of a record, rendered as `{`
+    ///
+    /// Records show up as types, in the form of `{ x: U8, y: U8 }`, and as
+    /// expressions that construct them, in the form of `{ x, y }`.
+    RecordStart,
+
+    /// # The end of a record, rendered as `}`
+    RecordEnd,
+}
+
+impl fmt::Display for Punctuator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let punctuator = match self {
+            Self::Delimiter => ",",
+            Self::Introducer => ":",
+            Self::Transformer => "->",
+            Self::Terminator => ".",
+            Self::RecordStart => "{",
+            Self::RecordEnd => "}",
+        };
+
+        write!(f, "{punctuator}")
+    }
 }
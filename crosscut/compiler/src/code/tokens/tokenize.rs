@@ -1,57 +1,105 @@
 use std::mem;
 
-use crate::code::tokens::Token;
+use crate::code::tokens::{Position, Span, Token};
 
 use super::{Keyword::*, Punctuator::*};
 
-pub fn tokenize(input: &str) -> Vec<Token> {
+pub fn tokenize(input: &str) -> Vec<(Token, Span)> {
     let eager_tokens = vec![
         (r",", Token::Punctuator(Delimiter)),
         (r":", Token::Punctuator(Introducer)),
         (r"->", Token::Punctuator(Transformer)),
         (r".", Token::Punctuator(Terminator)),
+        (r"{", Token::Punctuator(RecordStart)),
+        (r"}", Token::Punctuator(RecordEnd)),
     ];
 
     let mut state = State::Initial;
     let mut buffer = Buffer::default();
+    let mut position = Position::default();
 
     let mut tokens = Vec::new();
 
     for ch in input.chars() {
+        let next_position = position.advance(ch);
+
         match state {
             State::Initial => match ch {
                 '#' => {
-                    buffer.take_literal_or_keyword_or_identifier(&mut tokens);
-                    state = State::Comment;
+                    buffer.take_literal_or_keyword_or_identifier(
+                        position,
+                        &mut tokens,
+                    );
+                    state = State::Comment { start: position };
                 }
                 ch if ch.is_whitespace() => {
-                    buffer.take_literal_or_keyword_or_identifier(&mut tokens);
+                    buffer.take_literal_or_keyword_or_identifier(
+                        position,
+                        &mut tokens,
+                    );
                 }
                 ch => {
-                    buffer.push(ch);
+                    buffer.push(position, ch);
+                    let end = next_position;
 
                     for (s, token) in &eager_tokens {
                         if buffer.take_from_end(s) {
+                            // Eager tokens never contain a line break, so we
+                            // can just count back from the end.
+                            let start = Position {
+                                offset: end.offset - s.len(),
+                                line: end.line,
+                                column: end.column - s.chars().count(),
+                            };
+
                             buffer.take_literal_or_keyword_or_identifier(
+                                start,
                                 &mut tokens,
                             );
-                            tokens.push(token.clone());
+                            tokens.push((token.clone(), Span { start, end }));
                         }
                     }
                 }
             },
-            State::Comment => match ch {
+            State::Comment { start } => match ch {
                 '\n' => {
-                    tokens.push(Token::CommentLine {
-                        line: buffer.take(),
-                    });
+                    tokens.push((
+                        Token::CommentLine {
+                            line: buffer.take(),
+                        },
+                        Span {
+                            start,
+                            end: position,
+                        },
+                    ));
                     state = State::Initial;
                 }
                 ch => {
-                    buffer.push(ch);
+                    buffer.push(position, ch);
                 }
             },
         }
+
+        position = next_position;
+    }
+
+    // The input might not end in whitespace. Make sure we don't lose whatever
+    // is still left in the buffer.
+    match state {
+        State::Initial => {
+            buffer.take_literal_or_keyword_or_identifier(position, &mut tokens);
+        }
+        State::Comment { start } => {
+            tokens.push((
+                Token::CommentLine {
+                    line: buffer.take(),
+                },
+                Span {
+                    start,
+                    end: position,
+                },
+            ));
+        }
     }
 
     tokens
@@ -59,16 +107,21 @@ pub fn tokenize(input: &str) -> Vec<Token> {
 
 enum State {
     Initial,
-    Comment,
+    Comment { start: Position },
 }
 
 #[derive(Default)]
 struct Buffer {
     inner: String,
+    start: Position,
 }
 
 impl Buffer {
-    pub fn push(&mut self, ch: char) {
+    pub fn push(&mut self, position: Position, ch: char) {
+        if self.inner.is_empty() {
+            self.start = position;
+        }
+
         self.inner.push(ch);
     }
 
@@ -86,20 +139,29 @@ impl Buffer {
 
     pub fn take_literal_or_keyword_or_identifier(
         &mut self,
-        tokens: &mut Vec<Token>,
+        end: Position,
+        tokens: &mut Vec<(Token, Span)>,
     ) {
+        let start = self.start;
+
         tokens.extend(self.take_if_not_empty().map(|token| {
-            if let Ok(value) = token.parse() {
+            let token = if let Ok(value) = token.parse() {
                 Token::IntegerLiteral { value }
             } else if token == "br" {
                 Token::Keyword(Br)
+            } else if token == "captures" {
+                Token::Keyword(Captures)
             } else if token == "end" {
                 Token::Keyword(End)
             } else if token == "fn" {
                 Token::Keyword(Fn)
+            } else if token == "import" {
+                Token::Keyword(Import)
             } else {
                 Token::Identifier { name: token }
-            }
+            };
+
+            (token, Span { start, end })
         }));
     }
 
@@ -1,12 +1,15 @@
 use std::collections::VecDeque;
 
-use super::{tokenize::tokenize, Token};
+use super::{tokenize::tokenize, Keyword, Position, Span, Token};
 
 /// # The tokens in a script
 ///
 /// See [parent module](super).
 pub struct Tokens {
-    inner: VecDeque<Token>,
+    inner: VecDeque<(Token, Span)>,
+    previous: Span,
+    end_of_input: Span,
+    depth: usize,
 }
 
 impl Tokens {
@@ -16,19 +19,92 @@ impl Tokens {
     /// form.
     pub fn tokenize(input: &str) -> Self {
         let tokens = tokenize(input);
+        let end_of_input =
+            input.chars().fold(Position::default(), Position::advance);
+
         Self {
             inner: tokens.into(),
+            previous: Span::default(),
+            end_of_input: Span::at(end_of_input),
+            depth: 0,
         }
     }
 
     /// # Peek at the next token without taking it
     pub fn peek(&self) -> Result<&Token, NoMoreTokens> {
-        self.inner.front().ok_or(NoMoreTokens)
+        self.peek_nth(0)
+    }
+
+    /// # Peek at the token `n` positions after the next one
+    ///
+    /// `peek_nth(0)` is equivalent to [`Tokens::peek`].
+    pub fn peek_nth(&self, n: usize) -> Result<&Token, NoMoreTokens> {
+        self.inner
+            .get(n)
+            .map(|(token, _)| token)
+            .ok_or(NoMoreTokens)
     }
 
     /// # Take the next token
     pub fn take(&mut self) -> Result<Token, NoMoreTokens> {
-        self.inner.pop_front().ok_or(NoMoreTokens)
+        let (token, span) = self.inner.pop_front().ok_or(NoMoreTokens)?;
+
+        match token {
+            Token::Keyword(Keyword::Br | Keyword::Fn) => {
+                self.depth += 1;
+            }
+            Token::Keyword(Keyword::End) => {
+                self.depth = self.depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+
+        self.previous = span;
+
+        Ok(token)
+    }
+
+    /// # Access the span of the token that was taken last
+    ///
+    /// If no token has been taken yet, this returns an empty span at the start
+    /// of the input.
+    pub fn previous_span(&self) -> Span {
+        self.previous
+    }
+
+    /// # Access the span of the next token
+    ///
+    /// If there are no more tokens, this returns an empty span at the end of
+    /// the input.
+    pub fn next_span(&self) -> Span {
+        self.inner
+            .front()
+            .map(|(_, span)| *span)
+            .unwrap_or(self.end_of_input)
+    }
+
+    /// # The nesting depth after the tokens that have been taken so far
+    ///
+    /// Every `fn` and `br` keyword opens a new level of nesting, which is
+    /// closed again by an `end` keyword. The parser uses this to find its way
+    /// back to a known point, after encountering an unexpected token.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// # Take tokens until the nesting depth drops to the provided value
+    ///
+    /// Returns immediately, if the nesting depth is already at or below the
+    /// provided value.
+    pub fn skip_until_depth(
+        &mut self,
+        depth: usize,
+    ) -> Result<(), NoMoreTokens> {
+        while self.depth > depth {
+            self.take()?;
+        }
+
+        Ok(())
     }
 }
 
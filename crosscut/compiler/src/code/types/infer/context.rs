@@ -1,8 +1,10 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crosscut_runtime::Value;
 
 use crate::code::{
     syntax::{FunctionLocation, MemberLocation, ParameterLocation},
-    Index, Signature, Type,
+    Index, InferenceErrorLocation, Signature, Type,
 };
 
 use super::{
@@ -17,6 +19,17 @@ pub struct InferenceContext {
     pub functions: BTreeMap<FunctionLocation, InferredFunction>,
     pub bindings: BTreeMap<ParameterLocation, Index<InferredType>>,
     pub expressions: BTreeMap<MemberLocation, IndirectSignature>,
+    pub literals: Vec<InferredLiteral>,
+
+    /// # Expressions that can't be compiled without knowing their type
+    pub requires_type: BTreeSet<MemberLocation>,
+}
+
+/// # A literal, whose value must fit the type that gets inferred for it
+pub struct InferredLiteral {
+    pub value: Value,
+    pub type_: Index<InferredType>,
+    pub location: InferenceErrorLocation,
 }
 
 impl InferenceContext {
@@ -24,6 +37,7 @@ impl InferenceContext {
         &mut self,
         location: &FunctionLocation,
         functions: &BTreeMap<FunctionLocation, Signature>,
+        partial_functions: &BTreeMap<FunctionLocation, Signature<Option<Type>>>,
     ) -> Option<IndirectSignature> {
         functions
             .get(location)
@@ -33,6 +47,13 @@ impl InferenceContext {
                     &mut self.types,
                 )
             })
+            .or_else(|| {
+                let signature = partial_functions.get(location)?;
+                Some(IndirectSignature::from_partial(
+                    signature.clone(),
+                    &mut self.types,
+                ))
+            })
             .or_else(|| {
                 let function = self.functions.get(location).cloned()?;
                 function.to_signature()
@@ -22,6 +22,38 @@ pub struct InferredFunction {
 }
 
 impl InferredFunction {
+    /// # Check whether the other function has a different number of inputs
+    /// or outputs
+    ///
+    /// Branches of the same function must agree on how many inputs they
+    /// consume and how many outputs they produce. Returns a message describing
+    /// the mismatch, if that's not the case.
+    pub fn arity_mismatch(&self, other: &Self) -> Option<String> {
+        if self.inputs.len() != other.inputs.len() {
+            return Some(format!(
+                "Branch has {} parameter(s), but other branches of the \
+                function have {}.",
+                other.inputs.len(),
+                self.inputs.len(),
+            ));
+        }
+
+        if let (Some(self_outputs), Some(other_outputs)) =
+            (&self.outputs, &other.outputs)
+        {
+            if self_outputs.len() != other_outputs.len() {
+                return Some(format!(
+                    "Branch returns {} value(s), but other branches of the \
+                    function return {}.",
+                    other_outputs.len(),
+                    self_outputs.len(),
+                ));
+            }
+        }
+
+        None
+    }
+
     pub fn unify_with(&mut self, other: &mut Self, types: &mut InferredTypes) {
         unify_type_list([&self.inputs, &other.inputs], types);
 
@@ -62,22 +94,22 @@ mod tests {
 
         let a = InferredFunction {
             inputs: vec![
-                types.push(InferredType::Direct(Type::Number)),
+                types.push(InferredType::Direct(Type::S32)),
                 types.push(InferredType::Unknown),
             ],
             outputs: Some(vec![
-                types.push(InferredType::Direct(Type::Number)),
+                types.push(InferredType::Direct(Type::S32)),
                 types.push(InferredType::Unknown),
             ]),
         };
         let b = InferredFunction {
             inputs: vec![
                 types.push(InferredType::Unknown),
-                types.push(InferredType::Direct(Type::Number)),
+                types.push(InferredType::Direct(Type::S32)),
             ],
             outputs: Some(vec![
                 types.push(InferredType::Unknown),
-                types.push(InferredType::Direct(Type::Number)),
+                types.push(InferredType::Direct(Type::S32)),
             ]),
         };
 
@@ -92,8 +124,8 @@ mod tests {
                 assert_eq!(
                     signature,
                     Signature {
-                        inputs: vec![Type::Number, Type::Number],
-                        outputs: vec![Type::Number, Type::Number]
+                        inputs: vec![Type::S32, Type::S32],
+                        outputs: vec![Type::S32, Type::S32]
                     },
                 )
             }
@@ -106,7 +138,7 @@ mod tests {
 
         let a = InferredFunction {
             inputs: vec![],
-            outputs: Some(vec![types.push(InferredType::Direct(Type::Number))]),
+            outputs: Some(vec![types.push(InferredType::Direct(Type::S32))]),
         };
         let b = InferredFunction {
             inputs: vec![],
@@ -125,7 +157,7 @@ mod tests {
                     signature,
                     Signature {
                         inputs: vec![],
-                        outputs: vec![Type::Number]
+                        outputs: vec![Type::S32]
                     },
                 )
             }
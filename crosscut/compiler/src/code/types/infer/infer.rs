@@ -10,13 +10,14 @@ use crate::{
         },
         types::repr::Stacks,
         Bindings, Dependencies, DependencyCluster, Environment,
-        IdentifierTarget, Identifiers, Signature, Type, TypeAnnotations,
+        IdentifierTarget, Identifiers, InferenceError, InferenceErrorLocation,
+        Signature, Type, TypeAnnotations,
     },
     intrinsics::IntrinsicFunction,
 };
 
 use super::{
-    context::InferenceContext,
+    context::{InferenceContext, InferredLiteral},
     function::InferredFunction,
     signature::{self, IndirectSignature},
     stack::{LocalStack, MaybeLocalStack},
@@ -27,32 +28,7 @@ pub fn infer(compiler_context: CompilerContext) -> InferenceOutput {
     let mut output = InferenceOutput::default();
 
     for cluster in compiler_context.dependencies.clusters() {
-        if let Err(TypeError {
-            expected,
-            actual,
-            location,
-        }) = infer_cluster(cluster, compiler_context, &mut output)
-        {
-            let actual = actual
-                .map(|type_| format!("`{type_}`"))
-                .unwrap_or_else(|| "nothing".to_string());
-
-            let location = location
-                .map(|location| {
-                    format!(
-                        "at {}\n",
-                        location.display(compiler_context.syntax_tree)
-                    )
-                })
-                .unwrap_or(String::new());
-
-            panic!(
-                "\n\
-                Type error: expected {expected}, got {actual}\n\
-                \n\
-                {location}",
-            );
-        }
+        infer_cluster(cluster, compiler_context, &mut output);
     }
 
     output
@@ -70,19 +46,44 @@ pub struct CompilerContext<'r> {
 #[derive(Default)]
 pub struct InferenceOutput {
     pub functions: BTreeMap<FunctionLocation, Signature>,
+
+    /// # Signatures of functions that are only partially known
+    ///
+    /// The type of a literal can depend on where a function is called from. If
+    /// it's not known within the function itself, then neither is the
+    /// function's signature. Callers can still make use of what _is_ known.
+    pub partial_functions: BTreeMap<FunctionLocation, Signature<Option<Type>>>,
     pub expressions: BTreeMap<MemberLocation, Signature>,
     pub parameters: BTreeMap<ParameterLocation, Type>,
     pub stacks: Stacks,
+    pub errors: Vec<InferenceError>,
+}
+
+impl InferenceOutput {
+    fn record_error(
+        &mut self,
+        message: String,
+        location: InferenceErrorLocation,
+    ) {
+        // If something is wrong, that can easily cascade into more errors at
+        // the same location. Those don't add any useful information.
+        if self.errors.iter().any(|error| error.location == location) {
+            return;
+        }
+
+        self.errors.push(InferenceError { message, location });
+    }
 }
 
 fn infer_cluster(
     cluster: &DependencyCluster,
     compiler_context: CompilerContext,
     output: &mut InferenceOutput,
-) -> Result<()> {
+) {
     let mut inference_context = InferenceContext::default();
 
     for branch in cluster.branches(compiler_context.syntax_tree) {
+        let location = branch.location.clone();
         let function = (*branch.location.parent).clone();
 
         let environment = compiler_context.bindings.environment_of(&function);
@@ -93,9 +94,17 @@ fn infer_cluster(
             &mut inference_context,
             compiler_context,
             output,
-        )?;
+        );
 
         if let Some(function) = inference_context.functions.get_mut(&function) {
+            if let Some(message) = function.arity_mismatch(&branch) {
+                output.record_error(
+                    message,
+                    InferenceErrorLocation::Branch(location),
+                );
+                continue;
+            }
+
             function.unify_with(&mut branch, &mut inference_context.types);
         } else {
             inference_context.functions.insert(function.clone(), branch);
@@ -105,15 +114,28 @@ fn infer_cluster(
     // We have gone through all the branches in this branches, learning
     // everything about them there is to learn. Now we can go through the
     // inferred types and convert them into concrete types where possible.
-
+    //
+    // If that fails for functions or bindings, the conflicting types must have
+    // come from expressions. So we can rely on the errors being reported for
+    // those.
     for (location, function) in inference_context.functions {
         let Some(signature) = function.to_signature() else {
             continue;
         };
-        let Some(signature) =
-            signature.to_direct(&mut inference_context.types)?
-        else {
-            continue;
+        let signature = match signature.to_direct(&mut inference_context.types)
+        {
+            Ok(Some(signature)) => signature,
+            Ok(None) => {
+                if let Ok(signature) =
+                    signature.to_partial(&mut inference_context.types)
+                {
+                    output.partial_functions.insert(location, signature);
+                }
+                continue;
+            }
+            Err(_) => {
+                continue;
+            }
         };
 
         let existing = output.functions.insert(location, signature);
@@ -123,8 +145,8 @@ fn infer_cluster(
         );
     }
     for (location, index) in inference_context.bindings {
-        let InferredType::Direct(type_) =
-            inference_context.types.resolve(&index)?
+        let Ok(InferredType::Direct(type_)) =
+            inference_context.types.resolve(&index)
         else {
             continue;
         };
@@ -136,10 +158,19 @@ fn infer_cluster(
         );
     }
     for (location, signature) in inference_context.expressions {
-        let Some(signature) =
-            signature.to_direct(&mut inference_context.types)?
-        else {
-            continue;
+        let signature = match signature.to_direct(&mut inference_context.types)
+        {
+            Ok(Some(signature)) => signature,
+            Ok(None) => {
+                continue;
+            }
+            Err(err) => {
+                output.record_error(
+                    err.to_string(),
+                    InferenceErrorLocation::Expression(location),
+                );
+                continue;
+            }
         };
 
         let existing = output.expressions.insert(location, signature);
@@ -148,8 +179,37 @@ fn infer_cluster(
             "Did not expect to overwrite and existing expression signature.",
         );
     }
+    for location in inference_context.requires_type {
+        if output.expressions.contains_key(&location) {
+            continue;
+        }
 
-    Ok(())
+        output.record_error(
+            "Can't determine the type of the record, which is required to \
+            compile this expression."
+                .to_string(),
+            InferenceErrorLocation::Expression(location),
+        );
+    }
+    for literal in inference_context.literals {
+        let Ok(Some(type_)) = inference_context
+            .types
+            .resolve(&literal.type_)
+            .and_then(|type_| type_.into_type(&mut inference_context.types))
+        else {
+            continue;
+        };
+
+        if type_.literal_value(literal.value).is_none() {
+            output.record_error(
+                format!(
+                    "Literal `{}` is not a valid value of type `{type_}`.",
+                    literal.value.to_i32(),
+                ),
+                literal.location,
+            );
+        }
+    }
 }
 
 #[allow(clippy::type_complexity)]
@@ -159,7 +219,7 @@ fn infer_branch(
     inference_context: &mut InferenceContext,
     compiler_context: CompilerContext,
     output: &mut InferenceOutput,
-) -> Result<InferredFunction> {
+) -> InferredFunction {
     let mut register_binding =
         |location: ParameterLocation,
          parameters: &BTreeMap<ParameterLocation, Type>| {
@@ -181,7 +241,8 @@ fn infer_branch(
                     .annotations
                     .of_binding(&parameter.location)
                     .cloned(),
-                Parameter::Literal { .. } => Some(Type::Number),
+                // The type of a literal depends on the context it's used in.
+                Parameter::Literal { .. } => None,
             };
 
             if let Some(type_) = type_ {
@@ -196,6 +257,18 @@ fn infer_branch(
         register_binding(binding.location, &output.parameters);
     }
 
+    for (parameter, type_) in branch.parameters().zip(&parameters) {
+        if let Parameter::Literal { value } = parameter.fragment {
+            inference_context.literals.push(InferredLiteral {
+                value: *value,
+                type_: *type_,
+                location: InferenceErrorLocation::Branch(
+                    branch.location.clone(),
+                ),
+            });
+        }
+    }
+
     let mut local_stack = MaybeLocalStack::default();
     let mut stacks = BTreeMap::new();
 
@@ -206,14 +279,27 @@ fn infer_branch(
             stacks.insert(location.clone(), stack);
         }
 
-        if let Some(signature) = infer_expression(
+        match infer_expression(
             expression,
             inference_context,
             &mut local_stack,
             compiler_context,
             output,
-        )? {
-            inference_context.expressions.insert(location, signature);
+        ) {
+            Ok(Some(signature)) => {
+                inference_context.expressions.insert(location, signature);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                // We don't know what the expression would have done to the
+                // stack, so we can't keep track of it past this point.
+                local_stack.invalidate();
+
+                output.record_error(
+                    err.to_string(),
+                    InferenceErrorLocation::Expression(location),
+                );
+            }
         }
     }
 
@@ -221,11 +307,20 @@ fn infer_branch(
     // type of an earlier one. So let's handle the stacks we collected _after_
     // we look at all of the expressions.
     for (location, local_stack) in stacks {
-        let Some(local_stack) =
-            local_stack.make_direct(&mut inference_context.types)?
-        else {
-            continue;
-        };
+        let local_stack =
+            match local_stack.make_direct(&mut inference_context.types) {
+                Ok(Some(local_stack)) => local_stack,
+                Ok(None) => {
+                    continue;
+                }
+                Err(err) => {
+                    output.record_error(
+                        err.to_string(),
+                        InferenceErrorLocation::Expression(location),
+                    );
+                    continue;
+                }
+            };
 
         output.stacks.insert(location, local_stack);
     }
@@ -236,7 +331,7 @@ fn infer_branch(
         .cloned()
         .map(|local_stack| local_stack.inner);
 
-    Ok(InferredFunction { inputs, outputs })
+    InferredFunction { inputs, outputs }
 }
 
 fn infer_expression(
@@ -244,7 +339,7 @@ fn infer_expression(
     inference_context: &mut InferenceContext,
     local_stack: &mut MaybeLocalStack,
     compiler_context: CompilerContext,
-    output: &InferenceOutput,
+    output: &mut InferenceOutput,
 ) -> Result<Option<IndirectSignature>> {
     let explicit = compiler_context
         .annotations
@@ -258,6 +353,26 @@ fn infer_expression(
         });
 
     let inferred = match expression.fragment {
+        Expression::FieldAccess { field } => {
+            // We can only compile a field access, if we know the layout of the
+            // record. Let's make sure that's checked, once we're done.
+            inference_context
+                .requires_type
+                .insert(expression.location.clone());
+
+            local_stack
+                .get()
+                .map(|local_stack| {
+                    infer_field_access(
+                        field,
+                        &expression.location,
+                        &mut inference_context.types,
+                        local_stack,
+                    )
+                })
+                .transpose()?
+                .flatten()
+        }
         Expression::Identifier { name: identifier } => {
             match compiler_context
                 .identifiers
@@ -310,31 +425,62 @@ fn infer_expression(
                             .transpose()?
                     }
                     IdentifierTarget::UserDefinedFunction(location) => {
-                        inference_context.function(location, &output.functions)
+                        inference_context.function(
+                            location,
+                            &output.functions,
+                            &output.partial_functions,
+                        )
                     }
                 },
                 None => None,
             }
         }
-        Expression::LiteralNumber { .. } => {
+        Expression::LiteralNumber { value } => {
+            // The type of a literal depends on the context it's used in.
+            let output = inference_context.types.push(InferredType::Unknown);
+            inference_context.literals.push(InferredLiteral {
+                value: *value,
+                type_: output,
+                location: InferenceErrorLocation::Expression(
+                    expression.location.clone(),
+                ),
+            });
+
             let signature = Signature {
                 inputs: vec![],
-                outputs: vec![Type::Number],
+                outputs: vec![output],
             };
-            let signature = IndirectSignature::from_direct(
-                signature,
-                &mut inference_context.types,
-            );
             Some(signature)
         }
         Expression::LocalFunction { .. } => {
             let location = FunctionLocation::from(expression.location.clone());
+
+            // The values the local function captures become part of it. So
+            // their types are part of its type.
+            let environment = compiler_context
+                .bindings
+                .environment_of(&location)
+                .bindings(compiler_context.syntax_tree)
+                .map(|binding| {
+                    inference_context
+                        .binding(&binding.location, &output.parameters)
+                        .unwrap_or_else(|| {
+                            inference_context.types.push(InferredType::Unknown)
+                        })
+                })
+                .collect::<Vec<_>>();
+
             inference_context
-                .function(&location, &output.functions)
+                .function(
+                    &location,
+                    &output.functions,
+                    &output.partial_functions,
+                )
                 .map(|signature| {
                     let function = inference_context.types.push(
                         InferredType::IndirectFunction {
                             signature: signature.clone(),
+                            environment,
                         },
                     );
 
@@ -344,10 +490,34 @@ fn infer_expression(
                     }
                 })
         }
+        Expression::Record { fields } => {
+            inference_context
+                .requires_type
+                .insert(expression.location.clone());
+
+            let inputs = fields
+                .iter()
+                .map(|_| inference_context.types.push(InferredType::Unknown))
+                .collect::<Vec<_>>();
+            let output =
+                inference_context.types.push(InferredType::IndirectRecord {
+                    fields: fields
+                        .iter()
+                        .cloned()
+                        .zip(inputs.clone())
+                        .collect(),
+                });
+
+            Some(Signature {
+                inputs,
+                outputs: vec![output],
+            })
+        }
+        Expression::Error { .. } => None,
     };
 
-    if let [Some(explicit), Some(inferred)] =
-        [explicit.as_ref(), inferred.as_ref()].try_map_ext(|signature| {
+    if let [Some(_), Some(_)] = [explicit.as_ref(), inferred.as_ref()]
+        .try_map_ext(|signature| {
             signature
                 .and_then(|signature| {
                     signature
@@ -357,19 +527,14 @@ fn infer_expression(
                 .transpose()
         })?
     {
-        panic!(
-            "Type that could be inferred was also specified explicitly. This \
-            is currently not allowed, as the goal is to transition away from \
-            explicit type annotations completely.\n\
-            \n\
-            Explicit type: {explicit:?}\n\
-            Inferred type: {inferred:?}\n\
-            \n\
-            Expression: {:#?}
-            \n\
-            At {}\n",
-            expression.fragment,
-            expression.location.display(compiler_context.syntax_tree),
+        // The goal is to transition away from explicit type annotations
+        // completely. Until then, they are only allowed where the type can't
+        // be inferred.
+        output.record_error(
+            "Type annotation is not allowed here, because the type can be \
+            inferred."
+                .to_string(),
+            InferenceErrorLocation::Expression(expression.location.clone()),
         );
     }
 
@@ -390,7 +555,13 @@ fn infer_expression(
             for input in signature.inputs.iter().rev() {
                 match local_stack.inner.pop() {
                     Some(operand) => {
-                        inference_context.types.unify([&operand, input]);
+                        inference_context
+                            .types
+                            .unify_if_compatible([input, &operand])
+                            .map_err(|err| TypeError {
+                                location: Some(expression.location.clone()),
+                                ..err
+                            })?;
                     }
                     None => {
                         let input = inference_context.types.resolve(input)?;
@@ -418,6 +589,54 @@ fn infer_expression(
     Ok(None)
 }
 
+fn infer_field_access(
+    field: &str,
+    location: &MemberLocation,
+    types: &mut InferredTypes,
+    local_stack: &LocalStack,
+) -> Result<Option<IndirectSignature>> {
+    let expected = || ExpectedType::Record {
+        fields: vec![field.to_string()],
+    };
+
+    let Some(record) = local_stack.inner.last().copied() else {
+        return Err(TypeError {
+            expected: expected(),
+            actual: None,
+            location: Some(location.clone()),
+        });
+    };
+
+    let type_ = match types.resolve(&record)? {
+        InferredType::IndirectRecord { fields } => fields
+            .into_iter()
+            .find_map(|(name, type_)| (name == field).then_some(type_)),
+        InferredType::Direct(Type::Record { fields }) => fields
+            .into_iter()
+            .find_map(|(name, type_)| (name == field).then_some(type_))
+            .map(|type_| types.push(InferredType::Direct(type_))),
+        InferredType::Unknown => {
+            return Ok(None);
+        }
+        InferredType::IndirectFunction { .. } | InferredType::Direct(_) => None,
+    };
+
+    let Some(type_) = type_ else {
+        let actual = types.resolve(&record)?.into_type(types)?;
+
+        return Err(TypeError {
+            expected: expected(),
+            actual,
+            location: Some(location.clone()),
+        });
+    };
+
+    Ok(Some(Signature {
+        inputs: vec![record],
+        outputs: vec![type_],
+    }))
+}
+
 fn infer_intrinsic(
     intrinsic: &IntrinsicFunction,
     location: &MemberLocation,
@@ -441,20 +660,28 @@ fn infer_intrinsic(
                 outputs: vec![],
             })
         }
+        IntrinsicFunction::Eq => {
+            let input = types.push(InferredType::Unknown);
+            let output = types.push(InferredType::Direct(Type::Bool));
+
+            Some(Signature {
+                inputs: vec![input, input],
+                outputs: vec![output],
+            })
+        }
         IntrinsicFunction::Eval => {
-            let top_operand = local_stack
-                .inner
-                .last()
-                .map(|index| types.resolve(index))
-                .transpose()?;
+            let function = local_stack.inner.last().copied();
+            let top_operand =
+                function.map(|index| types.resolve(&index)).transpose()?;
 
             let signature = match top_operand {
-                Some(InferredType::IndirectFunction { signature }) => {
+                Some(InferredType::IndirectFunction { signature, .. }) => {
                     Some(signature)
                 }
-                Some(InferredType::Direct(Type::Function { signature })) => {
-                    Some(IndirectSignature::from_direct(signature, types))
-                }
+                Some(InferredType::Direct(Type::Function {
+                    signature,
+                    ..
+                })) => Some(IndirectSignature::from_direct(signature, types)),
                 Some(InferredType::Direct(actual)) => {
                     return Err(TypeError {
                         expected: ExpectedType::Function,
@@ -462,7 +689,14 @@ fn infer_intrinsic(
                         location: Some(location.clone()),
                     });
                 }
-                Some(InferredType::Unknown { .. }) => None,
+                Some(record @ InferredType::IndirectRecord { .. }) => {
+                    return Err(TypeError {
+                        expected: ExpectedType::Function,
+                        actual: record.into_type(types)?,
+                        location: Some(location.clone()),
+                    });
+                }
+                Some(InferredType::Unknown) => None,
                 None => {
                     return Err(TypeError {
                         expected: ExpectedType::Function,
@@ -472,17 +706,16 @@ fn infer_intrinsic(
                 }
             };
 
-            signature.map(|signature| {
-                let outputs = signature.outputs.clone();
-                let inputs = signature
-                    .inputs
-                    .clone()
-                    .into_iter()
-                    .chain([types
-                        .push(InferredType::IndirectFunction { signature })])
-                    .collect();
-
-                IndirectSignature { inputs, outputs }
+            signature.zip(function).map(|(signature, function)| {
+                // The function itself is the last input. It's already on the
+                // stack, so there's no need to come up with its type again.
+                let inputs =
+                    signature.inputs.into_iter().chain([function]).collect();
+
+                IndirectSignature {
+                    inputs,
+                    outputs: signature.outputs,
+                }
             })
         }
         intrinsic => {
@@ -21,6 +21,28 @@ impl IndirectSignature {
         }
     }
 
+    pub fn from_partial(
+        signature: Signature<Option<Type>>,
+        types: &mut InferredTypes,
+    ) -> Self {
+        let mut map = |from: Vec<Option<Type>>| {
+            from.into_iter()
+                .map(|type_| {
+                    types.push(
+                        type_
+                            .map(InferredType::Direct)
+                            .unwrap_or(InferredType::Unknown),
+                    )
+                })
+                .collect()
+        };
+
+        Signature {
+            inputs: map(signature.inputs),
+            outputs: map(signature.outputs),
+        }
+    }
+
     pub fn to_direct(
         &self,
         types: &mut InferredTypes,
@@ -43,6 +65,25 @@ impl IndirectSignature {
 
         Ok(signature)
     }
+
+    pub fn to_partial(
+        &self,
+        types: &mut InferredTypes,
+    ) -> Result<Signature<Option<Type>>> {
+        let mut try_map = |from: &Vec<Index<InferredType>>| {
+            from.iter()
+                .map(|index| {
+                    let type_ = types.resolve(index)?;
+                    type_.into_type(types)
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+
+        Ok(Signature {
+            inputs: try_map(&self.inputs)?,
+            outputs: try_map(&self.outputs)?,
+        })
+    }
 }
 
 pub fn unify([a, b]: [&IndirectSignature; 2], types: &mut InferredTypes) {
@@ -31,7 +31,72 @@ impl InferredTypes {
             unified_set = unified_set.union(&set).cloned().collect();
         }
 
-        self.equivalence_sets.insert(unified_set);
+        self.equivalence_sets.insert(unified_set.clone());
+        self.unify_fields(&unified_set);
+    }
+
+    /// # Unify two types, unless what's known about them already conflicts
+    ///
+    /// Once conflicting types are unified, every expression that refers to any
+    /// type in their equivalence set has a type error. Checking first allows
+    /// the caller to report the error once, where the conflict comes up.
+    pub fn unify_if_compatible(
+        &mut self,
+        [a, b]: [&Index<InferredType>; 2],
+    ) -> Result<()> {
+        let resolved_a = self.resolve(a)?;
+        let resolved_b = self.resolve(b)?;
+        merge_inferred_types([resolved_a, resolved_b], self)?;
+
+        self.unify([a, b]);
+
+        Ok(())
+    }
+
+    /// # Unify the fields of all records in an equivalence set
+    ///
+    /// Resolving a type merges the records in its equivalence set into a new
+    /// type. But the types of the original fields can be referenced elsewhere
+    /// (by the inputs of the expression that constructed the record, for
+    /// example), and those need to be known too.
+    fn unify_fields(&mut self, set: &BTreeSet<Index<InferredType>>) {
+        let mut records = Vec::new();
+
+        for index in set {
+            match self.get(index).clone() {
+                InferredType::IndirectRecord { fields } => {
+                    records.push(fields);
+                }
+                InferredType::Direct(Type::Record { fields }) => {
+                    let fields = fields
+                        .into_iter()
+                        .map(|(name, type_)| {
+                            (name, self.push(InferredType::Direct(type_)))
+                        })
+                        .collect();
+                    records.push(fields);
+                }
+                _ => {}
+            }
+        }
+
+        let Some((first, rest)) = records.split_first() else {
+            return;
+        };
+
+        for other in rest {
+            let names_match = first.len() == other.len()
+                && first.iter().zip(other).all(|((a, _), (b, _))| a == b);
+            if !names_match {
+                // This is a type error, but it's going to be reported when
+                // resolving the types in this set.
+                continue;
+            }
+
+            for ((_, a), (_, b)) in first.iter().zip(other) {
+                self.unify([a, b]);
+            }
+        }
     }
 
     pub fn resolve(
@@ -74,21 +139,57 @@ fn merge_inferred_types(
 ) -> Result<InferredType> {
     let type_ = match (a, b) {
         (
-            InferredType::IndirectFunction { signature: a },
-            InferredType::IndirectFunction { signature: b },
+            InferredType::IndirectFunction {
+                signature: a,
+                environment: environment_a,
+            },
+            InferredType::IndirectFunction {
+                signature: b,
+                environment: environment_b,
+            },
         ) => {
+            if environment_a.len() != environment_b.len() {
+                let actual = InferredType::IndirectFunction {
+                    signature: b,
+                    environment: environment_b,
+                };
+
+                return Err(TypeError {
+                    expected: ExpectedType::FunctionCapturing {
+                        values: environment_a.len(),
+                    },
+                    actual: actual.into_type(types)?,
+                    location: None,
+                });
+            }
+
+            let environment =
+                merge_type_list([environment_a, environment_b], types)?;
             let signature = merge_signatures([a, b], types)?;
-            InferredType::IndirectFunction { signature }
+            InferredType::IndirectFunction {
+                signature,
+                environment,
+            }
         }
         (
-            InferredType::IndirectFunction { signature: a },
+            InferredType::IndirectFunction {
+                signature: a,
+                environment: environment_a,
+            },
             InferredType::Direct(b),
         )
         | (
             InferredType::Direct(b),
-            InferredType::IndirectFunction { signature: a },
+            InferredType::IndirectFunction {
+                signature: a,
+                environment: environment_a,
+            },
         ) => {
-            let Type::Function { signature: b } = b else {
+            let Type::Function {
+                signature: b,
+                environment: environment_b,
+            } = b
+            else {
                 return Err(TypeError {
                     expected: ExpectedType::Function,
                     actual: Some(b),
@@ -96,10 +197,79 @@ fn merge_inferred_types(
                 });
             };
 
+            if environment_a.len() != environment_b.len() {
+                return Err(TypeError {
+                    expected: ExpectedType::FunctionCapturing {
+                        values: environment_a.len(),
+                    },
+                    actual: Some(Type::Function {
+                        signature: b,
+                        environment: environment_b,
+                    }),
+                    location: None,
+                });
+            }
+
             let b = IndirectSignature::from_direct(b, types);
+            let environment_b = environment_b
+                .into_iter()
+                .map(|type_| types.push(InferredType::Direct(type_)))
+                .collect();
 
+            let environment =
+                merge_type_list([environment_a, environment_b], types)?;
             let signature = merge_signatures([a, b], types)?;
-            InferredType::IndirectFunction { signature }
+            InferredType::IndirectFunction {
+                signature,
+                environment,
+            }
+        }
+        (
+            InferredType::IndirectRecord { fields: a },
+            InferredType::IndirectRecord { fields: b },
+        ) => {
+            let fields = merge_fields([a, b], types)?;
+            InferredType::IndirectRecord { fields }
+        }
+        (
+            InferredType::IndirectRecord { fields: a },
+            InferredType::Direct(b),
+        )
+        | (
+            InferredType::Direct(b),
+            InferredType::IndirectRecord { fields: a },
+        ) => {
+            let Type::Record { fields: b } = b else {
+                return Err(TypeError {
+                    expected: ExpectedType::record(&a),
+                    actual: Some(b),
+                    location: None,
+                });
+            };
+
+            let b = b
+                .into_iter()
+                .map(|(name, type_)| {
+                    (name, types.push(InferredType::Direct(type_)))
+                })
+                .collect();
+
+            let fields = merge_fields([a, b], types)?;
+            InferredType::IndirectRecord { fields }
+        }
+        (
+            InferredType::IndirectFunction { .. },
+            record @ InferredType::IndirectRecord { .. },
+        )
+        | (
+            record @ InferredType::IndirectRecord { .. },
+            InferredType::IndirectFunction { .. },
+        ) => {
+            return Err(TypeError {
+                expected: ExpectedType::Function,
+                actual: record.into_type(types)?,
+                location: None,
+            });
         }
         (InferredType::Direct(a), InferredType::Direct(b)) => {
             merge_direct_types([a, b])?
@@ -135,7 +305,11 @@ fn merge_type_list(
     let mut merged = Vec::new();
 
     for (a, b) in a.into_iter().zip(b) {
-        let [a, b] = [a, b].map(|index| types.get(&index).clone());
+        // The types in the lists might have been unified with others, which
+        // could be what provides the information we need.
+        let a = types.resolve(&a)?;
+        let b = types.resolve(&b)?;
+
         let type_ = merge_inferred_types([a, b], types)?;
         let type_ = types.push(type_);
         merged.push(type_);
@@ -144,12 +318,40 @@ fn merge_type_list(
     Ok(merged)
 }
 
+fn merge_fields(
+    [a, b]: [Vec<(String, Index<InferredType>)>; 2],
+    types: &mut InferredTypes,
+) -> Result<Vec<(String, Index<InferredType>)>> {
+    let names_match =
+        a.len() == b.len() && a.iter().zip(&b).all(|((a, _), (b, _))| a == b);
+    if !names_match {
+        let expected = ExpectedType::record(&a);
+        let actual = InferredType::IndirectRecord { fields: b };
+
+        return Err(TypeError {
+            expected,
+            actual: actual.into_type(types)?,
+            location: None,
+        });
+    }
+
+    let (names, a): (Vec<_>, Vec<_>) = a.into_iter().unzip();
+    let b = b.into_iter().map(|(_, type_)| type_).collect();
+
+    let fields = names
+        .into_iter()
+        .zip(merge_type_list([a, b], types)?)
+        .collect();
+
+    Ok(fields)
+}
+
 fn merge_direct_types([a, b]: [Type; 2]) -> Result<InferredType> {
     if a == b {
         Ok(InferredType::Direct(a))
     } else {
         Err(TypeError {
-            expected: ExpectedType::Specific(a),
+            expected: ExpectedType::specific(a),
             actual: Some(b),
             location: None,
         })
@@ -158,7 +360,13 @@ fn merge_direct_types([a, b]: [Type; 2]) -> Result<InferredType> {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum InferredType {
-    IndirectFunction { signature: IndirectSignature },
+    IndirectFunction {
+        signature: IndirectSignature,
+        environment: Vec<Index<InferredType>>,
+    },
+    IndirectRecord {
+        fields: Vec<(String, Index<InferredType>)>,
+    },
     Direct(Type),
     Unknown,
 }
@@ -166,9 +374,31 @@ pub enum InferredType {
 impl InferredType {
     pub fn into_type(self, types: &mut InferredTypes) -> Result<Option<Type>> {
         let type_ = match self {
-            Self::IndirectFunction { signature } => signature
-                .to_direct(types)?
-                .map(|signature| Type::Function { signature }),
+            Self::IndirectFunction {
+                signature,
+                environment,
+            } => {
+                let signature = signature.to_direct(types)?;
+                let environment = environment
+                    .into_iter()
+                    .map(|index| types.resolve(&index)?.into_type(types))
+                    .collect::<Result<Option<Vec<_>>>>()?;
+
+                signature.zip(environment).map(|(signature, environment)| {
+                    Type::Function {
+                        signature,
+                        environment,
+                    }
+                })
+            }
+            Self::IndirectRecord { fields } => fields
+                .into_iter()
+                .map(|(name, index)| {
+                    let type_ = types.resolve(&index)?.into_type(types)?;
+                    Ok(type_.map(|type_| (name, type_)))
+                })
+                .collect::<Result<Option<_>>>()?
+                .map(|fields| Type::Record { fields }),
             Self::Direct(type_) => Some(type_),
             Self::Unknown { .. } => None,
         };
@@ -181,13 +411,19 @@ impl InferredType {
         types: &mut InferredTypes,
     ) -> Result<ExpectedType> {
         let expected_type = match self {
-            InferredType::IndirectFunction { signature } => signature
-                .to_direct(types)?
-                .map(|signature| {
-                    ExpectedType::Specific(Type::Function { signature })
-                })
+            function @ InferredType::IndirectFunction { .. } => function
+                .into_type(types)?
+                .map(ExpectedType::specific)
                 .unwrap_or(ExpectedType::Function),
-            InferredType::Direct(type_) => ExpectedType::Specific(type_),
+            InferredType::IndirectRecord { fields } => {
+                let expected = ExpectedType::record(&fields);
+
+                InferredType::IndirectRecord { fields }
+                    .into_type(types)?
+                    .map(ExpectedType::specific)
+                    .unwrap_or(expected)
+            }
+            InferredType::Direct(type_) => ExpectedType::specific(type_),
             InferredType::Unknown => ExpectedType::Unknown,
         };
 
@@ -204,17 +440,86 @@ pub struct TypeError {
     pub location: Option<MemberLocation>,
 }
 
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Self {
+            expected, actual, ..
+        } = self;
+
+        match actual {
+            Some(actual) => {
+                write!(f, "Type error: expected {expected}, got `{actual}`.")
+            }
+            None if matches!(
+                expected,
+                ExpectedType::FunctionCapturing { .. }
+            ) =>
+            {
+                write!(
+                    f,
+                    "Type error: expected {expected}, got a function that \
+                    captures a different number of values."
+                )
+            }
+            None => write!(
+                f,
+                "Type error: expected {expected}, but there are not enough \
+                operands on the stack."
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum ExpectedType {
     Function,
-    Specific(Type),
+    FunctionCapturing { values: usize },
+    Record { fields: Vec<String> },
+    Specific(Box<Type>),
     Unknown,
 }
 
+impl ExpectedType {
+    fn specific(type_: Type) -> Self {
+        Self::Specific(Box::new(type_))
+    }
+
+    fn record<T>(fields: &[(String, T)]) -> Self {
+        Self::Record {
+            fields: fields.iter().map(|(name, _)| name.clone()).collect(),
+        }
+    }
+}
+
 impl fmt::Display for ExpectedType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Function => write!(f, "function"),
+            Self::FunctionCapturing { values: 1 } => {
+                write!(f, "function that captures 1 value")
+            }
+            Self::FunctionCapturing { values } => {
+                write!(f, "function that captures {values} values")
+            }
+            Self::Record { fields } => {
+                write!(f, "record")?;
+
+                match fields.as_slice() {
+                    [] => {}
+                    [field] => write!(f, " with field `{field}`")?,
+                    fields => {
+                        write!(f, " with fields ")?;
+                        for (i, field) in fields.iter().enumerate() {
+                            if i > 0 {
+                                write!(f, ", ")?;
+                            }
+                            write!(f, "`{field}`")?;
+                        }
+                    }
+                }
+
+                Ok(())
+            }
             Self::Specific(type_) => write!(f, "`{type_}`"),
             Self::Unknown => write!(f, "unknown type"),
         }
@@ -234,7 +539,7 @@ mod tests {
     fn resolve_known() {
         let mut types = InferredTypes::default();
 
-        let type_ = InferredType::Direct(Type::Number);
+        let type_ = InferredType::Direct(Type::S32);
         let index = types.push(type_.clone());
 
         assert_eq!(types.resolve(&index), Ok(type_));
@@ -254,7 +559,7 @@ mod tests {
     fn resolve_unified() {
         let mut types = InferredTypes::default();
 
-        let type_ = InferredType::Direct(Type::Number);
+        let type_ = InferredType::Direct(Type::S32);
 
         let a = types.push(type_.clone());
         let b = types.push(InferredType::Unknown);
@@ -269,7 +574,7 @@ mod tests {
     fn resolve_unified_with_type_known_only_indirectly() {
         let mut types = InferredTypes::default();
 
-        let type_ = InferredType::Direct(Type::Number);
+        let type_ = InferredType::Direct(Type::S32);
 
         let a = types.push(type_.clone());
         let b = types.push(InferredType::Unknown);
@@ -285,12 +590,13 @@ mod tests {
     fn resolve_conflicting_unified() {
         let mut types = InferredTypes::default();
 
-        let a = Type::Number;
+        let a = Type::S32;
         let b = Type::Function {
             signature: Signature {
                 inputs: vec![],
-                outputs: vec![Type::Number],
+                outputs: vec![Type::S32],
             },
+            environment: vec![],
         };
 
         let index_a = types.push(InferredType::Direct(a.clone()));
@@ -301,7 +607,7 @@ mod tests {
         assert_eq!(
             types.resolve(&index_a),
             Err(TypeError {
-                expected: ExpectedType::Specific(a.clone()),
+                expected: ExpectedType::specific(a.clone()),
                 actual: Some(b.clone()),
                 location: None,
             })
@@ -309,7 +615,7 @@ mod tests {
         assert_eq!(
             types.resolve(&index_b),
             Err(TypeError {
-                expected: ExpectedType::Specific(b),
+                expected: ExpectedType::specific(b),
                 actual: Some(a),
                 location: None,
             })
@@ -2,7 +2,10 @@ mod infer;
 mod repr;
 mod resolve;
 
-pub use self::repr::{Signature, Type, TypeAnnotations, Types};
+pub use self::repr::{
+    InferenceError, InferenceErrorLocation, Signature, Type, TypeAnnotations,
+    Types,
+};
 
 #[cfg(test)]
 mod tests {
@@ -11,12 +14,13 @@ mod tests {
     use crate::{
         code::{
             syntax::{Expression, SyntaxTree},
-            Bindings, Dependencies, FunctionCalls, Identifiers, Tokens, Type,
+            Bindings, Dependencies, FunctionCalls, Identifiers, Signature,
+            Tokens, Type,
         },
         host::NoHost,
     };
 
-    use super::{TypeAnnotations, Types};
+    use super::{InferenceErrorLocation, TypeAnnotations, Types};
 
     #[test]
     fn infer_type_of_binding_from_use() {
@@ -26,9 +30,9 @@ mod tests {
         let (syntax_tree, types) = infer_types(
             r"
                 f: fn
-                    br value: Number ->
-                        value: -> Number .
-                        not: Number -> Number .
+                    br value: S32 ->
+                        value: -> S32 .
+                        neg_s32: S32 -> S32 .
                     end
                 end
             ",
@@ -51,17 +55,18 @@ mod tests {
 
     #[test]
     fn infer_type_of_binding_from_other_branch() {
-        // The type of a binding can be inferred, if its type is specified in
-        // the parameter list of another branch.
+        // The type of a binding can be inferred, if the type of the
+        // corresponding parameter is known in another branch.
 
         let branch_with_known_type = r"
-            br 0 ->
-                0: -> Number .
+            br y: S32 ->
+                y: -> S32 .
+                neg_s32: S32 -> S32 .
             end
         ";
         let branch_with_unknown_type = r"
-            br x: Number ->
-                x: -> Number .
+            br x: S32 ->
+                x: -> S32 .
             end
         ";
 
@@ -98,23 +103,27 @@ mod tests {
     #[test]
     fn infer_type_of_binding_from_use_in_local_function() {
         // If the type of a binding can be inferred in a local function, that
-        // should carry over to the parent.
+        // should carry over to the parent. Since the local function captures
+        // the binding, its type should be part of the local function's type.
+        //
+        // There's no syntax for annotating what a function captures, so this
+        // test checks the inferred types directly.
 
-        let (syntax_tree, types) = infer_types(
+        let (syntax_tree, types) = infer_types_without_annotations(
             r"
                 f: fn
-                    br value: Number ->
+                    br value ->
                         # We should know the type of `value` from its use within
                         # the local function.
-                        value: -> Number .
+                        value
 
                         fn
                             br ->
-                                value: -> Number .
+                                value
                                 # Type of `value` can be inferred from this.
-                                not: Number -> Number .
+                                neg_s32
                             end
-                        end: -> fn -> Number end .
+                        end
                     end
                 end
             ",
@@ -126,24 +135,39 @@ mod tests {
             .into_located_function();
         let f_branch = f.find_single_branch().unwrap();
 
-        let value_expression = f_branch
+        let value = f_branch.bindings().next().unwrap();
+        let local_function = f_branch
             .expressions()
             .map(|expression| expression.location)
-            .next()
+            .nth(1)
             .unwrap();
 
-        assert_eq!(types.stack_at(&value_expression).unwrap(), &[]);
+        assert_eq!(types.type_of_parameter(&value.location), Some(&Type::S32));
+        assert_eq!(
+            types.signature_of_expression(&local_function),
+            Some(&Signature {
+                inputs: vec![],
+                outputs: vec![Type::Function {
+                    signature: Signature {
+                        inputs: vec![],
+                        outputs: vec![Type::S32],
+                    },
+                    environment: vec![Type::S32],
+                }],
+            }),
+        );
     }
 
     #[test]
     fn infer_type_of_literal() {
-        // The type of a literal can be trivially inferred.
+        // The type of a literal can be inferred from the context it's used in.
 
         let (syntax_tree, types) = infer_types(
             r"
                 f: fn
                     br ->
-                        1: -> Number .
+                        1: -> S32 .
+                        neg_s32: S32 -> S32 .
                     end
                 end
             ",
@@ -173,15 +197,15 @@ mod tests {
             r"
                 f: fn
                     br ->
-                        0: -> Number .
-                        g: Number -> Number .
+                        0: -> S32 .
+                        g: S32 -> S32 .
                     end
                 end
 
                 g: fn
-                    br x: Number ->
-                        x: -> Number .
-                        not: Number -> Number .
+                    br x: S32 ->
+                        x: -> S32 .
+                        neg_s32: S32 -> S32 .
                     end
                 end
             ",
@@ -198,7 +222,7 @@ mod tests {
             .nth(1)
             .unwrap();
 
-        assert_eq!(types.stack_at(&g).unwrap(), &[Type::Number]);
+        assert_eq!(types.stack_at(&g).unwrap(), &[Type::S32]);
     }
 
     #[test]
@@ -211,19 +235,21 @@ mod tests {
             r"
                 f: fn
                     br ->
-                        0: -> Number .
-                        0: -> Number .
-                        g: Number, Number -> Number .
+                        0: -> S32 .
+                        0: -> S32 .
+                        g: S32, S32 -> S32 .
                     end
                 end
 
                 g: fn
-                    br 0, x: Number ->
-                        x: -> Number .
+                    br 0, x: S32 ->
+                        x: -> S32 .
+                        neg_s32: S32 -> S32 .
                     end
 
-                    br x: Number, 0 ->
-                        x: -> Number .
+                    br x: S32, 0 ->
+                        x: -> S32 .
+                        neg_s32: S32 -> S32 .
                     end
                 end
             ",
@@ -240,7 +266,7 @@ mod tests {
             .nth(2)
             .unwrap();
 
-        assert_eq!(types.stack_at(&g).unwrap(), &[Type::Number, Type::Number]);
+        assert_eq!(types.stack_at(&g).unwrap(), &[Type::S32, Type::S32]);
     }
 
     #[test]
@@ -251,13 +277,15 @@ mod tests {
 
         let branch_recursive = r"
             br 0 ->
-                1: -> Number .
-                g: Number -> Number .
+                1: -> S32 .
+                neg_s32: S32 -> S32 .
+                g: S32 -> S32 .
             end
         ";
         let branch_non_recursive = r"
-            br x: Number ->
-                x: -> Number .
+            br x: S32 ->
+                x: -> S32 .
+                neg_s32: S32 -> S32 .
             end
         ";
 
@@ -269,8 +297,8 @@ mod tests {
                 r"
                     f: fn
                         br ->
-                            0: -> Number .
-                            g: Number -> Number .
+                            0: -> S32 .
+                            g: S32 -> S32 .
                         end
                     end
 
@@ -292,7 +320,7 @@ mod tests {
                 .nth(1)
                 .unwrap();
 
-            assert_eq!(types.stack_at(&g).unwrap(), &[Type::Number]);
+            assert_eq!(types.stack_at(&g).unwrap(), &[Type::S32]);
         }
     }
 
@@ -306,33 +334,37 @@ mod tests {
             r"
                 f: fn
                     br ->
-                        0: -> Number .
-                        g: Number -> Number .
-                        0: -> Number .
-                        h: Number -> Number .
+                        0: -> S32 .
+                        g: S32 -> S32 .
+                        0: -> S32 .
+                        h: S32 -> S32 .
                     end
                 end
 
                 g: fn
                     br 0 ->
-                        0: -> Number .
-                        h: Number -> Number .
+                        0: -> S32 .
+                        neg_s32: S32 -> S32 .
+                        h: S32 -> S32 .
                     end
-                    
-                    br _: Number ->
-                        1: -> Number .
-                        h: Number -> Number .
+
+                    br x: S32 ->
+                        x: -> S32 .
+                        neg_s32: S32 -> S32 .
+                        h: S32 -> S32 .
                     end
                 end
 
                 h: fn
                     br 0 ->
-                        1: -> Number .
-                        g: Number -> Number .
+                        1: -> S32 .
+                        neg_s32: S32 -> S32 .
+                        g: S32 -> S32 .
                     end
 
-                    br _: Number ->
-                        0: -> Number .
+                    br x: S32 ->
+                        x: -> S32 .
+                        neg_s32: S32 -> S32 .
                     end
                 end
             ",
@@ -363,16 +395,16 @@ mod tests {
             .unwrap()
             .expressions()
             .map(|expression| expression.location)
-            .nth(1)
+            .nth(2)
             .unwrap();
 
         let check = |call, stack: &[Type]| {
             assert_eq!(types.stack_at(call).unwrap(), stack);
         };
 
-        check(&call_to_g_in_f, &[Type::Number]);
-        check(&call_to_h_in_f, &[Type::Number, Type::Number]);
-        check(&call_to_g_in_h, &[Type::Number]);
+        check(&call_to_g_in_f, &[Type::S32]);
+        check(&call_to_h_in_f, &[Type::S32, Type::S32]);
+        check(&call_to_g_in_h, &[Type::S32]);
     }
 
     #[test]
@@ -384,27 +416,59 @@ mod tests {
             r"
                 f: fn
                     br ->
-                        0: -> Number .
+                        0: -> S32 .
+                        neg_s32: S32 -> S32 .
                         fn
                             br 0 ->
-                                0: -> Number .
+                                0: -> S32 .
+                                neg_s32: S32 -> S32 .
                             end
 
                             # Add this branch to make sure that `f` and the
                             # local function are in the same cluster. This more
                             # complicated to handle, resulting in this test
                             # covering a bit more ground.
-                            br _: Number ->
-                                f: -> Number .
+                            br _: S32 ->
+                                f: -> S32 .
                             end
-                        end: -> fn Number -> Number end .
-                        eval: Number, fn Number -> Number end -> Number .
+                        end: -> fn S32 -> S32 end .
+                        eval: S32, fn S32 -> S32 end -> S32 .
                     end
                 end
             ",
         );
 
         let f_local = syntax_tree
+            .function_by_name("f")
+            .unwrap()
+            .into_located_function()
+            .find_single_branch()
+            .unwrap()
+            .expressions()
+            .map(|expression| expression.location)
+            .nth(2)
+            .unwrap();
+
+        assert_eq!(types.stack_at(&f_local).unwrap(), &[Type::S32]);
+    }
+
+    #[test]
+    fn report_call_with_too_few_operands() {
+        // A call that consumes more operands than there are on the stack is an
+        // error that must be reported.
+
+        let (syntax_tree, types) = infer_types_without_annotations(
+            r"
+                f: fn
+                    br ->
+                        1
+                        add_s32
+                    end
+                end
+            ",
+        );
+
+        let add = syntax_tree
             .function_by_name("f")
             .unwrap()
             .into_located_function()
@@ -415,7 +479,158 @@ mod tests {
             .nth(1)
             .unwrap();
 
-        assert_eq!(types.stack_at(&f_local).unwrap(), &[Type::Number]);
+        assert!(types.has_error_at_expression(&add));
+
+        let [error] = types.errors().collect::<Vec<_>>()[..] else {
+            panic!("Expected exactly one error.");
+        };
+        assert_eq!(error.location, InferenceErrorLocation::Expression(add));
+    }
+
+    #[test]
+    fn report_arity_mismatch_between_branches() {
+        // All branches of a function must have the same number of inputs and
+        // outputs.
+
+        let (syntax_tree, types) = infer_types_without_annotations(
+            r"
+                f: fn
+                    br 0 ->
+                        1
+                    end
+
+                    br _ ->
+                        1
+                        1
+                    end
+                end
+            ",
+        );
+
+        let (_, second_branch) = syntax_tree
+            .function_by_name("f")
+            .unwrap()
+            .into_located_function()
+            .branches()
+            .map(|branch| branch.location)
+            .collect_tuple()
+            .unwrap();
+
+        let [error] = types.errors().collect::<Vec<_>>()[..] else {
+            panic!("Expected exactly one error.");
+        };
+        assert_eq!(
+            error.location,
+            InferenceErrorLocation::Branch(second_branch),
+        );
+    }
+
+    #[test]
+    fn report_literal_that_does_not_fit_inferred_type() {
+        // The type of a literal is inferred from the context it's used in. If
+        // its value doesn't fit that type, that's an error.
+
+        let (syntax_tree, types) = infer_types_without_annotations(
+            r"
+                f: fn
+                    br ->
+                        256
+                        1
+                        add_u8
+                    end
+                end
+            ",
+        );
+
+        let literal = syntax_tree
+            .function_by_name("f")
+            .unwrap()
+            .into_located_function()
+            .find_single_branch()
+            .unwrap()
+            .expressions()
+            .map(|expression| expression.location)
+            .next()
+            .unwrap();
+
+        let [error] = types.errors().collect::<Vec<_>>()[..] else {
+            panic!("Expected exactly one error.");
+        };
+        assert_eq!(error.location, InferenceErrorLocation::Expression(literal));
+    }
+
+    #[test]
+    fn infer_type_of_record_and_field_access() {
+        // The type of a record is made up of the types of its fields. Field
+        // access leaves the type of the field.
+
+        infer_types(
+            r"
+                f: fn
+                    br x: U8, y: S32 ->
+                        x: -> U8 .
+                        1: -> U8 .
+                        add_u8: U8, U8 -> U8 .
+                        y: -> S32 .
+                        neg_s32: S32 -> S32 .
+                        { a, b }: U8, S32 -> { a: U8, b: S32 } .
+                        .b: { a: U8, b: S32 } -> S32 .
+                    end
+                end
+            ",
+        );
+    }
+
+    #[test]
+    fn report_field_access_on_record_of_unknown_type() {
+        // Field access can't be compiled without knowing the type of the
+        // record. If it can't be inferred, that's an error.
+
+        let (syntax_tree, types) = infer_types_without_annotations(
+            r"
+                f: fn
+                    br record ->
+                        record
+                        .x
+                    end
+                end
+            ",
+        );
+
+        let field_access = syntax_tree
+            .function_by_name("f")
+            .unwrap()
+            .into_located_function()
+            .find_single_branch()
+            .unwrap()
+            .expressions()
+            .map(|expression| expression.location)
+            .nth(1)
+            .unwrap();
+
+        let [error] = types.errors().collect::<Vec<_>>()[..] else {
+            panic!("Expected exactly one error.");
+        };
+        assert_eq!(
+            error.location,
+            InferenceErrorLocation::Expression(field_access),
+        );
+    }
+
+    #[test]
+    fn report_access_of_field_that_does_not_exist() {
+        let (_, types) = infer_types_without_annotations(
+            r"
+                f: fn
+                    br record: { x: S32 } ->
+                        record
+                        .y
+                    end
+                end
+            ",
+        );
+
+        assert_eq!(types.errors().count(), 1);
     }
 
     fn infer_types(input: &str) -> (SyntaxTree, Types) {
@@ -455,7 +670,8 @@ mod tests {
         let function_calls = FunctionCalls::resolve(&syntax_tree, &NoHost);
         let identifiers =
             Identifiers::resolve(&syntax_tree, &bindings, &function_calls);
-        let dependencies = Dependencies::resolve(&syntax_tree, &function_calls);
+        let dependencies =
+            Dependencies::resolve(&syntax_tree, &bindings, &function_calls);
 
         // Don't use any of the type annotations for the inference. They'll be
         // used to _check_ the inference later.
@@ -479,4 +695,26 @@ mod tests {
 
         (syntax_tree, types)
     }
+
+    fn infer_types_without_annotations(input: &str) -> (SyntaxTree, Types) {
+        let tokens = Tokens::tokenize(input);
+        let syntax_tree = SyntaxTree::parse(tokens);
+
+        let bindings = Bindings::resolve(&syntax_tree);
+        let function_calls = FunctionCalls::resolve(&syntax_tree, &NoHost);
+        let identifiers =
+            Identifiers::resolve(&syntax_tree, &bindings, &function_calls);
+        let dependencies =
+            Dependencies::resolve(&syntax_tree, &bindings, &function_calls);
+
+        let types = Types::infer(
+            &syntax_tree,
+            &bindings,
+            &identifiers,
+            &dependencies,
+            TypeAnnotations::none(),
+        );
+
+        (syntax_tree, types)
+    }
 }
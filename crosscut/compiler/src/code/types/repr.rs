@@ -1,7 +1,12 @@
 use std::{collections::BTreeMap, fmt};
 
+use crosscut_runtime::Value;
+
 use crate::code::{
-    syntax::{FunctionLocation, MemberLocation, ParameterLocation, SyntaxTree},
+    syntax::{
+        BranchLocation, FunctionLocation, MemberLocation, ParameterLocation,
+        SyntaxTree,
+    },
     Bindings, Dependencies, Identifiers,
 };
 
@@ -22,6 +27,7 @@ use super::{
 pub struct TypeAnnotations {
     bindings: BTreeMap<ParameterLocation, Type>,
     expressions: BTreeMap<MemberLocation, Signature>,
+    errors: Vec<InferenceError>,
 }
 
 impl TypeAnnotations {
@@ -30,16 +36,22 @@ impl TypeAnnotations {
         Self {
             bindings: BTreeMap::default(),
             expressions: BTreeMap::default(),
+            errors: Vec::new(),
         }
     }
 
     /// # Resolve all explicit type annotations
+    ///
+    /// Annotations that can't be resolved are ignored. The errors that result
+    /// from them become part of the [`Types`] that are inferred later.
     pub fn resolve(syntax_tree: &SyntaxTree) -> Self {
-        let (bindings, expressions) = resolve_type_annotations(syntax_tree);
+        let (bindings, expressions, errors) =
+            resolve_type_annotations(syntax_tree);
 
         Self {
             bindings,
             expressions,
+            errors,
         }
     }
 
@@ -78,6 +90,7 @@ pub struct Types {
     parameters: BTreeMap<ParameterLocation, Type>,
     expressions: BTreeMap<MemberLocation, Signature>,
     stacks: Stacks,
+    errors: Vec<InferenceError>,
 }
 
 impl Types {
@@ -97,9 +110,11 @@ impl Types {
     ) -> Self {
         let InferenceOutput {
             functions,
+            partial_functions: _,
             expressions,
             parameters,
             stacks,
+            errors,
         } = infer(CompilerContext {
             syntax_tree,
             bindings,
@@ -107,12 +122,14 @@ impl Types {
             dependencies,
             annotations: &annotations,
         });
+        let errors = annotations.errors.into_iter().chain(errors).collect();
 
         Self {
             functions,
             parameters,
             expressions,
             stacks,
+            errors,
         }
     }
 
@@ -152,6 +169,63 @@ impl Types {
     pub fn stack_at(&self, location: &MemberLocation) -> Option<&[Type]> {
         self.stacks.get(location).map(|stack| &**stack)
     }
+
+    /// # Iterate over the errors that were found during inference
+    pub fn errors(&self) -> impl Iterator<Item = &InferenceError> {
+        self.errors.iter()
+    }
+
+    /// # Determine whether inference failed at the given expression
+    pub fn has_error_at_expression(&self, location: &MemberLocation) -> bool {
+        self.errors.iter().any(|error| {
+            error.location
+                == InferenceErrorLocation::Expression(location.clone())
+        })
+    }
+
+    /// # Determine whether inference failed at the given branch
+    ///
+    /// This covers errors that apply to the branch as a whole, or to any of its
+    /// parameters. It does not cover those that apply to any of its
+    /// expressions.
+    pub fn has_error_at_branch(&self, location: &BranchLocation) -> bool {
+        self.errors.iter().any(|error| match &error.location {
+            InferenceErrorLocation::Branch(branch) => branch == location,
+            InferenceErrorLocation::Parameter(parameter) => {
+                *parameter.parent == *location
+            }
+            InferenceErrorLocation::Expression(_) => false,
+        })
+    }
+}
+
+/// # An error that was found during type inference
+///
+/// Type inference does not stop at the first error. It records the error and
+/// continues with whatever information it still has.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct InferenceError {
+    /// # A message describing the error
+    pub message: String,
+
+    /// # The place in the code where the error was found
+    pub location: InferenceErrorLocation,
+}
+
+/// # The place in the code where an [`InferenceError`] was found
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum InferenceErrorLocation {
+    /// # The error applies to a whole branch
+    ///
+    /// This is the case, if the branch doesn't match the other branches of its
+    /// function.
+    Branch(BranchLocation),
+
+    /// # The error applies to a single parameter of a branch
+    Parameter(ParameterLocation),
+
+    /// # The error applies to a single expression
+    Expression(MemberLocation),
 }
 
 pub type Stacks = BTreeMap<MemberLocation, Stack>;
@@ -237,32 +311,152 @@ impl fmt::Display for Signature {
     udigest::Digestable,
 )]
 pub enum Type {
+    /// # A boolean value
+    ///
+    /// Represented as `0` (false) or `1` (true) at runtime.
+    Bool,
+
     /// # A function
+    ///
+    /// A function value is laid out on the stack as the values it captures from
+    /// its environment, followed by the address of its code on top. As a
+    /// result, two functions with the same signature have the same type only if
+    /// they capture values of the same types.
     Function {
         /// # The function's signature
         signature: Signature,
+
+        /// # The types of the values that the function captures, in order
+        ///
+        /// This is empty for any function that is not a closure.
+        environment: Vec<Type>,
     },
 
-    /// # A number
+    /// # A record
     ///
-    /// ## Implementation Note
+    /// Records are laid out on the stack as the values of their fields, one
+    /// after the other. The first field is the one furthest from the top.
+    Record {
+        /// # The names and types of the record's fields, in order
+        fields: Vec<(String, Type)>,
+    },
+
+    /// # A signed 8-bit integer
+    S8,
+
+    /// # A signed 32-bit integer
+    S32,
+
+    /// # An unsigned 8-bit integer
+    U8,
+}
+
+impl Type {
+    /// # Convert a literal into the value that represents it at runtime
+    ///
+    /// Literals are parsed as signed 32-bit integers. Other types have a
+    /// different representation at runtime, and might not be able to represent
+    /// the literal at all. In that case, this method returns `None`.
+    pub fn literal_value(&self, literal: Value) -> Option<Value> {
+        let literal = literal.to_i32();
+
+        match self {
+            Self::Bool => match literal {
+                0 | 1 => Some(Value::from(literal)),
+                _ => None,
+            },
+            Self::Function { .. } | Self::Record { .. } => None,
+            Self::S8 => i8::try_from(literal).ok().map(Value::from),
+            Self::S32 => Some(Value::from(literal)),
+            Self::U8 => u8::try_from(literal).ok().map(Value::from),
+        }
+    }
+
+    /// # The number of operands that a value of this type occupies
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Function { environment, .. } => {
+                environment.iter().map(|type_| type_.size()).sum::<usize>() + 1
+            }
+            Self::Record { fields } => {
+                fields.iter().map(|(_, type_)| type_.size()).sum()
+            }
+            Self::Bool | Self::S8 | Self::S32 | Self::U8 => 1,
+        }
+    }
+
+    /// # Find a field of a record
     ///
-    /// Since the language is still mostly untyped, this actually covers many
-    /// different types that are all represented as a 32-bit number.
+    /// Returns the field's type, along with its offset. That's the number of
+    /// operands occupied by the fields that come before it.
     ///
-    /// I expect that this will get split into multiple, more specific numeric
-    /// types at some point.
-    Number,
+    /// Returns `None`, if this is not a record, or it has no field of that
+    /// name.
+    pub fn field(&self, name: &str) -> Option<(usize, &Type)> {
+        let Self::Record { fields } = self else {
+            return None;
+        };
+
+        let mut offset = 0;
+
+        for (field, type_) in fields {
+            if field == name {
+                return Some((offset, type_));
+            }
+
+            offset += type_.size();
+        }
+
+        None
+    }
 }
 
 impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::Function { signature } => {
-                write!(f, "fn {signature} end")?;
+            Self::Bool => {
+                write!(f, "Bool")?;
+            }
+            Self::Function {
+                signature,
+                environment,
+            } => {
+                write!(f, "fn {signature}")?;
+
+                if !environment.is_empty() {
+                    write!(f, " captures ")?;
+                    for (i, type_) in environment.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{type_}")?;
+                    }
+                }
+
+                write!(f, " end")?;
+            }
+            Self::Record { fields } => {
+                if fields.is_empty() {
+                    write!(f, "{{}}")?;
+                } else {
+                    write!(f, "{{ ")?;
+                    for (i, (name, type_)) in fields.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{name}: {type_}")?;
+                    }
+                    write!(f, " }}")?;
+                }
+            }
+            Self::S8 => {
+                write!(f, "S8")?;
+            }
+            Self::S32 => {
+                write!(f, "S32")?;
             }
-            Self::Number => {
-                write!(f, "Number")?;
+            Self::U8 => {
+                write!(f, "U8")?;
             }
         }
 
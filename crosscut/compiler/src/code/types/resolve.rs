@@ -4,16 +4,22 @@ use crate::code::syntax::{
     MemberLocation, ParameterLocation, SyntaxTree, SyntaxType,
 };
 
-use super::{Signature, Type};
+use super::{InferenceError, InferenceErrorLocation, Signature, Type};
 
+/// # Resolve the explicit type annotations in the provided syntax tree
+///
+/// Annotations that refer to unknown types are left out of the result. An error
+/// is returned for each of them instead.
 pub fn resolve_type_annotations(
     syntax_tree: &SyntaxTree,
 ) -> (
     BTreeMap<ParameterLocation, Type>,
     BTreeMap<MemberLocation, Signature>,
+    Vec<InferenceError>,
 ) {
     let mut bindings = BTreeMap::new();
     let mut expressions = BTreeMap::new();
+    let mut errors = Vec::new();
 
     for function in syntax_tree.all_functions() {
         for branch in function.branches() {
@@ -22,9 +28,19 @@ pub fn resolve_type_annotations(
                     continue;
                 };
 
-                let type_ = resolve_type(type_);
-
-                bindings.insert(binding.location, type_);
+                match resolve_type(type_) {
+                    Ok(type_) => {
+                        bindings.insert(binding.location, type_);
+                    }
+                    Err(UnknownType { name }) => {
+                        errors.push(InferenceError {
+                            message: format!("Unknown type `{name}`"),
+                            location: InferenceErrorLocation::Parameter(
+                                binding.location,
+                            ),
+                        });
+                    }
+                }
             }
 
             for (expression, signature) in branch.annotated_expressions() {
@@ -32,38 +48,74 @@ pub fn resolve_type_annotations(
                     continue;
                 };
 
-                let signature = resolve_signature(signature);
-
-                expressions.insert(expression.location, signature);
+                match resolve_signature(signature) {
+                    Ok(signature) => {
+                        expressions.insert(expression.location, signature);
+                    }
+                    Err(UnknownType { name }) => {
+                        errors.push(InferenceError {
+                            message: format!("Unknown type `{name}`"),
+                            location: InferenceErrorLocation::Expression(
+                                expression.location,
+                            ),
+                        });
+                    }
+                }
             }
         }
     }
 
-    (bindings, expressions)
+    (bindings, expressions, errors)
 }
 
-fn resolve_signature(signature: &Signature<SyntaxType>) -> Signature {
-    Signature {
-        inputs: resolve_types(&signature.inputs),
-        outputs: resolve_types(&signature.outputs),
-    }
+fn resolve_signature(
+    signature: &Signature<SyntaxType>,
+) -> Result<Signature, UnknownType> {
+    Ok(Signature {
+        inputs: resolve_types(&signature.inputs)?,
+        outputs: resolve_types(&signature.outputs)?,
+    })
 }
 
-fn resolve_types(types: &[SyntaxType]) -> Vec<Type> {
+fn resolve_types(types: &[SyntaxType]) -> Result<Vec<Type>, UnknownType> {
     types.iter().map(resolve_type).collect()
 }
 
-fn resolve_type(type_: &SyntaxType) -> Type {
-    match type_ {
-        SyntaxType::Function { signature } => {
-            let signature = resolve_signature(signature);
-            Type::Function { signature }
+fn resolve_type(type_: &SyntaxType) -> Result<Type, UnknownType> {
+    let type_ = match type_ {
+        SyntaxType::Function {
+            signature,
+            environment,
+        } => {
+            let signature = resolve_signature(signature)?;
+            let environment = resolve_types(environment)?;
+
+            Type::Function {
+                signature,
+                environment,
+            }
         }
         SyntaxType::Identifier { name } => match name.as_str() {
-            "Number" => Type::Number,
-            type_ => {
-                panic!("Unknown type `{type_}`");
+            "Bool" => Type::Bool,
+            "S8" => Type::S8,
+            "S32" => Type::S32,
+            "U8" => Type::U8,
+            _ => {
+                return Err(UnknownType { name: name.clone() });
             }
         },
-    }
+        SyntaxType::Record { fields } => {
+            let fields = fields
+                .iter()
+                .map(|(name, type_)| Ok((name.clone(), resolve_type(type_)?)))
+                .collect::<Result<_, _>>()?;
+            Type::Record { fields }
+        }
+    };
+
+    Ok(type_)
+}
+
+struct UnknownType {
+    name: String,
 }
@@ -5,9 +5,11 @@ use crosscut_runtime::InstructionAddress;
 use crate::{
     code::{
         syntax::{FunctionLocation, SyntaxTree},
-        Bindings, Dependencies, FunctionCalls, Functions, Identifiers,
-        Recursion, TailExpressions, Tokens, TypeAnnotations, Types,
+        Bindings, Contexts, Dependencies, FunctionCalls, Functions,
+        Identifiers, Recursion, TailExpressions, Tokens, TypeAnnotations,
+        Types,
     },
+    diagnostics::Diagnostic,
     host::Host,
     passes::{detect_changes, generate_instructions},
     source_map::SourceMap,
@@ -18,25 +20,49 @@ use crate::{
 #[derive(Default)]
 pub struct Compiler {
     old_code: Option<SyntaxTree>,
+    old_contexts: Contexts,
     instructions: Instructions,
     call_instructions_by_callee: CallInstructionsByCallee,
     compiled_functions_by_location:
-        BTreeMap<FunctionLocation, crosscut_runtime::Function>,
+        BTreeMap<FunctionLocation, InstructionAddress>,
     source_map: SourceMap,
 }
 
 impl Compiler {
     /// # Compile the provided source code
+    ///
+    /// This is a shortcut for code that consists of only a single module. See
+    /// [`Compiler::compile_modules`].
     pub fn compile(&mut self, input: &str, host: &impl Host) -> CompilerOutput {
-        let tokens = Tokens::tokenize(input);
-        let syntax_tree = SyntaxTree::parse(tokens);
+        self.compile_modules([(SyntaxTree::MAIN_MODULE, input)], host)
+    }
+
+    /// # Compile the provided modules
+    ///
+    /// Expects the name and source code of each module. Functions can call
+    /// functions in other modules by qualifying their names with the name of
+    /// the module (like `vec/add`), if their own module imports that module
+    /// (with `import vec` at the top). The `main` function is expected in the
+    /// module named `main`.
+    pub fn compile_modules<'r>(
+        &mut self,
+        modules: impl IntoIterator<Item = (&'r str, &'r str)>,
+        host: &impl Host,
+    ) -> CompilerOutput {
+        let mut syntax_tree = SyntaxTree::default();
+        for (module, input) in modules {
+            let tokens = Tokens::tokenize(input);
+            syntax_tree.parse_module(module, tokens);
+        }
+
         let type_annotations = TypeAnnotations::resolve(&syntax_tree);
         let bindings = Bindings::resolve(&syntax_tree);
         let function_calls = FunctionCalls::resolve(&syntax_tree, host);
         let identifiers =
             Identifiers::resolve(&syntax_tree, &bindings, &function_calls);
         let tail_expressions = TailExpressions::find(&syntax_tree);
-        let dependencies = Dependencies::resolve(&syntax_tree, &function_calls);
+        let dependencies =
+            Dependencies::resolve(&syntax_tree, &bindings, &function_calls);
         let recursion =
             Recursion::find(&syntax_tree, &function_calls, &dependencies);
         let types = Types::infer(
@@ -52,9 +78,21 @@ impl Compiler {
                 .map(|function| (function.location, function.fragment.clone()))
                 .collect(),
         };
-        let changes = detect_changes(self.old_code.take(), &syntax_tree);
+        let mut diagnostics = Diagnostic::from_syntax_tree(&syntax_tree);
+        diagnostics.extend(Diagnostic::from_imports(&syntax_tree));
+        diagnostics
+            .extend(Diagnostic::from_identifiers(&identifiers, &syntax_tree));
+        diagnostics.extend(Diagnostic::from_types(&types, &syntax_tree));
+        let contexts = Contexts::new(&syntax_tree, &identifiers, &types);
+        let changes = detect_changes(
+            self.old_code.take(),
+            &self.old_contexts,
+            &syntax_tree,
+            &contexts,
+        );
 
         self.old_code = Some(syntax_tree.clone());
+        self.old_contexts = contexts;
 
         generate_instructions(
             &syntax_tree,
@@ -71,6 +109,7 @@ impl Compiler {
             &mut self.compiled_functions_by_location,
             &mut self.source_map,
         );
+        self.source_map.update_spans(&syntax_tree.spans);
 
         CompilerOutput {
             syntax_tree,
@@ -80,6 +119,7 @@ impl Compiler {
             types,
             instructions: self.instructions.clone(),
             source_map: self.source_map.clone(),
+            diagnostics,
         }
     }
 }
@@ -98,4 +138,5 @@ pub struct CompilerOutput {
     pub types: Types,
     pub instructions: Instructions,
     pub source_map: SourceMap,
+    pub diagnostics: Vec<Diagnostic>,
 }
@@ -0,0 +1,243 @@
+use crate::code::{
+    syntax::{FunctionLocation, SyntaxTree},
+    Identifiers, InferenceErrorLocation, Span, Types,
+};
+
+/// # A problem in the code that the compiler has found
+///
+/// The compiler does not stop at the first problem it finds. Code that has
+/// diagnostics still compiles, but the affected parts trigger a build error, if
+/// they are executed.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Diagnostic {
+    /// # A message describing the problem
+    pub message: String,
+
+    /// # The module that the problem was found in
+    pub module: String,
+
+    /// # The span of source code that the problem relates to
+    pub span: Span,
+}
+
+impl Diagnostic {
+    /// # Collect the diagnostics from the provided syntax tree
+    pub fn from_syntax_tree(syntax_tree: &SyntaxTree) -> Vec<Self> {
+        syntax_tree
+            .errors
+            .iter()
+            .map(|error| Self {
+                message: error.message.clone(),
+                module: error.module.clone(),
+                span: error.span,
+            })
+            .collect()
+    }
+
+    /// # Collect the diagnostics from imports of modules that don't exist
+    ///
+    /// Unlike [`Diagnostic::from_syntax_tree`], this depends on all modules
+    /// being part of the syntax tree.
+    pub fn from_imports(syntax_tree: &SyntaxTree) -> Vec<Self> {
+        syntax_tree
+            .modules
+            .iter()
+            .flat_map(|(name, module)| {
+                module.imports.iter().enumerate().filter_map(
+                    move |(index, import)| {
+                        if syntax_tree.modules.contains_key(&import.module) {
+                            return None;
+                        }
+
+                        let span = syntax_tree
+                            .spans
+                            .imports
+                            .get(&(name.clone(), index))?;
+
+                        Some(Self {
+                            message: format!(
+                                "Unknown module `{}`",
+                                import.module
+                            ),
+                            module: name.clone(),
+                            span: *span,
+                        })
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// # Collect the diagnostics from identifiers that don't refer to anything
+    pub fn from_identifiers(
+        identifiers: &Identifiers,
+        syntax_tree: &SyntaxTree,
+    ) -> Vec<Self> {
+        identifiers
+            .unresolved()
+            .filter_map(|(location, name)| {
+                let span = syntax_tree.spans.members.get(location)?;
+                let module = module_of(&location.parent.parent, syntax_tree)?;
+
+                Some(Self {
+                    message: format!("Unresolved identifier `{name}`"),
+                    module,
+                    span: *span,
+                })
+            })
+            .collect()
+    }
+
+    /// # Collect the diagnostics from the errors found during type inference
+    pub fn from_types(types: &Types, syntax_tree: &SyntaxTree) -> Vec<Self> {
+        types
+            .errors()
+            .filter_map(|error| {
+                let (function, span) = match &error.location {
+                    InferenceErrorLocation::Branch(location) => (
+                        &location.parent,
+                        syntax_tree.spans.branches.get(location)?,
+                    ),
+                    InferenceErrorLocation::Parameter(location) => (
+                        &location.parent.parent,
+                        syntax_tree.spans.parameters.get(location)?,
+                    ),
+                    InferenceErrorLocation::Expression(location) => (
+                        &location.parent.parent,
+                        syntax_tree.spans.members.get(location)?,
+                    ),
+                };
+                let module = module_of(function, syntax_tree)?;
+
+                Some(Self {
+                    message: error.message.clone(),
+                    module,
+                    span: *span,
+                })
+            })
+            .collect()
+    }
+}
+
+fn module_of(
+    function: &FunctionLocation,
+    syntax_tree: &SyntaxTree,
+) -> Option<String> {
+    let named_function =
+        syntax_tree.find_top_level_parent_function(function)?;
+    Some(named_function.fragment.module.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{host::NoHost, Compiler};
+
+    #[test]
+    fn report_unresolved_identifier() {
+        let output = Compiler::default().compile(
+            r"
+                main: fn
+                    br ->
+                        does_not_exist
+                    end
+                end
+            ",
+            &NoHost,
+        );
+
+        let [diagnostic] = output.diagnostics.as_slice() else {
+            panic!(
+                "Expected exactly one diagnostic: {:#?}",
+                output.diagnostics
+            );
+        };
+        assert_eq!(
+            diagnostic.message,
+            "Unresolved identifier `does_not_exist`"
+        );
+        assert_eq!(diagnostic.span.to_string(), "4:25");
+    }
+
+    #[test]
+    fn report_unknown_type_in_annotation() {
+        // Annotations are compiled on every keystroke in the editor. A type
+        // that has only been typed halfway must not crash the compiler.
+
+        let output = Compiler::default().compile(
+            r"
+                main: fn
+                    br x: S3 ->
+                        1: -> S3.
+                    end
+                end
+            ",
+            &NoHost,
+        );
+
+        let messages = output
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                (diagnostic.message.as_str(), diagnostic.span.to_string())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                ("Unknown type `S3`", "3:24".to_string()),
+                ("Unknown type `S3`", "4:25".to_string()),
+            ],
+        );
+    }
+
+    #[test]
+    fn report_annotation_of_type_that_can_be_inferred() {
+        let output = Compiler::default().compile(
+            r"
+                main: fn
+                    br ->
+                        1
+                        neg_s32: S32 -> S32.
+                    end
+                end
+            ",
+            &NoHost,
+        );
+
+        let [diagnostic] = output.diagnostics.as_slice() else {
+            panic!(
+                "Expected exactly one diagnostic: {:#?}",
+                output.diagnostics
+            );
+        };
+        assert!(diagnostic.message.contains("can be inferred"));
+        assert_eq!(diagnostic.span.to_string(), "5:25");
+    }
+
+    #[test]
+    fn report_import_of_unknown_module() {
+        let output = Compiler::default().compile_modules(
+            [
+                (
+                    "main",
+                    r"
+                        import vec
+                        import other
+                    ",
+                ),
+                ("other", ""),
+            ],
+            &NoHost,
+        );
+
+        let [diagnostic] = output.diagnostics.as_slice() else {
+            panic!(
+                "Expected exactly one diagnostic: {:#?}",
+                output.diagnostics
+            );
+        };
+        assert_eq!(diagnostic.message, "Unknown module `vec`");
+        assert_eq!(diagnostic.module, "main");
+        assert_eq!(diagnostic.span.to_string(), "2:25");
+    }
+}
@@ -0,0 +1,526 @@
+//! # Formatting of source code
+//!
+//! The syntax is not sensitive to indentation or line breaks, so the same code
+//! can be written in many ways. The formatter turns code into its canonical
+//! form.
+
+use std::fmt::Write;
+
+use crate::{
+    code::{
+        syntax::{
+            Branch, Comment, Expression, Function, Member, NamedFunction,
+            Parameter, SyntaxTree, SyntaxType,
+        },
+        Signature, Span, Token, Tokens,
+    },
+    diagnostics::Diagnostic,
+};
+
+/// # Format the provided source code
+///
+/// Code that has syntax errors is not formatted, as the parser might have
+/// skipped parts of it. Those would then be missing from the formatted code.
+///
+/// ## Implementation Note
+///
+/// The formatter can only emit what made it into the syntax tree. To make sure
+/// no code gets lost, should the parser ever miss something, the formatted
+/// code is checked against the original, and an error is returned, if they
+/// don't match.
+pub fn format(input: &str) -> Result<String, FormatError> {
+    let syntax_tree = SyntaxTree::parse(Tokens::tokenize(input));
+
+    let diagnostics = Diagnostic::from_syntax_tree(&syntax_tree);
+    if !diagnostics.is_empty() {
+        return Err(FormatError::Diagnostics { diagnostics });
+    }
+
+    let output = format_module(&syntax_tree, SyntaxTree::MAIN_MODULE);
+    check_tokens(input, &output)?;
+
+    Ok(output)
+}
+
+/// # Format a module from the provided syntax tree
+///
+/// Unlike [`format`], this can't check whether any code is missing from the
+/// syntax tree. It's up to the caller to make sure that's not the case.
+pub fn format_module(syntax_tree: &SyntaxTree, module: &str) -> String {
+    let mut output = String::new();
+
+    let (imports, trailing_comment) = syntax_tree
+        .modules
+        .get(module)
+        .map(|module| {
+            (module.imports.as_slice(), module.trailing_comment.as_ref())
+        })
+        .unwrap_or_default();
+    for import in imports {
+        format_comment(import.comment.as_ref(), 0, &mut output);
+        writeln!(output, "import {}", import.module).expect(INFALLIBLE);
+    }
+
+    let named_functions = syntax_tree
+        .named_functions()
+        .filter(|function| function.module == module);
+    for named_function in named_functions {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+
+        format_named_function(named_function.fragment, &mut output);
+    }
+
+    if trailing_comment.is_some() && !output.is_empty() {
+        output.push('\n');
+    }
+    format_comment(trailing_comment, 0, &mut output);
+
+    output
+}
+
+/// # An error that prevented code from being formatted
+#[derive(Debug, thiserror::Error)]
+pub enum FormatError {
+    /// # The code has errors
+    #[error("Can't format code that has errors")]
+    Diagnostics {
+        /// # The errors in the code
+        diagnostics: Vec<Diagnostic>,
+    },
+
+    /// # The formatted code doesn't match the original code
+    #[error("Formatting would change the code at {span}")]
+    NotPreserved {
+        /// # Where in the original code the change was detected
+        span: Span,
+    },
+}
+
+const INDENTATION: &str = "    ";
+
+fn format_named_function(named_function: &NamedFunction, output: &mut String) {
+    format_comment(named_function.comment.as_ref(), 0, output);
+    write!(output, "{}: ", named_function.name).expect(INFALLIBLE);
+    format_function(&named_function.inner, 0, output);
+    output.push('\n');
+}
+
+fn format_function(function: &Function, level: usize, output: &mut String) {
+    output.push_str("fn\n");
+
+    for (i, branch) in function.branches.values().enumerate() {
+        if i > 0 {
+            output.push('\n');
+        }
+
+        format_branch(branch, level + 1, output);
+    }
+
+    format_comment(function.trailing_comment.as_ref(), level + 1, output);
+
+    indent(level, output);
+    output.push_str("end");
+}
+
+fn format_branch(branch: &Branch, level: usize, output: &mut String) {
+    format_comment(branch.comment.as_ref(), level, output);
+
+    indent(level, output);
+    output.push_str("br ");
+    for (i, parameter) in branch.parameters.values().enumerate() {
+        if i > 0 {
+            output.push_str(", ");
+        }
+
+        match parameter {
+            Parameter::Binding { binding, type_ } => {
+                output.push_str(&binding.name);
+
+                if let Some(type_) = type_ {
+                    output.push_str(": ");
+                    format_type(type_, output);
+                }
+            }
+            Parameter::Literal { value } => {
+                write!(output, "{}", value.to_i32()).expect(INFALLIBLE);
+            }
+        }
+    }
+    if !branch.parameters.is_empty() {
+        output.push(' ');
+    }
+    output.push_str("->\n");
+
+    for member in branch.body.values() {
+        match member {
+            Member::Comment(comment) => {
+                format_comment(Some(comment), level + 1, output);
+            }
+            Member::Expression {
+                expression,
+                signature,
+            } => {
+                indent(level + 1, output);
+                format_expression(expression, level + 1, output);
+
+                if let Some(signature) = signature {
+                    output.push_str(": ");
+                    format_signature(signature, output);
+                    output.push('.');
+                }
+
+                output.push('\n');
+            }
+        }
+    }
+
+    indent(level, output);
+    output.push_str("end\n");
+}
+
+fn format_expression(
+    expression: &Expression,
+    level: usize,
+    output: &mut String,
+) {
+    match expression {
+        Expression::FieldAccess { field } => {
+            write!(output, ".{field}").expect(INFALLIBLE);
+        }
+        Expression::Identifier { name } => {
+            output.push_str(name);
+        }
+        Expression::LiteralNumber { value } => {
+            write!(output, "{}", value.to_i32()).expect(INFALLIBLE);
+        }
+        Expression::LocalFunction { function } => {
+            format_function(function, level, output);
+        }
+        Expression::Record { fields } => {
+            let fields = fields.iter().map(|name| (name.as_str(), None));
+            format_record(fields, output);
+        }
+        Expression::Error { .. } => {
+            unreachable!(
+                "Not formatting code with errors, so there can't be any error \
+                expressions."
+            );
+        }
+    }
+}
+
+fn format_comment(
+    comment: Option<&Comment>,
+    level: usize,
+    output: &mut String,
+) {
+    let Some(comment) = comment else {
+        return;
+    };
+
+    for line in &comment.lines {
+        indent(level, output);
+
+        if line.is_empty() {
+            output.push_str("#\n");
+        } else {
+            writeln!(output, "# {line}").expect(INFALLIBLE);
+        }
+    }
+}
+
+fn format_signature(signature: &Signature<SyntaxType>, output: &mut String) {
+    for (i, input) in signature.inputs.iter().enumerate() {
+        if i > 0 {
+            output.push_str(", ");
+        }
+
+        format_type(input, output);
+    }
+
+    if !signature.inputs.is_empty() {
+        output.push(' ');
+    }
+    output.push_str("->");
+
+    for (i, output_type) in signature.outputs.iter().enumerate() {
+        if i > 0 {
+            output.push(',');
+        }
+
+        output.push(' ');
+        format_type(output_type, output);
+    }
+}
+
+fn format_type(type_: &SyntaxType, output: &mut String) {
+    match type_ {
+        SyntaxType::Function {
+            signature,
+            environment,
+        } => {
+            output.push_str("fn ");
+            format_signature(signature, output);
+
+            if !environment.is_empty() {
+                output.push_str(" captures ");
+                for (i, type_) in environment.iter().enumerate() {
+                    if i > 0 {
+                        output.push_str(", ");
+                    }
+                    format_type(type_, output);
+                }
+            }
+
+            output.push_str(" end");
+        }
+        SyntaxType::Identifier { name } => {
+            output.push_str(name);
+        }
+        SyntaxType::Record { fields } => {
+            let fields = fields
+                .iter()
+                .map(|(name, type_)| (name.as_str(), Some(type_)));
+            format_record(fields, output);
+        }
+    }
+}
+
+fn format_record<'r>(
+    fields: impl IntoIterator<Item = (&'r str, Option<&'r SyntaxType>)>,
+    output: &mut String,
+) {
+    let fields = fields.into_iter().collect::<Vec<_>>();
+
+    if fields.is_empty() {
+        output.push_str("{}");
+        return;
+    }
+
+    output.push_str("{ ");
+    for (i, (name, type_)) in fields.into_iter().enumerate() {
+        if i > 0 {
+            output.push_str(", ");
+        }
+
+        output.push_str(name);
+
+        if let Some(type_) = type_ {
+            output.push_str(": ");
+            format_type(type_, output);
+        }
+    }
+    output.push_str(" }");
+}
+
+fn indent(level: usize, output: &mut String) {
+    for _ in 0..level {
+        output.push_str(INDENTATION);
+    }
+}
+
+fn check_tokens(input: &str, output: &str) -> Result<(), FormatError> {
+    let mut input = Tokens::tokenize(input);
+    let mut output = Tokens::tokenize(output);
+
+    loop {
+        let span = input.next_span();
+
+        match (input.take(), output.take()) {
+            (Err(_), Err(_)) => {
+                return Ok(());
+            }
+            (Ok(a), Ok(b)) if tokens_match(&a, &b) => {
+                continue;
+            }
+            _ => {
+                return Err(FormatError::NotPreserved { span });
+            }
+        }
+    }
+}
+
+fn tokens_match(a: &Token, b: &Token) -> bool {
+    match (a, b) {
+        // The formatter normalizes the whitespace at the start of comment
+        // lines. That's not a change we need to worry about.
+        (Token::CommentLine { line: a }, Token::CommentLine { line: b }) => {
+            a.trim() == b.trim()
+        }
+        (a, b) => a == b,
+    }
+}
+
+const INFALLIBLE: &str = "Writing to `String` can't fail";
+
+#[cfg(test)]
+mod tests {
+    use super::{format, FormatError};
+
+    #[test]
+    fn format_into_canonical_form() {
+        let output = format(
+            r"
+            f:fn br a,b->a b
+            fn br->end end
+            end
+            br 0,_ -> end end
+            g: fn
+                br -> end
+            end
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(
+            output,
+            "\
+f: fn
+    br a, b ->
+        a
+        b
+        fn
+            br ->
+            end
+        end
+    end
+
+    br 0, _ ->
+    end
+end
+
+g: fn
+    br ->
+    end
+end
+"
+        );
+    }
+
+    #[test]
+    fn preserve_comments_and_type_annotations() {
+        let input = "\
+# A function
+#   with an indented line
+f: fn
+    # A branch
+    br x: S32, g: fn S32 -> S32 end ->
+        # A member
+        x: -> S32.
+        g: fn S32 -> S32 end ->.
+        eval: S32, fn S32 -> S32 end -> S32, S32.
+    end
+end
+";
+
+        assert_eq!(format(input).unwrap(), input);
+    }
+
+    #[test]
+    fn preserve_imports() {
+        let input = "\
+# Vectors
+import vec
+import color
+
+f: fn
+    br ->
+        vec/add
+    end
+end
+";
+
+        assert_eq!(format(input).unwrap(), input);
+    }
+
+    #[test]
+    fn preserve_captures_in_function_types() {
+        let input = "\
+f: fn
+    br g: fn S32 -> captures U8, S32 end ->
+        g
+    end
+end
+";
+
+        assert_eq!(format(input).unwrap(), input);
+    }
+
+    #[test]
+    fn preserve_records() {
+        let input = "\
+f: fn
+    br p: { x: S32, y: S32 } ->
+        p
+        .x
+        p
+        .y
+        { x, y }: S32, S32 -> { x: S32, y: S32 }.
+        {}
+    end
+end
+";
+
+        assert_eq!(format(input).unwrap(), input);
+    }
+
+    #[test]
+    fn formatting_is_idempotent() {
+        let input = include_str!("../../../games/snake/main.capi");
+
+        let output = format(input).unwrap();
+        assert_eq!(format(&output).unwrap(), output);
+    }
+
+    #[test]
+    fn refuse_to_format_code_with_errors() {
+        let result = format(
+            r"
+                f: fn
+                    br ->
+                        :
+                    end
+                end
+            ",
+        );
+
+        assert!(matches!(result, Err(FormatError::Diagnostics { .. })));
+    }
+
+    #[test]
+    fn preserve_comment_at_end_of_file() {
+        let input = "\
+import vec
+
+f: fn
+    br ->
+    end
+end
+
+# This comment is at the end of the file.
+";
+
+        assert_eq!(format(input).unwrap(), input);
+    }
+
+    #[test]
+    fn preserve_comment_before_end_of_function() {
+        let input = "\
+f: fn
+    br ->
+        fn
+            br ->
+            end
+            # This comment is at the end of a local function.
+        end
+        # This comment is at the end of a branch.
+    end
+    # This comment is at the end of a named function.
+end
+";
+
+        assert_eq!(format(input).unwrap(), input);
+    }
+}
@@ -1,7 +1,18 @@
 use crosscut_runtime::{Instruction, InstructionAddress};
 
+use crate::code::Hash;
+
 /// # Compiled instructions for the runtime to execute
-#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    udigest::Digestable,
+)]
 pub struct Instructions {
     inner: Vec<(InstructionAddress, Instruction)>,
 }
@@ -33,7 +44,200 @@ impl Instructions {
         *stored_instruction = instruction;
     }
 
-    pub fn to_runtime_instructions(&self) -> crosscut_runtime::Instructions {
+    /// # Determine the changes that turn these instructions into `other`
+    pub fn diff(&self, other: &Instructions) -> InstructionsPatch {
+        let mut ranges: Vec<PatchRange> = Vec::new();
+
+        for (address, instruction) in &other.inner {
+            if self.get_if_present(address) == Some(instruction) {
+                continue;
+            }
+
+            match ranges.last_mut() {
+                Some(range) if range.end() == *address => {
+                    range.instructions.push(instruction.clone());
+                }
+                _ => {
+                    ranges.push(PatchRange {
+                        start: *address,
+                        instructions: vec![instruction.clone()],
+                    });
+                }
+            }
+        }
+
+        InstructionsPatch {
+            base: Hash::new(self),
+            len: other.inner.len(),
+            ranges,
+        }
+    }
+
+    /// # Apply changes that were determined by [`Instructions::diff`]
+    ///
+    /// Patches can come from another process, which might have a different idea
+    /// of what the instructions currently are. If the patch was not created
+    /// from these instructions, or doesn't fit them for any other reason, the
+    /// instructions stay as they are, and an error is returned.
+    pub fn apply(
+        &mut self,
+        patch: &InstructionsPatch,
+    ) -> Result<(), PatchMismatch> {
+        if Hash::new(self) != patch.base {
+            return Err(PatchMismatch);
+        }
+
+        let mut patched = self.clone();
+        patched.inner.truncate(patch.len);
+
+        for range in &patch.ranges {
+            let mut address = range.start;
+
+            for instruction in &range.instructions {
+                match patched.inner.get_mut(address.to_usize()) {
+                    Some((_, stored_instruction)) => {
+                        *stored_instruction = instruction.clone();
+                    }
+                    None => {
+                        if patched.push(instruction.clone()) != address {
+                            return Err(PatchMismatch);
+                        }
+                    }
+                }
+
+                address = address.next();
+            }
+        }
+
+        if patched.inner.len() != patch.len {
+            return Err(PatchMismatch);
+        }
+
+        *self = patched;
+
+        Ok(())
+    }
+
+    fn get_if_present(
+        &self,
+        address: &InstructionAddress,
+    ) -> Option<&Instruction> {
+        let (_, instruction) = self.inner.get(address.to_usize())?;
+        Some(instruction)
+    }
+
+    pub fn to_runtime_instructions(
+        &self,
+    ) -> crosscut_runtime::Instructions<'_> {
         crosscut_runtime::Instructions { inner: &self.inner }
     }
 }
+
+/// # The changes between two versions of [`Instructions`]
+///
+/// Only contains the instructions that changed, which usually makes it much
+/// smaller than the full instructions. Created by [`Instructions::diff`], and
+/// applied by [`Instructions::apply`].
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct InstructionsPatch {
+    /// # The hash of the instructions that the patch was created from
+    ///
+    /// A patch only fits the instructions it was created from. This is used to
+    /// check that, before applying it.
+    pub base: Hash<Instructions>,
+
+    /// # The number of instructions, after the patch has been applied
+    pub len: usize,
+
+    /// # The ranges of instructions that changed, ordered by address
+    pub ranges: Vec<PatchRange>,
+}
+
+/// # A patch did not fit the instructions it was applied to
+///
+/// See [`Instructions::apply`].
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+#[error("Patch does not fit the instructions it is applied to")]
+pub struct PatchMismatch;
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct PatchRange {
+    pub start: InstructionAddress,
+    pub instructions: Vec<Instruction>,
+}
+
+impl PatchRange {
+    fn end(&self) -> InstructionAddress {
+        let len: u32 = self.instructions.len().try_into().unwrap();
+        InstructionAddress {
+            index: self.start.index + len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crosscut_runtime::{Effect, Instruction, InstructionAddress};
+
+    use super::{Instructions, PatchMismatch};
+
+    #[test]
+    fn patch_changed_and_appended_instructions() {
+        let old = instructions([
+            Instruction::Nop,
+            Instruction::Nop,
+            Instruction::Nop,
+            Instruction::Nop,
+        ]);
+        let mut new = old.clone();
+        new.replace(&InstructionAddress { index: 1 }, brk());
+        new.push(brk());
+        new.push(Instruction::Nop);
+
+        let patch = old.diff(&new);
+        assert_eq!(patch.ranges.len(), 2);
+        assert_eq!(patch.ranges[0].instructions, [brk()]);
+        assert_eq!(patch.ranges[1].instructions, [brk(), Instruction::Nop]);
+
+        let mut patched = old;
+        patched.apply(&patch).unwrap();
+        assert_eq!(patched.inner, new.inner);
+    }
+
+    #[test]
+    fn patch_removed_instructions() {
+        let old = instructions([Instruction::Nop, brk(), Instruction::Nop]);
+        let new = instructions([brk()]);
+
+        let mut patched = old.clone();
+        patched.apply(&old.diff(&new)).unwrap();
+        assert_eq!(patched.inner, new.inner);
+    }
+
+    #[test]
+    fn reject_patch_that_was_created_from_other_instructions() {
+        let old = instructions([Instruction::Nop, Instruction::Nop]);
+        let new = instructions([Instruction::Nop, brk()]);
+        let other = instructions([brk(), Instruction::Nop]);
+
+        let mut patched = other.clone();
+        assert_eq!(patched.apply(&old.diff(&new)), Err(PatchMismatch));
+        assert_eq!(patched, other);
+    }
+
+    fn instructions(
+        instructions: impl IntoIterator<Item = Instruction>,
+    ) -> Instructions {
+        let mut result = Instructions::default();
+        for instruction in instructions {
+            result.push(instruction);
+        }
+        result
+    }
+
+    fn brk() -> Instruction {
+        Instruction::TriggerEffect {
+            effect: Effect::Breakpoint,
+        }
+    }
+}
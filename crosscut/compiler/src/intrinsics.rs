@@ -32,6 +32,11 @@ macro_rules! intrinsics {
         }
 
         impl IntrinsicFunction {
+            /// # Iterate over all intrinsic functions
+            pub fn all() -> impl Iterator<Item = IntrinsicFunction> {
+                [$(Self::$variant,)*].into_iter()
+            }
+
             pub fn from_name(name: &str) -> Option<IntrinsicFunction> {
                 let intrinsic = match name {
                     $($name => Self::$variant,)*
@@ -86,19 +91,19 @@ use Type::*;
 
 intrinsics! {
     /// # Add two signed 8-bit integers, triggering an error on overflow
-    "add_s8", AddS8, Some(([Number, Number], [Number]));
+    "add_s8", AddS8, Some(([S8, S8], [S8]));
 
     /// # Add two signed 32-bit integers, triggering an error on overflow
-    "add_s32", AddS32, Some(([Number, Number], [Number]));
+    "add_s32", AddS32, Some(([S32, S32], [S32]));
 
     /// # Add two unsigned 8-bit integers, triggering an error on overflow
-    "add_u8", AddU8, Some(([Number, Number], [Number]));
+    "add_u8", AddU8, Some(([U8, U8], [U8]));
 
     /// # Add two unsigned 8-bit integers, wrapping on overflow
-    "add_u8_wrap", AddU8Wrap, Some(([Number, Number], [Number]));
+    "add_u8_wrap", AddU8Wrap, Some(([U8, U8], [U8]));
 
     /// # Logical and
-    "and", And, Some(([Number, Number], [Number]));
+    "and", And, Some(([Bool, Bool], [Bool]));
 
     /// # Trigger a breakpoint
     "brk", Brk, Some(([], []));
@@ -107,56 +112,71 @@ intrinsics! {
     "copy", Copy, Option::<([Type; 0], [Type; 0])>::None;
 
     /// # Divide two signed 32-bit integers
-    "div_s32", DivS32, Some(([Number, Number], [Number]));
+    "div_s32", DivS32, Some(([S32, S32], [S32]));
 
     /// # Divide two unsigned 8-bit integers
-    "div_u8", DivU8, Some(([Number, Number], [Number]));
+    "div_u8", DivU8, Some(([U8, U8], [U8]));
 
     /// # Drop a value
     "drop", Drop, Option::<([Type; 0], [Type; 0])>::None;
 
-    /// # Compare two values for equality
-    "eq", Eq, Some(([Number, Number], [Number]));
+    /// # Compare two values of the same type for equality
+    "eq", Eq, Option::<([Type; 0], [Type; 0])>::None;
 
     /// # Evaluate an anonymous function
     "eval", Eval, Option::<([Type; 0], [Type; 0])>::None;
 
     /// # Determine if the first of two signed 8-bit numbers is greater
-    "greater_s8", GreaterS8, Some(([Number, Number], [Number]));
+    "greater_s8", GreaterS8, Some(([S8, S8], [Bool]));
 
     /// # Determine if the first of two signed 32-bit numbers is greater
-    "greater_s32", GreaterS32, Some(([Number, Number], [Number]));
+    "greater_s32", GreaterS32, Some(([S32, S32], [Bool]));
 
     /// # Determine if the first of two unsigned 8-bit numbers is greater
-    "greater_u8", GreaterU8, Some(([Number, Number], [Number]));
+    "greater_u8", GreaterU8, Some(([U8, U8], [Bool]));
 
     /// # Multiply two signed 32-bit numbers, triggering an error on overflow
-    "mul_s32", MulS32, Some(([Number, Number], [Number]));
+    "mul_s32", MulS32, Some(([S32, S32], [S32]));
 
     /// # Multiply two unsigned 8-bit numbers, wrapping on overflow
-    "mul_u8_wrap", MulU8Wrap, Some(([Number, Number], [Number]));
+    "mul_u8_wrap", MulU8Wrap, Some(([U8, U8], [U8]));
 
     /// # Negate a signed 32-bit number
-    "neg_s32", NegS32, Some(([Number], [Number]));
+    "neg_s32", NegS32, Some(([S32], [S32]));
 
     /// No operation
     "nop", Nop, Some(([], []));
 
     /// # Logical not
-    "not", Not, Some(([Number], [Number]));
+    "not", Not, Some(([Bool], [Bool]));
 
     /// # Compute the remainder of the division of two signed 32-bit numbers
-    "remainder_s32", RemainderS32, Some(([Number, Number], [Number]));
+    "remainder_s32", RemainderS32, Some(([S32, S32], [S32]));
+
+    /// # Convert a signed 8-bit number to a signed 32-bit number
+    "s8_to_s32", S8ToS32, Some(([S8], [S32]));
+
+    /// # Convert a signed 8-bit number to an unsigned 8-bit number, wrapping
+    "s8_to_u8_wrap", S8ToU8Wrap, Some(([S8], [U8]));
 
     /// # Convert a signed 32-bit number to a signed 8-bit number
-    "s32_to_s8", S32ToS8, Some(([Number], [Number]));
+    "s32_to_s8", S32ToS8, Some(([S32], [S8]));
+
+    /// # Convert a signed 32-bit number to an unsigned 8-bit number
+    "s32_to_u8", S32ToU8, Some(([S32], [U8]));
 
     /// # Subtract two signed 32-bit numbers, triggering an error on overflow
-    "sub_s32", SubS32, Some(([Number, Number], [Number]));
+    "sub_s32", SubS32, Some(([S32, S32], [S32]));
 
     /// # Subtract two unsigned 8-bit numbers, triggering an error on overflow
-    "sub_u8", SubU8, Some(([Number, Number], [Number]));
+    "sub_u8", SubU8, Some(([U8, U8], [U8]));
 
     /// # Subtract two unsigned 8-bit numbers, wrapping on overflow
-    "sub_u8_wrap", SubU8Wrap, Some(([Number, Number], [Number]));
+    "sub_u8_wrap", SubU8Wrap, Some(([U8, U8], [U8]));
+
+    /// # Convert an unsigned 8-bit number to a signed 8-bit number, wrapping
+    "u8_to_s8_wrap", U8ToS8Wrap, Some(([U8], [S8]));
+
+    /// # Convert an unsigned 8-bit number to a signed 32-bit number
+    "u8_to_s32", U8ToS32, Some(([U8], [S32]));
 }
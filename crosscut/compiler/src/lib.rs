@@ -1,4 +1,6 @@
 pub mod code;
+pub mod diagnostics;
+pub mod format;
 pub mod host;
 pub mod intrinsics;
 pub mod source_map;
@@ -12,5 +14,7 @@ mod tests;
 
 pub use self::{
     compiler::{Compiler, CompilerOutput},
-    instructions::Instructions,
+    instructions::{
+        Instructions, InstructionsPatch, PatchMismatch, PatchRange,
+    },
 };
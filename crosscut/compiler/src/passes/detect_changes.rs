@@ -1,33 +1,55 @@
 use std::collections::BTreeMap;
 
 use crate::code::{
-    syntax::SyntaxTree, Changes, FunctionInUpdate, FunctionUpdate, Hash,
+    syntax::SyntaxTree, Changes, Contexts, FunctionInUpdate, FunctionUpdate,
+    Hash,
 };
 
 pub fn detect_changes(
     old_code: Option<SyntaxTree>,
+    old_contexts: &Contexts,
     new_code: &SyntaxTree,
+    new_contexts: &Contexts,
 ) -> Changes {
     let old_code = old_code.unwrap_or_default();
 
     let mut added = BTreeMap::new();
     let mut updated = Vec::new();
 
+    // Changes are detected per module. A function is only ever compared to the
+    // functions that were previously defined in the same module.
     for new_function in new_code.named_functions() {
-        if old_code.named_functions.values().any(|old_function| {
-            Hash::new(&old_function.inner)
-                == Hash::new(&new_function.fragment.inner)
-        }) {
+        // What the calls in a function refer to depends on the imports of its
+        // module. If those have changed, so might have the function.
+        let imports_unchanged =
+            imported_modules(&old_code, &new_function.module)
+                == imported_modules(new_code, &new_function.module);
+
+        // What a function compiles to also depends on what the identifiers in
+        // it resolve to, and on the types inferred for it. Those types depend
+        // on the signatures of the functions it calls. If any of that has
+        // changed, the function needs to be compiled again, even if its code is
+        // the same.
+        let new_context = new_contexts.get(&new_function.index());
+
+        if imports_unchanged
+            && old_code.named_functions().any(|old_function| {
+                old_function.module == new_function.module
+                    && Hash::new(&old_function.inner)
+                        == Hash::new(&new_function.fragment.inner)
+                    && old_contexts.get(&old_function.index()) == new_context
+            })
+        {
             // Function has not changed. We can forget about it.
             continue;
         }
 
         if let Some(old_function) =
-            old_code.function_by_name(&new_function.name)
+            old_code.function_by_path(&new_function.name, &new_function.module)
         {
-            // Found a function with the same name. But it can't have the same
-            // hash, or we wouldn't have made it here. Assuming the new function
-            // is an updated version of the old.
+            // Found a function with the same name in the same module. But it
+            // can't have the same hash, imports, and context, or we wouldn't
+            // have made it here. Assuming the new function is an updated version of the old.
             updated.push(FunctionUpdate {
                 old: FunctionInUpdate {
                     location: old_function.location(),
@@ -49,3 +71,16 @@ pub fn detect_changes(
 
     Changes { added, updated }
 }
+
+fn imported_modules<'r>(code: &'r SyntaxTree, module: &str) -> Vec<&'r str> {
+    code.modules
+        .get(module)
+        .map(|module| {
+            module
+                .imports
+                .iter()
+                .map(|import| import.module.as_str())
+                .collect()
+        })
+        .unwrap_or_default()
+}
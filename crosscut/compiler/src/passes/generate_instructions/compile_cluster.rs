@@ -48,12 +48,12 @@ pub fn compile_cluster(
     for function in cluster.functions(functions_context.syntax_tree) {
         let location = function.location.clone();
 
-        let runtime_function =
+        let address =
             compile_function(function, &mut context, functions_context);
 
         functions_context
             .compiled_functions_by_location
-            .insert(location, runtime_function);
+            .insert(location, address);
     }
 
     for (callee, calls) in context.recursive_calls_by_callee {
@@ -1,11 +1,14 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::BTreeMap;
 
 use crosscut_runtime::{Effect, Instruction, InstructionAddress, Value};
 
 use crate::{
-    code::syntax::{
-        Binding, Branch, Expression, Function, FunctionLocation, Located,
-        Parameter,
+    code::{
+        syntax::{
+            Binding, Branch, Expression, Function, FunctionLocation, Located,
+            Parameter, SyntaxTree,
+        },
+        Bindings, Type, Types,
     },
     intrinsics::IntrinsicFunction,
     source_map::Mapping,
@@ -24,22 +27,30 @@ pub fn compile_function(
     function: Located<&Function>,
     cluster_context: &mut ClusterContext,
     functions_context: &mut FunctionsContext,
-) -> crosscut_runtime::Function {
+) -> InstructionAddress {
     let mut context = FunctionContext {
         location: &function.location,
     };
-    let mut runtime_function = crosscut_runtime::Function::default();
-    let mut instruction_range = None;
+    let mut instruction_range: Option<[InstructionAddress; 2]> = None;
+
+    // If the arguments don't match a branch's parameters, it jumps to the next
+    // branch. We don't know where that is, until we've compiled it.
+    let mut jumps_to_next_branch = Vec::new();
 
     for branch in function.branches() {
-        let (runtime_branch, [first_address, last_address]) = compile_branch(
+        let ([first_address, last_address], jumps) = compile_branch(
             branch,
             &mut context,
             cluster_context,
             functions_context,
         );
 
-        runtime_function.branches.push(runtime_branch);
+        compile_jumps(
+            jumps_to_next_branch,
+            first_address,
+            functions_context.instructions,
+        );
+        jumps_to_next_branch = jumps;
 
         instruction_range = {
             let [first_in_function, _last_in_function] =
@@ -49,13 +60,49 @@ pub fn compile_function(
         };
     }
 
-    if let Some(instruction_range) = instruction_range {
+    if instruction_range.is_none() || !jumps_to_next_branch.is_empty() {
+        // There's no branch left to jump to. Since this means no branch has
+        // matched, that's an error.
+        let no_match = emit_instruction(
+            Instruction::TriggerEffect {
+                effect: Effect::NoMatch,
+            },
+            functions_context.instructions,
+            None,
+        );
+        compile_jumps(
+            jumps_to_next_branch,
+            no_match,
+            functions_context.instructions,
+        );
+
+        instruction_range = Some(
+            instruction_range
+                .map(|[first_address, _]| [first_address, no_match])
+                .unwrap_or([no_match, no_match]),
+        );
+    }
+
+    let Some(instruction_range) = instruction_range else {
+        unreachable!("Just made sure that the function has instructions.");
+    };
+    let [entry_point, _] = instruction_range;
+
+    if let FunctionLocation::Local { .. } = function.location {
+        // A local function is represented at runtime by the address of its
+        // entry point. By the time we get there, the values it captured from
+        // its environment are on top of its arguments. The branches expect
+        // those as additional arguments.
         functions_context
-            .source_map
-            .map_function_to_instructions(function.location, instruction_range);
+            .entry_points_by_local_function
+            .insert(function.location.clone(), entry_point);
     }
 
-    runtime_function
+    functions_context
+        .source_map
+        .map_function_to_instructions(function.location, instruction_range);
+
+    entry_point
 }
 
 fn compile_branch(
@@ -63,44 +110,80 @@ fn compile_branch(
     function_context: &mut FunctionContext,
     cluster_context: &mut ClusterContext,
     functions_context: &mut FunctionsContext,
-) -> (crosscut_runtime::Branch, [InstructionAddress; 2]) {
-    let parameters = branch.parameters.values().filter_map(|parameter| {
-        match parameter {
-            Parameter::Binding {
-                binding: Binding { name },
-                type_: _,
-            } => Some(name),
-            Parameter::Literal { .. } => {
-                // Literal patterns are only relevant when
-                // selecting the branch to execute. They no
-                // longer have meaning once the function
-                // actually starts executing.
-                None
+) -> ([InstructionAddress; 2], Vec<InstructionAddress>) {
+    // The values that a local function captures are passed to it like
+    // arguments, on top of the regular ones.
+    let environment = compile_environment(
+        function_context.location,
+        functions_context.syntax_tree,
+        functions_context.bindings,
+        functions_context.types,
+    );
+
+    let patterns = branch
+        .parameters()
+        .flat_map(|parameter| {
+            let type_ = functions_context
+                .types
+                .type_of_parameter(&parameter.location);
+
+            match parameter.fragment {
+                Parameter::Binding {
+                    binding: Binding { name },
+                    type_: _,
+                } => binding_names(name, type_)
+                    .into_iter()
+                    .map(|name| Pattern::Binding { name })
+                    .collect(),
+                Parameter::Literal { value } => {
+                    vec![Pattern::Literal {
+                        value: literal_value(*value, type_),
+                    }]
+                }
             }
-        }
-    });
+        })
+        .chain(
+            environment
+                .into_iter()
+                .map(|name| Pattern::Binding { name }),
+        )
+        .collect::<Vec<_>>();
+    let parameters = patterns
+        .iter()
+        .filter_map(|pattern| match pattern {
+            Pattern::Binding { name } => Some(name.clone()),
+            Pattern::Literal { .. } => None,
+        })
+        .collect::<Vec<_>>();
+
+    let (match_address, jumps_to_next_branch) =
+        compile_pattern_matching(&patterns, functions_context.instructions);
     let bindings_address =
-        compile_bindings(parameters, functions_context.instructions);
+        compile_bindings(&patterns, functions_context.instructions);
 
     let [body_address, last_address] = {
         let mut body_address = None;
 
-        for expression in branch.expressions() {
-            let bindings = branch
-                .bindings()
-                .map(|binding| binding.fragment.clone())
-                .chain(
-                    functions_context
-                        .bindings
-                        .environment_of(function_context.location)
-                        .bindings(functions_context.syntax_tree)
-                        .map(|binding| binding.fragment.clone()),
-                )
-                .collect();
+        if functions_context
+            .types
+            .has_error_at_branch(&branch.location)
+        {
+            // The branch doesn't fit with the rest of the function. There's no
+            // way to execute it that makes sense.
+            let address = emit_instruction(
+                Instruction::TriggerEffect {
+                    effect: Effect::BuildError,
+                },
+                functions_context.instructions,
+                None,
+            );
+            body_address = Some(address);
+        }
 
+        for expression in branch.expressions() {
             let addr = compile_expression(
                 expression,
-                bindings,
+                &parameters,
                 cluster_context,
                 functions_context,
             );
@@ -138,46 +221,118 @@ fn compile_branch(
         [first_instruction, last_instruction]
     };
 
-    let first_address = bindings_address.unwrap_or(body_address);
+    let first_address =
+        match_address.or(bindings_address).unwrap_or(body_address);
 
-    let branch = crosscut_runtime::Branch {
-        parameters: branch
-            .parameters
-            .values()
-            .cloned()
-            .map(|parameter| match parameter {
-                Parameter::Binding {
-                    binding: Binding { name },
-                    type_: _,
-                } => crosscut_runtime::Pattern::Identifier { name },
-                Parameter::Literal { value } => {
-                    crosscut_runtime::Pattern::Literal { value }
-                }
-            })
-            .collect(),
-        start: first_address,
-    };
+    functions_context.source_map.map_branch_to_instructions(
+        branch.location.clone(),
+        [first_address, last_address],
+        parameters,
+    );
+
+    ([first_address, last_address], jumps_to_next_branch)
+}
 
-    (branch, [first_address, last_address])
+/// # A pattern that an argument is matched against
+///
+/// Every pattern corresponds to a single operand. Parameters whose type
+/// occupies multiple operands have been expanded into multiple patterns.
+enum Pattern {
+    Binding { name: String },
+    Literal { value: Value },
 }
 
-fn compile_bindings<'r, N>(
-    names: N,
+/// # Compile the code that checks whether the arguments match the patterns
+///
+/// Leaves the arguments on the stack, regardless of whether they match. If they
+/// don't, the emitted code jumps to the next branch. Returns the addresses of
+/// those jumps, as that next branch hasn't been compiled yet.
+fn compile_pattern_matching(
+    patterns: &[Pattern],
     instructions: &mut Instructions,
-) -> Option<InstructionAddress>
-where
-    N: IntoIterator<Item = &'r String>,
-    N::IntoIter: DoubleEndedIterator,
-{
+) -> (Option<InstructionAddress>, Vec<InstructionAddress>) {
     let mut first_address = None;
+    let mut jumps = Vec::new();
+
+    for (i, pattern) in patterns.iter().enumerate() {
+        let Pattern::Literal { value } = pattern else {
+            continue;
+        };
+
+        // The last argument is on top of the stack.
+        let offset_from_top = patterns.len() - 1 - i;
 
-    for name in names.into_iter().rev() {
         let address = emit_instruction(
-            Instruction::Bind { name: name.clone() },
+            Instruction::Push {
+                value: Value::from(offset_from_top as i32),
+            },
             instructions,
             None,
         );
         first_address = first_address.or(Some(address));
+
+        for instruction in [
+            Instruction::Copy,
+            Instruction::Push { value: *value },
+            Instruction::Eq,
+        ] {
+            emit_instruction(instruction, instructions, None);
+        }
+
+        let jump = emit_instruction(
+            Instruction::JumpIfZero {
+                // This is a placeholder. It's going to be replaced, once we
+                // know where the next branch is.
+                target: address,
+            },
+            instructions,
+            None,
+        );
+        jumps.push(jump);
+    }
+
+    (first_address, jumps)
+}
+
+fn compile_jumps(
+    jumps: Vec<InstructionAddress>,
+    target: InstructionAddress,
+    instructions: &mut Instructions,
+) {
+    for jump in jumps {
+        instructions.replace(&jump, Instruction::JumpIfZero { target });
+    }
+}
+
+fn compile_bindings(
+    patterns: &[Pattern],
+    instructions: &mut Instructions,
+) -> Option<InstructionAddress> {
+    let mut first_address = None;
+
+    // The slots of the bindings are assigned in order, but the last argument
+    // is on top of the stack.
+    let mut slot = patterns
+        .iter()
+        .filter(|pattern| matches!(pattern, Pattern::Binding { .. }))
+        .count();
+
+    for pattern in patterns.iter().rev() {
+        let instruction = match pattern {
+            Pattern::Binding { .. } => {
+                slot -= 1;
+                Instruction::Bind { slot: slot as u32 }
+            }
+            Pattern::Literal { .. } => {
+                // Literal patterns are only relevant when selecting the branch
+                // to execute. They no longer have meaning once the branch
+                // actually starts executing.
+                Instruction::Drop
+            }
+        };
+
+        let address = emit_instruction(instruction, instructions, None);
+        first_address = first_address.or(Some(address));
     }
 
     first_address
@@ -185,7 +340,7 @@ where
 
 fn compile_expression(
     expression: Located<&Expression>,
-    _: BTreeSet<Binding>,
+    bindings: &[String],
     cluster_context: &mut ClusterContext,
     functions_context: &mut FunctionsContext,
 ) -> InstructionAddress {
@@ -197,24 +352,91 @@ fn compile_expression(
         .source_map
         .map_expression_to_instructions(expression.location.clone());
 
+    if functions_context
+        .types
+        .has_error_at_expression(&expression.location)
+    {
+        return emit_instruction(
+            Instruction::TriggerEffect {
+                effect: Effect::BuildError,
+            },
+            functions_context.instructions,
+            Some(&mut mapping),
+        );
+    }
+
     match expression.fragment {
+        Expression::FieldAccess { field } => {
+            let Some((record, (offset, type_))) = functions_context
+                .types
+                .signature_of_expression(&expression.location)
+                .and_then(|signature| signature.inputs.first())
+                .and_then(|record| Some((record, record.field(field)?)))
+            else {
+                // Inference makes sure that field accesses have a known type,
+                // or records an error. Either way, we won't get here.
+                return emit_instruction(
+                    Instruction::TriggerEffect {
+                        effect: Effect::CompilerBug,
+                    },
+                    functions_context.instructions,
+                    Some(&mut mapping),
+                );
+            };
+
+            let mut instructions = Vec::new();
+
+            // Drop the fields that come after the one we're accessing. Those
+            // are on top of it.
+            for _ in 0..record.size() - offset - type_.size() {
+                instructions.push(Instruction::Drop);
+            }
+
+            // Then remove the fields that come before it, one by one, from
+            // right underneath it.
+            for _ in 0..offset {
+                instructions.push(Instruction::Push {
+                    value: Value::from(type_.size() as i32),
+                });
+                instructions.push(Instruction::Remove);
+            }
+
+            emit_instructions(
+                instructions,
+                functions_context.instructions,
+                &mut mapping,
+            )
+        }
         Expression::Identifier { name } => {
-            if functions_context
-                .bindings
-                .is_binding(&expression.location)
-                .is_some()
+            if let Some(binding) =
+                functions_context.bindings.is_binding(&expression.location)
             {
-                emit_instruction(
-                    Instruction::BindingEvaluate { name: name.clone() },
+                let type_ = functions_context.types.type_of_parameter(binding);
+
+                let instructions = binding_names(name, type_)
+                    .into_iter()
+                    .map(|name| Instruction::BindingEvaluate {
+                        slot: binding_slot(&name, bindings),
+                    })
+                    .collect();
+
+                emit_instructions(
+                    instructions,
                     functions_context.instructions,
-                    Some(&mut mapping),
+                    &mut mapping,
                 )
             } else if let Some(function) = functions_context
                 .function_calls
                 .is_call_to_intrinsic_function(&expression.location)
             {
+                let operand = functions_context
+                    .types
+                    .signature_of_expression(&expression.location)
+                    .and_then(|signature| signature.inputs.first());
+
                 compile_intrinsic(
                     function,
+                    operand,
                     is_tail_expression,
                     functions_context.instructions,
                     &mut mapping,
@@ -303,7 +525,7 @@ fn compile_expression(
 
                     emit_instruction(
                         Instruction::CallFunction {
-                            callee: function.clone(),
+                            callee: *function,
                             is_tail_call: is_tail_expression,
                         },
                         functions_context.instructions,
@@ -335,13 +557,59 @@ fn compile_expression(
                 )
             }
         }
-        Expression::LiteralNumber { value } => emit_instruction(
-            Instruction::Push { value: *value },
+        Expression::LiteralNumber { value } => {
+            let type_ = functions_context
+                .types
+                .signature_of_expression(&expression.location)
+                .and_then(|signature| signature.outputs.first());
+
+            emit_instruction(
+                Instruction::Push {
+                    value: literal_value(*value, type_),
+                },
+                functions_context.instructions,
+                Some(&mut mapping),
+            )
+        }
+        Expression::Record { .. } => {
+            // The fields are already on the stack, laid out the way the record
+            // is supposed to be. There's nothing to do at runtime.
+            emit_instruction(
+                Instruction::Nop,
+                functions_context.instructions,
+                Some(&mut mapping),
+            )
+        }
+        Expression::Error { .. } => emit_instruction(
+            Instruction::TriggerEffect {
+                effect: Effect::BuildError,
+            },
             functions_context.instructions,
             Some(&mut mapping),
         ),
         Expression::LocalFunction { function: _ } => {
-            if functions_context
+            let location = FunctionLocation::from(expression.location.clone());
+
+            // A local function is represented by the values it captures from
+            // its environment, followed by the address of its entry point.
+            let mut first_address = None;
+            for name in compile_environment(
+                &location,
+                functions_context.syntax_tree,
+                functions_context.bindings,
+                functions_context.types,
+            ) {
+                let address = emit_instruction(
+                    Instruction::BindingEvaluate {
+                        slot: binding_slot(&name, bindings),
+                    },
+                    functions_context.instructions,
+                    Some(&mut mapping),
+                );
+                first_address = first_address.or(Some(address));
+            }
+
+            let entry_point = if functions_context
                 .recursion
                 .is_recursive_expression(&expression.location)
             {
@@ -351,48 +619,41 @@ fn compile_expression(
                 // Let's emit a placeholder instruction and arrange for that
                 // to be replaced later, once all of the functions in the
                 // cluster have been compiled.
-                let address = emit_instruction(
-                    Instruction::TriggerEffect {
-                        effect: Effect::CompilerBug,
-                    },
-                    functions_context.instructions,
-                    Some(&mut mapping),
-                );
-                cluster_context
-                    .recursive_local_function_definitions_by_local_function
-                    .insert(expression.location.into(), address);
-
-                address
+                None
             } else {
-                let location = FunctionLocation::from(expression.location);
-
-                let Some(runtime_function) = functions_context
-                    .compiled_functions_by_location
+                let Some(entry_point) = functions_context
+                    .entry_points_by_local_function
                     .get(&location)
                 else {
                     unreachable!(
-                        "Replacing instructions that define local functions \
-                        _after_ all functions have been compiled. Yet can't \
-                        find the local function.",
+                        "Local function is not recursive, so it must have been \
+                        compiled already. Yet can't find its entry point.",
                     )
                 };
 
-                let environment = functions_context
-                    .bindings
-                    .environment_of(&location)
-                    .bindings(functions_context.syntax_tree)
-                    .map(|binding| binding.name.clone())
-                    .collect();
+                Some(*entry_point)
+            };
 
-                emit_instruction(
-                    Instruction::MakeAnonymousFunction {
-                        branches: runtime_function.branches.clone(),
-                        environment,
+            let address = emit_instruction(
+                match entry_point {
+                    Some(entry_point) => Instruction::Push {
+                        value: Value::from(entry_point.index),
                     },
-                    functions_context.instructions,
-                    Some(&mut mapping),
-                )
+                    None => Instruction::TriggerEffect {
+                        effect: Effect::CompilerBug,
+                    },
+                },
+                functions_context.instructions,
+                Some(&mut mapping),
+            );
+
+            if entry_point.is_none() {
+                cluster_context
+                    .recursive_local_function_definitions_by_local_function
+                    .insert(location, address);
             }
+
+            first_address.unwrap_or(address)
         }
     }
 }
@@ -400,7 +661,7 @@ fn compile_expression(
 pub fn compile_call_to_function(
     callee: &FunctionLocation,
     call: CallToFunction,
-    functions: &mut BTreeMap<FunctionLocation, crosscut_runtime::Function>,
+    functions: &mut BTreeMap<FunctionLocation, InstructionAddress>,
     instructions: &mut Instructions,
 ) {
     let callee = functions.get(callee).expect(
@@ -411,7 +672,7 @@ pub fn compile_call_to_function(
     instructions.replace(
         &call.address,
         Instruction::CallFunction {
-            callee: callee.clone(),
+            callee: *callee,
             is_tail_call: call.is_tail_call,
         },
     );
@@ -422,8 +683,8 @@ pub fn compile_definition_of_local_function(
     address: InstructionAddress,
     functions_context: &mut FunctionsContext,
 ) {
-    let Some(runtime_function) = functions_context
-        .compiled_functions_by_location
+    let Some(entry_point) = functions_context
+        .entry_points_by_local_function
         .get(&local_function)
     else {
         unreachable!(
@@ -432,28 +693,61 @@ pub fn compile_definition_of_local_function(
         )
     };
 
-    let environment = functions_context
-        .bindings
-        .environment_of(&local_function)
-        .bindings(functions_context.syntax_tree)
-        .map(|binding| binding.name.clone())
-        .collect();
-
     functions_context.instructions.replace(
         &address,
-        Instruction::MakeAnonymousFunction {
-            branches: runtime_function.branches.clone(),
-            environment,
+        Instruction::Push {
+            value: Value::from(entry_point.index),
         },
     );
 }
 
+/// # The slot of the binding with the provided name
+///
+/// The slots of a branch are its parameters, followed by the values it
+/// captures, in the order they are bound in.
+fn binding_slot(name: &str, bindings: &[String]) -> u32 {
+    let Some(slot) = bindings.iter().position(|binding| binding == name) else {
+        unreachable!(
+            "Compiling access to binding `{name}`, but it's not available in \
+            the current branch. Bindings are resolved before compilation, so \
+            this can't happen."
+        );
+    };
+
+    slot as u32
+}
+
+/// # The names of the values that a local function captures, in order
+fn compile_environment(
+    local_function: &FunctionLocation,
+    syntax_tree: &SyntaxTree,
+    bindings: &Bindings,
+    types: &Types,
+) -> Vec<String> {
+    bindings
+        .environment_of(local_function)
+        .bindings(syntax_tree)
+        .flat_map(|binding| {
+            let type_ = types.type_of_parameter(&binding.location);
+            binding_names(&binding.name, type_)
+        })
+        .collect()
+}
+/// # Compile a call to an intrinsic function
+///
+/// Some intrinsics work with values of any type. If so, the type of the first
+/// operand determines how many operands they need to work with.
 fn compile_intrinsic(
     intrinsic: &IntrinsicFunction,
+    operand: Option<&Type>,
     is_tail_call: bool,
     instructions: &mut Instructions,
     mapping: &mut Mapping,
 ) -> InstructionAddress {
+    // If the type isn't known, we're dealing with a value that occupies a
+    // single operand. Otherwise, inference would have made sure we know it.
+    let size = operand.map(|type_| type_.size()).unwrap_or(1);
+
     let instruction = match intrinsic {
         IntrinsicFunction::AddS8 => Instruction::AddS8,
         IntrinsicFunction::AddS32 => Instruction::AddS32,
@@ -464,23 +758,42 @@ fn compile_intrinsic(
             effect: Effect::Breakpoint,
         },
         IntrinsicFunction::Copy => {
-            let offset_from_top = Value::from(0);
+            // Every copy moves the next operand of the value to the same
+            // offset from the top.
+            let offset_from_top = Value::from(size as i32 - 1);
+
+            let instructions_for_copy = (0..size)
+                .flat_map(|_| {
+                    [
+                        Instruction::Push {
+                            value: offset_from_top,
+                        },
+                        Instruction::Copy,
+                    ]
+                })
+                .collect();
 
-            let address = emit_instruction(
-                Instruction::Push {
-                    value: offset_from_top,
-                },
+            return emit_instructions(
+                instructions_for_copy,
                 instructions,
-                Some(mapping),
+                mapping,
             );
-            emit_instruction(Instruction::Copy, instructions, Some(mapping));
-
-            return address;
         }
         IntrinsicFunction::DivS32 => Instruction::DivS32,
         IntrinsicFunction::DivU8 => Instruction::DivU8,
-        IntrinsicFunction::Drop => Instruction::Drop,
-        IntrinsicFunction::Eq => Instruction::Eq,
+        IntrinsicFunction::Drop => {
+            let instructions_for_drop =
+                (0..size).map(|_| Instruction::Drop).collect();
+
+            return emit_instructions(
+                instructions_for_drop,
+                instructions,
+                mapping,
+            );
+        }
+        IntrinsicFunction::Eq => {
+            return emit_instructions(compile_eq(size), instructions, mapping);
+        }
         IntrinsicFunction::Eval => Instruction::Eval { is_tail_call },
         IntrinsicFunction::GreaterS8 => Instruction::GreaterS8,
         IntrinsicFunction::GreaterS32 => Instruction::GreaterS32,
@@ -491,15 +804,125 @@ fn compile_intrinsic(
         IntrinsicFunction::Nop => Instruction::Nop,
         IntrinsicFunction::Not => Instruction::LogicalNot,
         IntrinsicFunction::RemainderS32 => Instruction::RemainderS32,
+        IntrinsicFunction::S8ToS32 => Instruction::ConvertS8ToS32,
+        IntrinsicFunction::S8ToU8Wrap => Instruction::ConvertS8ToU8Wrap,
         IntrinsicFunction::S32ToS8 => Instruction::ConvertS32ToS8,
+        IntrinsicFunction::S32ToU8 => Instruction::ConvertS32ToU8,
         IntrinsicFunction::SubS32 => Instruction::SubS32,
         IntrinsicFunction::SubU8 => Instruction::SubU8,
         IntrinsicFunction::SubU8Wrap => Instruction::SubU8Wrap,
+        IntrinsicFunction::U8ToS8Wrap => Instruction::ConvertU8ToS8Wrap,
+        IntrinsicFunction::U8ToS32 => Instruction::ConvertU8ToS32,
     };
 
     emit_instruction(instruction, instructions, Some(mapping))
 }
 
+/// # Compare two values that occupy the provided number of operands each
+fn compile_eq(size: usize) -> Vec<Instruction> {
+    if size == 1 {
+        return vec![Instruction::Eq];
+    }
+
+    let mut instructions = Vec::new();
+
+    // Compare the values operand by operand, copying each pair to the top of
+    // the stack. Combine the results as we go, so there's only ever one
+    // result on top of the values we compare.
+    for i in 0..size {
+        let results = if i == 0 { 0 } else { 1 };
+
+        let offset_of_a = 2 * size - 1 - i + results;
+        let offset_of_b = size - 1 - i + results + 1;
+
+        for offset in [offset_of_a, offset_of_b] {
+            instructions.push(Instruction::Push {
+                value: Value::from(offset as i32),
+            });
+            instructions.push(Instruction::Copy);
+        }
+        instructions.push(Instruction::Eq);
+
+        if i > 0 {
+            instructions.push(Instruction::LogicalAnd);
+        }
+    }
+
+    // An empty record is always equal to another one.
+    if size == 0 {
+        instructions.push(Instruction::Push {
+            value: Value::from(1),
+        });
+    }
+
+    // Now the values we compared are still right underneath the result.
+    for _ in 0..2 * size {
+        instructions.push(Instruction::Push {
+            value: Value::from(1),
+        });
+        instructions.push(Instruction::Remove);
+    }
+
+    instructions
+}
+
+/// # The names of the slots that the operands of a binding are bound to
+///
+/// Every slot holds a single operand. A binding whose type occupies multiple
+/// operands (like a record, or a function that captures values) is bound to
+/// one slot per operand, each with its own name. Since `.` can't be part of an
+/// identifier, those names can't collide with any other binding. The debugger
+/// displays them, via the source map.
+fn binding_names(name: &str, type_: Option<&Type>) -> Vec<String> {
+    match type_ {
+        Some(Type::Function { environment, .. }) => environment
+            .iter()
+            .enumerate()
+            .flat_map(|(i, type_)| {
+                binding_names(&format!("{name}.{i}"), Some(type_))
+            })
+            .chain([name.to_string()])
+            .collect(),
+        Some(Type::Record { fields }) => fields
+            .iter()
+            .flat_map(|(field, type_)| {
+                binding_names(&format!("{name}.{field}"), Some(type_))
+            })
+            .collect(),
+        _ => vec![name.to_string()],
+    }
+}
+
+fn literal_value(value: Value, type_: Option<&Type>) -> Value {
+    // If the type of the literal is not known, that means it doesn't matter.
+    // If the literal doesn't fit its type, that has already been reported as an
+    // error. Either way, there's nothing better to do than the default.
+    type_
+        .and_then(|type_| type_.literal_value(value))
+        .unwrap_or(value)
+}
+
+/// # Emit the provided instructions, returning the address of the first
+///
+/// Emits a `nop`, if there are no instructions. Every expression needs at
+/// least one instruction, for the debugger to have something to point to.
+fn emit_instructions(
+    instructions: Vec<Instruction>,
+    target: &mut Instructions,
+    mapping: &mut Mapping<'_>,
+) -> InstructionAddress {
+    let mut first_address = None;
+
+    for instruction in instructions {
+        let address = emit_instruction(instruction, target, Some(mapping));
+        first_address = first_address.or(Some(address));
+    }
+
+    first_address.unwrap_or_else(|| {
+        emit_instruction(Instruction::Nop, target, Some(mapping))
+    })
+}
+
 fn emit_instruction(
     instruction: Instruction,
     instructions: &mut Instructions,
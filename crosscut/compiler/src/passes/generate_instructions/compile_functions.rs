@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use crosscut_runtime::Instruction;
+use crosscut_runtime::{Instruction, InstructionAddress};
 
 use crate::{
     code::{
@@ -21,12 +21,21 @@ pub struct FunctionsContext<'r> {
     pub bindings: &'r Bindings,
     pub function_calls: &'r FunctionCalls,
     pub tail_expressions: &'r TailExpressions,
+    pub types: &'r Types,
     pub recursion: &'r Recursion,
     pub instructions: &'r mut Instructions,
     pub source_map: &'r mut SourceMap,
     pub call_instructions_by_callee: &'r mut CallInstructionsByCallee,
     pub compiled_functions_by_location:
-        &'r mut BTreeMap<FunctionLocation, crosscut_runtime::Function>,
+        &'r mut BTreeMap<FunctionLocation, InstructionAddress>,
+
+    /// # The entry points of all local functions that have been compiled
+    ///
+    /// A local function is evaluated by jumping to its entry point, the start
+    /// of its first branch. Its address is what represents the local function
+    /// at runtime.
+    pub entry_points_by_local_function:
+        BTreeMap<FunctionLocation, InstructionAddress>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -38,14 +47,14 @@ pub fn compile_functions(
     bindings: &Bindings,
     function_calls: &FunctionCalls,
     tail_expressions: &TailExpressions,
-    _: &Types,
+    types: &Types,
     recursion: &Recursion,
     instructions: &mut Instructions,
     source_map: &mut SourceMap,
     call_instructions_by_callee: &mut CallInstructionsByCallee,
     compiled_functions_by_location: &mut BTreeMap<
         FunctionLocation,
-        crosscut_runtime::Function,
+        InstructionAddress,
     >,
 ) {
     let mut context = FunctionsContext {
@@ -54,11 +63,13 @@ pub fn compile_functions(
         bindings,
         function_calls,
         tail_expressions,
+        types,
         recursion,
         instructions,
         source_map,
         call_instructions_by_callee,
         compiled_functions_by_location,
+        entry_points_by_local_function: BTreeMap::new(),
     };
 
     for cluster in dependencies.clusters() {
@@ -96,7 +107,7 @@ pub fn compile_functions(
             context.instructions.replace(
                 &calling_address,
                 Instruction::CallFunction {
-                    callee: function.clone(),
+                    callee: *function,
                     is_tail_call: *is_tail_call,
                 },
             );
@@ -33,7 +33,7 @@ pub fn generate_instructions(
     call_instructions_by_callee: &mut CallInstructionsByCallee,
     compiled_functions_by_location: &mut BTreeMap<
         FunctionLocation,
-        crosscut_runtime::Function,
+        InstructionAddress,
     >,
     source_map: &mut SourceMap,
 ) {
@@ -91,7 +91,7 @@ fn compile_call_to_main(
     instructions: &mut Instructions,
     compiled_functions_by_location: &mut BTreeMap<
         FunctionLocation,
-        crosscut_runtime::Function,
+        InstructionAddress,
     >,
 ) {
     let Some(main) = syntax_tree.function_by_name("main") else {
@@ -2,7 +2,10 @@ use std::collections::BTreeMap;
 
 use crosscut_runtime::InstructionAddress;
 
-use crate::code::syntax::{FunctionLocation, MemberLocation};
+use crate::code::{
+    syntax::{BranchLocation, FunctionLocation, MemberLocation, Spans},
+    Span,
+};
 
 /// # Mapping of pre-compiled source code to fully compiled instructions
 #[derive(
@@ -14,6 +17,10 @@ pub struct SourceMap {
     instruction_to_expression: BTreeMap<InstructionAddress, MemberLocation>,
     function_to_instructions:
         BTreeMap<FunctionLocation, [InstructionAddress; 2]>,
+    branch_to_instructions: BTreeMap<BranchLocation, [InstructionAddress; 2]>,
+    branch_to_bindings: BTreeMap<BranchLocation, Vec<String>>,
+    expression_to_span: BTreeMap<MemberLocation, Span>,
+    function_to_span: BTreeMap<FunctionLocation, Span>,
 }
 
 impl SourceMap {
@@ -24,7 +31,7 @@ impl SourceMap {
     pub fn map_expression_to_instructions(
         &mut self,
         expression: MemberLocation,
-    ) -> Mapping {
+    ) -> Mapping<'_> {
         // Make sure we don't have a previous mapping whose leftovers might
         // corrupt the new one.
         self.expression_to_instructions.remove(&expression);
@@ -44,6 +51,30 @@ impl SourceMap {
         self.function_to_instructions.insert(function, range);
     }
 
+    /// # Define which instructions map to the given branch
+    ///
+    /// Also defines the names of the branch's bindings, indexed by the slot
+    /// they are bound to at runtime.
+    pub fn map_branch_to_instructions(
+        &mut self,
+        branch: BranchLocation,
+        range: [InstructionAddress; 2],
+        bindings: Vec<String>,
+    ) {
+        self.branch_to_instructions.insert(branch.clone(), range);
+        self.branch_to_bindings.insert(branch, bindings);
+    }
+
+    /// # Update the spans of source code that expressions and functions map to
+    ///
+    /// Spans change whenever code before them changes, even if the expressions
+    /// and functions themselves don't. This replaces all previous spans, so it
+    /// needs to be called with the spans of the latest code.
+    pub fn update_spans(&mut self, spans: &Spans) {
+        self.expression_to_span = spans.members.clone();
+        self.function_to_span = spans.functions.clone();
+    }
+
     /// # Get the location of the expression that the given instruction maps to
     ///
     /// Can return `None`, as there are a few compiler-generated instructions
@@ -70,6 +101,52 @@ impl SourceMap {
             .unwrap_or(&EMPTY)
     }
 
+    /// # Iterate over the first instruction of every expression
+    ///
+    /// Those are the instructions that the runtime evaluates when it starts
+    /// evaluating an expression. Comments have no instructions, and are not
+    /// represented here.
+    pub fn expression_entry_points(
+        &self,
+    ) -> impl Iterator<Item = InstructionAddress> + '_ {
+        self.expression_to_instructions
+            .values()
+            .filter_map(|instructions| instructions.first().copied())
+    }
+
+    /// # Get the span of source code that the given expression maps to
+    ///
+    /// Can return `None`, if the expression is not part of the latest code.
+    pub fn expression_to_span(
+        &self,
+        expression: &MemberLocation,
+    ) -> Option<Span> {
+        self.expression_to_span.get(expression).copied()
+    }
+
+    /// # Get the span of source code that the given instruction maps to
+    ///
+    /// Can return `None`, for the same reasons that
+    /// [`SourceMap::instruction_to_expression`] and
+    /// [`SourceMap::expression_to_span`] can.
+    pub fn instruction_to_span(
+        &self,
+        instruction: &InstructionAddress,
+    ) -> Option<Span> {
+        let expression = self.instruction_to_expression(instruction)?;
+        self.expression_to_span(expression)
+    }
+
+    /// # Get the span of source code that the given function maps to
+    ///
+    /// Can return `None`, if the function is not part of the latest code.
+    pub fn function_to_span(
+        &self,
+        function: &FunctionLocation,
+    ) -> Option<Span> {
+        self.function_to_span.get(function).copied()
+    }
+
     /// # Access the function from which this instruction was generated
     ///
     /// Can return `None`, as the instruction that call the `main` function were
@@ -90,6 +167,30 @@ impl SourceMap {
             },
         )
     }
+
+    /// # Access the names of the bindings available at the given instruction
+    ///
+    /// The names are indexed by the slot they are bound to at runtime. Can
+    /// return an empty slice, as there are a few compiler-generated
+    /// instructions that are not part of any branch.
+    pub fn instruction_to_bindings(
+        &self,
+        instruction: &InstructionAddress,
+    ) -> &[String] {
+        self.branch_to_instructions
+            .iter()
+            .find_map(|(location, [min, max])| {
+                if instruction.index >= min.index
+                    && instruction.index <= max.index
+                {
+                    self.branch_to_bindings.get(location)
+                } else {
+                    None
+                }
+            })
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
 }
 
 /// # A mapping of an expression to a number of instructions
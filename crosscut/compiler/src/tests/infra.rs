@@ -1,7 +1,8 @@
-use crosscut_runtime::{Effect, Heap, Runtime};
+use crosscut_runtime::{Effect, Runtime, StackLimits};
 
 use crate::{
     code::Type,
+    diagnostics::Diagnostic,
     host::{Host, HostFunction},
     Compiler, Instructions,
 };
@@ -15,16 +16,36 @@ pub struct TestRuntime {
     compiler: Compiler,
     runtime: Runtime,
     instructions: Option<Instructions>,
-    heap: Heap,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl TestRuntime {
+    pub fn stack_limits(&mut self, limits: StackLimits) -> &mut Self {
+        self.runtime = Runtime::with_stack_limits(limits);
+        self
+    }
+
     pub fn update_code(&mut self, source: &str) -> &mut Self {
         let output = self.compiler.compile(source, &TestHost {});
         self.instructions = Some(output.instructions);
+        self.diagnostics = output.diagnostics;
         self
     }
 
+    pub fn update_modules<'r>(
+        &mut self,
+        modules: impl IntoIterator<Item = (&'r str, &'r str)>,
+    ) -> &mut Self {
+        let output = self.compiler.compile_modules(modules, &TestHost {});
+        self.instructions = Some(output.instructions);
+        self.diagnostics = output.diagnostics;
+        self
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
     pub fn run_until_effect(&mut self) -> Option<Effect> {
         let instructions = self
             .instructions
@@ -34,7 +55,6 @@ impl TestRuntime {
         while self.runtime.state().is_running() {
             self.runtime.evaluate_next_instruction(
                 instructions.to_runtime_instructions(),
-                &mut self.heap,
             );
 
             if let Some(effect) = self.runtime.effect_mut().handle() {
@@ -95,7 +115,7 @@ impl Host for TestHost {
         [HostFunction {
             name: "send".into(),
             number: 0,
-            signature: ([Type::Number], []).into(),
+            signature: ([Type::S32], []).into(),
         }]
     }
 }
@@ -1,3 +1,5 @@
+use crosscut_runtime::Effect;
+
 use crate::tests::infra::runtime;
 
 #[test]
@@ -247,3 +249,232 @@ fn compile_call_to_function_that_has_not_been_updated() {
         )
         .run_until_receiving(1);
 }
+
+#[test]
+fn use_updated_code_from_other_module() {
+    // Changes are detected per module. Updating a function in one module
+    // should affect calls to that function, but not calls to functions with
+    // the same name in other modules.
+
+    let mut runtime = runtime();
+
+    let main = r"
+        import other
+
+        main: fn
+            br ->
+                notify
+                other/notify
+                main
+            end
+        end
+
+        notify: fn
+            br ->
+                0 send
+            end
+        end
+    ";
+
+    runtime
+        .update_modules([
+            ("main", main),
+            (
+                "other",
+                r"
+                    notify: fn
+                        br ->
+                            1 send
+                        end
+                    end
+                ",
+            ),
+        ])
+        .run_until_receiving(0)
+        .run_until_receiving(1);
+
+    runtime
+        .update_modules([
+            ("main", main),
+            (
+                "other",
+                r"
+                    notify: fn
+                        br ->
+                            2 send
+                        end
+                    end
+                ",
+            ),
+        ])
+        .run_until_receiving(0)
+        .run_until_receiving(2);
+}
+
+#[test]
+fn update_caller_of_function_that_was_added_to_other_module() {
+    // A call to a function that doesn't exist compiles to a build error. If
+    // the function is added later, the call must be compiled again, even
+    // though the caller itself has not changed.
+
+    let mut runtime = runtime();
+
+    let main = r"
+        import other
+
+        main: fn
+            br ->
+                0 send
+                notify
+                main
+            end
+        end
+
+        notify: fn
+            br ->
+                other/notify
+            end
+        end
+    ";
+
+    runtime
+        .update_modules([
+            ("main", main),
+            (
+                "other",
+                r"
+                    other: fn
+                        br ->
+                        end
+                    end
+                ",
+            ),
+        ])
+        .run_until_receiving(0);
+
+    runtime
+        .update_modules([
+            ("main", main),
+            (
+                "other",
+                r"
+                    other: fn
+                        br ->
+                        end
+                    end
+
+                    notify: fn
+                        br ->
+                            7 send
+                        end
+                    end
+                ",
+            ),
+        ])
+        .run_until_receiving(7);
+
+    assert_eq!(runtime.diagnostics(), &[]);
+}
+
+#[test]
+fn update_caller_of_function_whose_signature_has_changed() {
+    // How a function accesses the fields of a record depends on the layout of
+    // that record, which might come from a function it calls. If the callee
+    // changes the layout, the caller must be compiled again, even though it
+    // has not changed itself.
+
+    let mut runtime = runtime();
+
+    let main = r"
+        main: fn
+            br ->
+                1 2
+                make
+                .a
+                send
+                main
+            end
+        end
+    ";
+
+    runtime
+        .update_code(&format!(
+            "{main}
+            make: fn
+                br a: S32, b: S32 ->
+                    a b {{ a, b }}
+                end
+            end
+            "
+        ))
+        .run_until_receiving(1);
+
+    runtime
+        .update_code(&format!(
+            "{main}
+            make: fn
+                br a: S32, b: S32 ->
+                    b a {{ b, a }}
+                end
+            end
+            "
+        ))
+        .run_until_receiving(1);
+
+    assert_eq!(runtime.diagnostics(), &[]);
+}
+
+#[test]
+fn update_functions_of_module_whose_imports_have_changed() {
+    // What a call refers to depends on the imports of the caller's module. If
+    // those change, the functions in the module must be compiled again, even
+    // if they have not changed themselves.
+
+    let mut runtime = runtime();
+
+    let other = r"
+        notify: fn
+            br ->
+                1 send
+            end
+        end
+    ";
+
+    runtime
+        .update_modules([
+            (
+                "main",
+                r"
+                    import other
+
+                    main: fn
+                        br ->
+                            other/notify
+                            main
+                        end
+                    end
+                ",
+            ),
+            ("other", other),
+        ])
+        .run_until_receiving(1);
+
+    let effect = runtime
+        .update_modules([
+            (
+                "main",
+                r"
+                    main: fn
+                        br ->
+                            other/notify
+                            main
+                        end
+                    end
+                ",
+            ),
+            ("other", other),
+        ])
+        .run_until_effect();
+
+    assert_eq!(effect, Some(Effect::BuildError));
+}
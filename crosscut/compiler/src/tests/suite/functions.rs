@@ -1,4 +1,6 @@
-use crosscut_runtime::{Effect, PopOperandError};
+use crosscut_runtime::{
+    Effect, PopOperandError, PushOperandError, PushStackFrameError, StackLimits,
+};
 
 use crate::tests::infra::runtime;
 
@@ -31,3 +33,217 @@ fn access_operand_from_parent_scope() {
         })
     );
 }
+
+#[test]
+fn unresolved_identifier_triggers_build_error() {
+    // An identifier that refers to nothing must not stop the compiler. It
+    // should only cause an error when executed.
+
+    let effect = runtime()
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        does_not_exist
+                    end
+                end
+            ",
+        )
+        .run_until_effect();
+
+    assert_eq!(effect, Some(Effect::BuildError));
+}
+
+#[test]
+fn call_with_too_few_operands_triggers_build_error() {
+    // A call that can't get the operands it needs is detected at compile-time.
+    // It should trigger a build error, instead of failing at runtime.
+
+    let effect = runtime()
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        1
+                        add_s32
+                    end
+                end
+            ",
+        )
+        .run_until_effect();
+
+    assert_eq!(effect, Some(Effect::BuildError));
+}
+
+#[test]
+fn select_branch_by_matching_literal_patterns() {
+    // The first branch whose literal patterns match the arguments should be
+    // selected. Arguments that don't match a branch must remain available to
+    // the next one.
+
+    runtime()
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        1
+                        2
+                        f
+                    end
+                end
+
+                f: fn
+                    br 1, 1 ->
+                        1
+                        send
+                    end
+
+                    br 1, 2 ->
+                        2
+                        send
+                    end
+
+                    br _, _ ->
+                        3
+                        send
+                    end
+                end
+            ",
+        )
+        .run_until_receiving(2);
+}
+
+#[test]
+fn trigger_effect_if_no_branch_matches() {
+    // If no branch of a function matches the arguments, that should trigger an
+    // effect.
+
+    let effect = runtime()
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        1
+                        f
+                    end
+                end
+
+                f: fn
+                    br 0 ->
+                    end
+                end
+            ",
+        )
+        .run_until_effect();
+
+    assert_eq!(effect, Some(Effect::NoMatch));
+}
+
+#[test]
+fn support_deep_non_tail_recursion() {
+    // Recursion that can't make use of tail call elimination needs a new stack
+    // frame for every call. The default limits should allow for a lot of
+    // those.
+
+    runtime()
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        100
+                        count_down
+                        0
+                        send
+                    end
+                end
+
+                count_down: fn
+                    br 0 ->
+                    end
+
+                    br n ->
+                        n
+                        1
+                        sub_s32
+                        count_down
+                        nop # prevent tail call elimination
+                    end
+                end
+            ",
+        )
+        .run_until_receiving(0);
+}
+
+#[test]
+fn trigger_effect_when_exceeding_frame_limit() {
+    // The number of stack frames is limited. Exceeding that should trigger an
+    // effect.
+
+    let effect = runtime()
+        .stack_limits(StackLimits {
+            frames: 8,
+            ..StackLimits::default()
+        })
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        100
+                        count_down
+                    end
+                end
+
+                count_down: fn
+                    br 0 ->
+                    end
+
+                    br n ->
+                        n
+                        1
+                        sub_s32
+                        count_down
+                        nop # prevent tail call elimination
+                    end
+                end
+            ",
+        )
+        .run_until_effect();
+
+    assert_eq!(
+        effect,
+        Some(Effect::PushStackFrame {
+            source: PushStackFrameError::Overflow
+        })
+    );
+}
+
+#[test]
+fn trigger_effect_when_exceeding_operand_limit() {
+    // The number of operands is limited. Exceeding that should trigger an
+    // effect.
+
+    let effect = runtime()
+        .stack_limits(StackLimits {
+            operands: 2,
+            ..StackLimits::default()
+        })
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        1
+                        2
+                        3
+                    end
+                end
+            ",
+        )
+        .run_until_effect();
+
+    assert_eq!(
+        effect,
+        Some(Effect::PushOperand {
+            source: PushOperandError::Overflow
+        })
+    );
+}
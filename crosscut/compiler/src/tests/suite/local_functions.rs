@@ -104,3 +104,69 @@ fn captured_binding() {
         )
         .run_until_receiving(0);
 }
+
+#[test]
+fn evaluate_copy_of_closure() {
+    runtime()
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        0
+                        fn
+                            br channel ->
+                                fn
+                                    br ->
+                                        channel
+                                    end
+                                end
+                                copy
+                                eval
+                                send
+                                eval
+                                send
+                            end
+                        end
+                        eval
+                    end
+                end
+            ",
+        )
+        .run_until_receiving(0)
+        .run_until_receiving(0);
+}
+
+#[test]
+fn pass_closure_to_function() {
+    runtime()
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        0
+                        make_closure
+                        evaluate_twice
+                    end
+                end
+
+                make_closure: fn
+                    br channel ->
+                        fn
+                            br ->
+                                channel send
+                            end
+                        end
+                    end
+                end
+
+                evaluate_twice: fn
+                    br closure: fn -> captures S32 end ->
+                        closure eval
+                        closure eval
+                    end
+                end
+            ",
+        )
+        .run_until_receiving(0)
+        .run_until_receiving(0);
+}
@@ -1,3 +1,4 @@
 mod code_update;
 mod functions;
 mod local_functions;
+mod types;
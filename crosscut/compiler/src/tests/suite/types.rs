@@ -0,0 +1,122 @@
+use crate::tests::infra::runtime;
+
+#[test]
+fn encode_literal_according_to_inferred_type() {
+    // A literal's type depends on the context it's used in. It must be encoded
+    // accordingly, or operations on it would fail at runtime.
+
+    runtime()
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        -1
+                        s8_to_s32
+                        1
+                        add_s32
+                        send
+                    end
+                end
+            ",
+        )
+        .run_until_receiving(0);
+}
+
+#[test]
+fn pass_record_to_function_and_access_field() {
+    runtime()
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        1 0 2
+                        make_record
+                        copy
+                        drop
+                        get_y
+                        send
+                    end
+                end
+
+                make_record: fn
+                    br x: S32, y: S32, z: S32 ->
+                        x y z { x, y, z }
+                    end
+                end
+
+                get_y: fn
+                    br record: { x: S32, y: S32, z: S32 } ->
+                        record
+                        .y
+                    end
+                end
+            ",
+        )
+        .run_until_receiving(0);
+}
+
+#[test]
+fn compare_records() {
+    runtime()
+        .update_code(
+            r"
+                main: fn
+                    br ->
+                        1 2 { a, b }: S32, S32 -> { a: S32, b: S32 } .
+                        1 3 { a, b }
+                        eq
+                        send_if_false
+
+                        1 2 { a, b }: S32, S32 -> { a: S32, b: S32 } .
+                        1 2 { a, b }
+                        eq
+                        not
+                        send_if_false
+                    end
+                end
+
+                send_if_false: fn
+                    br 0 ->
+                        0 send
+                    end
+                    br _ ->
+                    end
+                end
+            ",
+        )
+        .run_until_receiving(0)
+        .run_until_receiving(0);
+}
+
+#[test]
+fn report_type_error_once_at_conflicting_call() {
+    // The output of `neg_s32` is passed to `add_u8`. That's a single mistake,
+    // so it should result in a single error, at the call that doesn't fit.
+
+    let mut runtime = runtime();
+    runtime.update_code(
+        r"
+            main: fn
+                br ->
+                    1
+                    neg_s32
+                    2
+                    add_u8
+                    drop
+                end
+            end
+        ",
+    );
+
+    let diagnostics = runtime
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| {
+            (diagnostic.message.as_str(), diagnostic.span.to_string())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        diagnostics,
+        [("Type error: expected `U8`, got `S32`.", "7:21".to_string())],
+    );
+}
@@ -1,53 +1,63 @@
-use crosscut_compiler::CompilerOutput;
-use crosscut_protocol::{command::CommandExt, ron_options, Versioned};
-use gloo_net::http::{Request, Response};
+use crosscut_protocol::{
+    code::{CodeAtClient, CodeUpdate},
+    command::CommandExt,
+    wire,
+};
+use gloo_net::http::Request;
 
 use crate::{commands::CommandsToRuntimeTx, model::PersistentState};
 
+#[derive(Default)]
 pub struct CodeFetcher {
-    pub timestamp: u64,
+    /// # The latest code that was received from the server, if any
+    ///
+    /// Updates from the server are based on this.
+    pub code: CodeAtClient,
 }
 
 impl CodeFetcher {
-    pub async fn new(
-        commands_to_runtime_tx: &CommandsToRuntimeTx,
-        state: &mut PersistentState,
-    ) -> anyhow::Result<Self> {
-        let code = Request::get("/code").send().await;
-        let timestamp =
-            on_new_code(code, commands_to_runtime_tx, state).await?;
-
-        Ok(Self { timestamp })
+    pub fn new() -> Self {
+        Self::default()
     }
 
+    /// # Wait for the server to provide new code
+    ///
+    /// If there is no code yet, the server provides the latest code right
+    /// away.
     pub async fn wait_for_new_code(
         &mut self,
         commands_to_runtime_tx: &CommandsToRuntimeTx,
         state: &mut PersistentState,
     ) -> anyhow::Result<()> {
-        let code = Request::get(&format!("/code/{}", self.timestamp))
+        let update = Request::get(&self.code.next_request())
             .send()
-            .await;
-
-        self.timestamp =
-            on_new_code(code, commands_to_runtime_tx, state).await?;
+            .await?
+            .binary()
+            .await?;
+        let update: CodeUpdate = match wire::decode(&update) {
+            Ok(update) => update,
+            Err(err) => {
+                state.on_decode_error(&err);
+                return Err(err.into());
+            }
+        };
+
+        let code = match self.code.apply(update) {
+            Ok(code) => code,
+            Err(err) => {
+                // We've dropped the code that the update didn't fit, so the
+                // next request gets us the full code.
+                log::warn!("Re-requesting full code: {err}");
+                return Ok(());
+            }
+        };
+
+        if let Some(command) = state.on_new_code(code.clone()) {
+            commands_to_runtime_tx.send(command.serialize()).expect(
+                "Command receiver lives in static variable, should never drop.",
+            );
+        }
 
         Ok(())
     }
 }
-
-async fn on_new_code(
-    code: Result<Response, gloo_net::Error>,
-    commands_to_runtime_tx: &CommandsToRuntimeTx,
-    state: &mut PersistentState,
-) -> anyhow::Result<u64> {
-    let code = code?.text().await?;
-    let code: Versioned<CompilerOutput> = ron_options().from_str(&code)?;
-
-    let command = state.on_new_code(code.inner);
-    commands_to_runtime_tx.send(command.serialize()).expect(
-        "Command receiver lives in static variable, should never drop.",
-    );
-
-    Ok(code.timestamp)
-}
@@ -34,10 +34,8 @@ impl Debugger {
         leptos::task::Executor::init_wasm_bindgen().unwrap();
 
         leptos::task::spawn_local(async move {
-            let mut code =
-                CodeFetcher::new(&commands_to_runtime_tx, &mut persistent)
-                    .await
-                    .unwrap();
+            let mut code = CodeFetcher::new();
+            let mut is_fetching_code = true;
 
             loop {
                 select! {
@@ -45,9 +43,17 @@ impl Debugger {
                         code.wait_for_new_code(
                             &commands_to_runtime_tx,
                             &mut persistent,
-                        )
+                        ),
+                        if is_fetching_code
                     => {
-                        result.unwrap();
+                        if let Err(err) = result {
+                            // Either the server is gone, or we can't
+                            // understand it. Retrying won't help in either
+                            // case, but the game can still be debugged with
+                            // the code we have.
+                            log::error!("Stopped fetching code: {err:?}");
+                            is_fetching_code = false;
+                        }
 
                         // Nothing else to do, except do the update that happens
                         // below this `select!`.
@@ -62,6 +68,7 @@ impl Debugger {
                         on_update_from_runtime(
                             update,
                             &mut persistent,
+                            &commands_to_runtime_tx,
                         );
                     }
                     action = actions_rx.recv() => {
@@ -100,9 +107,23 @@ impl Default for Debugger {
     }
 }
 
-fn on_update_from_runtime(update: Vec<u8>, state: &mut PersistentState) {
-    let update = UpdateFromHost::deserialize(update);
-    state.on_update_from_host(update);
+fn on_update_from_runtime(
+    update: Vec<u8>,
+    state: &mut PersistentState,
+    commands_to_runtime_tx: &CommandsToRuntimeTx,
+) {
+    let update = match UpdateFromHost::deserialize(update) {
+        Ok(update) => update,
+        Err(err) => {
+            log::error!("Ignoring malformed update from runtime: {err}");
+            state.on_decode_error(&err);
+            return;
+        }
+    };
+
+    if let Some(command) = state.on_update_from_host(update) {
+        commands_to_runtime_tx.send(command.serialize()).unwrap();
+    }
 }
 
 fn on_ui_action(
@@ -111,10 +132,15 @@ fn on_ui_action(
     transient: &TransientState,
     commands_to_runtime_tx: &CommandsToRuntimeTx,
 ) {
-    let commands = persistent.on_user_action(action, transient).expect(
-        "Failed to handle UI action. This is most likely a bug in the \
-        Crosscut debugger:",
-    );
+    let commands = match persistent.on_user_action(action, transient) {
+        Ok(commands) => commands,
+        Err(err) => {
+            // The action couldn't be handled in the current state, but that
+            // doesn't affect any future actions.
+            log::error!("Failed to handle UI action: {err:?}");
+            return;
+        }
+    };
 
     for command in commands {
         commands_to_runtime_tx.send(command.serialize()).unwrap();
@@ -16,15 +16,24 @@ static COMMANDS: Shared<FramedBuffer<COMMANDS_BUFFER_SIZE>> =
 /// See comment on `crosscut_host::ffi::LAST_UPDATE_READ`
 static LAST_UPDATE_WRITE: Mutex<Option<(usize, usize)>> = Mutex::new(None);
 
+/// # Reserve space for an update in the updates buffer
+///
+/// Returns `false`, if the update doesn't fit. In that case, the host must not
+/// write the update, nor call `on_update`.
 #[no_mangle]
-pub fn updates_write(len: usize) {
+pub fn updates_write(len: usize) -> bool {
     // Sound, because the reference is dropped before we give back control to
     // the host.
     let buffer = unsafe { UPDATES.access() };
-    let update = buffer.write_frame(len);
+    let Some(update) = buffer.write_frame(len) else {
+        log::error!("Dropping update that doesn't fit ({len} bytes)");
+        return false;
+    };
 
     *LAST_UPDATE_WRITE.lock().unwrap() =
         Some((update.as_ptr() as usize, update.len()));
+
+    true
 }
 
 #[no_mangle]
@@ -62,7 +71,14 @@ pub fn commands_read() {
         // Sound, because the reference is dropped before we call the method
         // again or we give back control to the host.
         let buffer = unsafe { COMMANDS.access() };
-        buffer.write_frame(command.len()).copy_from_slice(&command);
+        let Some(frame) = buffer.write_frame(command.len()) else {
+            log::error!(
+                "Dropping command that doesn't fit ({} bytes)",
+                command.len(),
+            );
+            continue;
+        };
+        frame.copy_from_slice(&command);
     }
 
     // Sound, because the reference is dropped before we give back control to
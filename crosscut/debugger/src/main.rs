@@ -2,9 +2,12 @@ mod code;
 mod commands;
 mod debugger;
 mod ffi;
-mod model;
 mod ui;
 
+// The model is shared with other debugger frontends, so it lives in the
+// library. This makes it available under the same path as the other modules.
+use crosscut_debugger::model;
+
 fn main() {
     console_error_panic_hook::set_once();
     console_log::init_with_level(log::Level::Error)
@@ -44,10 +44,16 @@ impl ActiveFunctions {
                         message: ActiveFunctionsMessage::ProcessFinished,
                     };
                 }
+                HostState::FrameOverrun => {
+                    return Self::Message {
+                        message: ActiveFunctionsMessage::FrameOverrun,
+                    };
+                }
                 HostState::Stopped {
                     effect: effects,
                     active_instructions,
                     current_operands: _,
+                    current_bindings: _,
                 } => (effects, active_instructions),
             },
             None => {
@@ -61,18 +67,26 @@ impl ActiveFunctions {
             active_instructions.clone().into();
 
         let mut entries = VecDeque::new();
-        let mut expected_next_function = Some("main".to_string());
+        // Functions are tracked by index, not by name. Functions in different
+        // modules can have the same name.
+        let main = code
+            .syntax_tree
+            .function_by_name("main")
+            .map(|function| function.index());
+        let mut expected_next_function = main;
 
         if let Some(outer) = active_instructions.front() {
-            let (outer, _) = instruction_to_named_function(outer, code);
-            if Some(outer.name) != expected_next_function {
-                expected_next_function = reconstruct_function(
-                    "main",
-                    &mut entries,
-                    code,
-                    breakpoints,
-                    effects.as_ref(),
-                );
+            let (_, outer) = instruction_to_named_function(outer, code);
+            if Some(outer) != expected_next_function {
+                if let Some(main) = main {
+                    expected_next_function = reconstruct_function(
+                        main,
+                        &mut entries,
+                        code,
+                        breakpoints,
+                        effects.as_ref(),
+                    );
+                }
             }
         }
 
@@ -82,10 +96,10 @@ impl ActiveFunctions {
             let active_expression =
                 code.source_map.instruction_to_expression(&address);
 
-            if let Some(expected_name) = &expected_next_function {
-                if expected_name != &named_function.name {
+            if let Some(expected_index) = expected_next_function {
+                if expected_index != function_index_in_root_context {
                     reconstruct_function(
-                        expected_name,
+                        expected_index,
                         &mut entries,
                         code,
                         breakpoints,
@@ -98,31 +112,35 @@ impl ActiveFunctions {
 
             expected_next_function =
                 active_expression.and_then(|active_expression| {
-                    function_call_to_function_name(active_expression, code)
+                    function_call_to_named_function(active_expression, code)
                 });
 
             let cluster = code
                 .dependencies
                 .find_cluster_by_named_function(&function_index_in_root_context)
                 .expect("All named functions must be part of a cluster.");
+            let mut function = DebugFunction::new(
+                named_function.inner,
+                FunctionLocation::Named {
+                    index: function_index_in_root_context,
+                },
+                active_expression,
+                active_instructions.is_empty(),
+                cluster,
+                &code.functions,
+                &code.function_calls,
+                &code.types,
+                &code.source_map,
+                breakpoints,
+                effects.as_ref(),
+            );
+            function
+                .mark_diagnostics(&named_function.module, &code.diagnostics);
+
             entries.push_front(ActiveFunctionsEntry::Function(
                 DebugNamedFunction {
                     name: named_function.name,
-                    inner: DebugFunction::new(
-                        named_function.inner,
-                        FunctionLocation::Named {
-                            index: function_index_in_root_context,
-                        },
-                        active_expression,
-                        active_instructions.is_empty(),
-                        cluster,
-                        &code.functions,
-                        &code.function_calls,
-                        &code.types,
-                        &code.source_map,
-                        breakpoints,
-                        effects.as_ref(),
-                    ),
+                    inner: function,
                 },
             ));
         }
@@ -184,10 +202,7 @@ impl ActiveFunctionsEntries {
                 ActiveFunctionsEntry::Function(function) => Some(function),
                 ActiveFunctionsEntry::Gap => None,
             })
-            .filter_map(|function| match function.inner.active_branch() {
-                Ok(branch) => Some(branch),
-                Err(_) => None,
-            })
+            .filter_map(|function| function.inner.active_branch().ok())
             .find(|branch| {
                 !branch.body.iter().any(|f| f.data.location == *expression)
             });
@@ -231,6 +246,7 @@ pub enum ActiveFunctionsMessage {
     NoProcess,
     ProcessRunning,
     ProcessFinished,
+    FrameOverrun,
 }
 
 impl fmt::Display for ActiveFunctionsMessage {
@@ -248,6 +264,13 @@ impl fmt::Display for ActiveFunctionsMessage {
             Self::ProcessFinished => {
                 write!(f, "Process is finished.")?;
             }
+            Self::FrameOverrun => {
+                write!(
+                    f,
+                    "Process did not finish its frame in time. It might be \
+                    stuck in an infinite loop. Stop it to find out.",
+                )?;
+            }
         }
 
         Ok(())
@@ -287,14 +310,17 @@ fn instruction_to_named_function(
 }
 
 fn reconstruct_function(
-    name: &str,
+    index: Index<NamedFunction>,
     entries: &mut VecDeque<ActiveFunctionsEntry>,
     code: &CompilerOutput,
     breakpoints: &Breakpoints,
     effect: Option<&Effect>,
-) -> Option<String> {
-    let Some(function) = code.syntax_tree.function_by_name(name) else {
-        panic!("Expecting function `{name}` to exist.");
+) -> Option<Index<NamedFunction>> {
+    let Some(function) = code
+        .syntax_tree
+        .find_top_level_parent_function(&FunctionLocation::Named { index })
+    else {
+        panic!("Expecting function `{index:?}` to exist.");
     };
 
     let tail_call = if let Some(branch) =
@@ -313,36 +339,39 @@ fn reconstruct_function(
 
     let expected_next_function = tail_call
         .as_ref()
-        .and_then(|tail_call| function_call_to_function_name(tail_call, code));
+        .and_then(|tail_call| function_call_to_named_function(tail_call, code));
 
     let cluster = code
         .dependencies
         .find_cluster_by_named_function(&function.index())
         .expect("All functions must be part of a cluster.");
+    let mut inner = DebugFunction::new(
+        function.inner.clone(),
+        function.location(),
+        tail_call.as_ref(),
+        false,
+        cluster,
+        &code.functions,
+        &code.function_calls,
+        &code.types,
+        &code.source_map,
+        breakpoints,
+        effect,
+    );
+    inner.mark_diagnostics(&function.module, &code.diagnostics);
+
     entries.push_front(ActiveFunctionsEntry::Function(DebugNamedFunction {
         name: function.name.clone(),
-        inner: DebugFunction::new(
-            function.inner.clone(),
-            function.location(),
-            tail_call.as_ref(),
-            false,
-            cluster,
-            &code.functions,
-            &code.function_calls,
-            &code.types,
-            &code.source_map,
-            breakpoints,
-            effect,
-        ),
+        inner,
     }));
 
     expected_next_function
 }
 
-fn function_call_to_function_name(
+fn function_call_to_named_function(
     function_call: &MemberLocation,
     code: &CompilerOutput,
-) -> Option<String> {
+) -> Option<Index<NamedFunction>> {
     let function_location = code
         .function_calls
         .is_call_to_user_defined_function(function_call)?;
@@ -350,5 +379,5 @@ fn function_call_to_function_name(
         .syntax_tree
         .find_top_level_parent_function(function_location)?;
 
-    Some(function.name.clone())
+    Some(function.index())
 }
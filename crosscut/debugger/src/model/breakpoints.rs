@@ -21,6 +21,10 @@ impl Breakpoints {
         self.durable.remove(instruction)
     }
 
+    pub fn durable(&self) -> impl Iterator<Item = InstructionAddress> + '_ {
+        self.durable.iter().copied()
+    }
+
     pub fn set_ephemeral(&mut self, instruction: InstructionAddress) {
         self.ephemeral.insert(instruction);
     }
@@ -6,7 +6,9 @@ use crosscut_compiler::{
         },
         DependencyCluster, FunctionCalls, Functions, Signature, Types,
     },
+    diagnostics::Diagnostic,
     source_map::SourceMap,
+    CompilerOutput,
 };
 use crosscut_runtime::Effect;
 
@@ -18,6 +20,45 @@ pub struct DebugNamedFunction {
     pub inner: DebugFunction,
 }
 
+impl DebugNamedFunction {
+    /// # Create the named functions of the provided code, none of them active
+    ///
+    /// This is used to display code that has errors, which the runtime isn't
+    /// executing. Since breakpoints refer to the instructions of the code that
+    /// is executing, none are displayed here.
+    pub fn all_inactive(code: &CompilerOutput) -> Vec<Self> {
+        code.syntax_tree
+            .named_functions()
+            .map(|function| {
+                let cluster = code
+                    .dependencies
+                    .find_cluster_by_named_function(&function.index())
+                    .expect("All named functions must be part of a cluster.");
+
+                let mut inner = DebugFunction::new(
+                    function.inner.clone(),
+                    function.location(),
+                    None,
+                    false,
+                    cluster,
+                    &code.functions,
+                    &code.function_calls,
+                    &code.types,
+                    &code.source_map,
+                    &Breakpoints::default(),
+                    None,
+                );
+                inner.mark_diagnostics(&function.module, &code.diagnostics);
+
+                Self {
+                    name: function.name.clone(),
+                    inner,
+                }
+            })
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DebugFunction {
     pub branches: Vec<DebugBranch>,
@@ -72,6 +113,23 @@ impl DebugFunction {
         }
     }
 
+    /// # Attach the provided diagnostics to the members they relate to
+    ///
+    /// Diagnostics are matched to members by their span. Spans are only unique
+    /// within a module, so only the diagnostics from the module that the
+    /// function is defined in are considered.
+    pub fn mark_diagnostics(
+        &mut self,
+        module: &str,
+        diagnostics: &[Diagnostic],
+    ) {
+        for branch in &mut self.branches {
+            for member in &mut branch.body {
+                member.mark_diagnostics(module, diagnostics);
+            }
+        }
+    }
+
     pub fn active_branch(&self) -> anyhow::Result<&DebugBranch> {
         self.branches
             .iter()
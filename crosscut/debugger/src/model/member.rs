@@ -3,8 +3,9 @@ use crosscut_compiler::{
         syntax::{
             Comment, Expression, FunctionLocation, Member, MemberLocation,
         },
-        DependencyCluster, FunctionCalls, Functions, Signature, Types,
+        DependencyCluster, FunctionCalls, Functions, Signature, Span, Types,
     },
+    diagnostics::Diagnostic,
     source_map::SourceMap,
 };
 use crosscut_runtime::Effect;
@@ -70,23 +71,51 @@ impl DebugMember {
         );
         let data = DebugMemberData {
             signature: types.signature_of_expression(&location).cloned(),
+            span: source_map.expression_to_span(&location),
             location,
             state,
             has_durable_breakpoint,
             effect: active_effect,
+            diagnostics: Vec::new(),
         };
 
         Self { kind, data }
     }
+
+    /// # Attach the provided diagnostics to the members they relate to
+    ///
+    /// See [`DebugFunction::mark_diagnostics`].
+    pub fn mark_diagnostics(
+        &mut self,
+        module: &str,
+        diagnostics: &[Diagnostic],
+    ) {
+        if let DebugMemberKind::Function { function } = &mut self.kind {
+            function.mark_diagnostics(module, diagnostics);
+        }
+
+        self.data.diagnostics = diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                diagnostic.module == module
+                    && Some(diagnostic.span) == self.data.span
+            })
+            .map(|diagnostic| diagnostic.message.clone())
+            .collect();
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DebugMemberData {
     pub signature: Option<Signature>,
+    pub span: Option<Span>,
     pub location: MemberLocation,
     pub state: DebugMemberState,
     pub has_durable_breakpoint: bool,
     pub effect: Option<Effect>,
+
+    /// # The messages of the diagnostics that relate to this member
+    pub diagnostics: Vec<String>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -119,6 +148,7 @@ impl DebugMemberState {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DebugMemberKind {
     Comment { lines: Vec<String> },
+    Error { message: String },
     Function { function: DebugFunction },
     Identifier { name: String },
     Value { as_string: String },
@@ -142,6 +172,9 @@ impl DebugMemberKind {
         match member {
             Member::Comment(Comment { lines }) => Self::Comment { lines },
             Member::Expression { expression, .. } => match expression {
+                Expression::FieldAccess { field } => Self::Identifier {
+                    name: format!(".{field}"),
+                },
                 Expression::Identifier { name } => Self::Identifier { name },
                 Expression::LiteralNumber { value } => Self::Value {
                     as_string: value.to_string(),
@@ -163,6 +196,10 @@ impl DebugMemberKind {
 
                     Self::Function { function }
                 }
+                Expression::Record { fields } => Self::Identifier {
+                    name: format!("{{ {} }}", fields.join(", ")),
+                },
+                Expression::Error { message } => Self::Error { message },
             },
         }
     }
@@ -4,6 +4,7 @@ mod breakpoints;
 mod code;
 mod function;
 mod member;
+mod stack_overflow;
 mod state;
 mod user_action;
 
@@ -17,6 +18,7 @@ pub use self::{
     code::DebugCode,
     function::{DebugFunction, DebugNamedFunction},
     member::{DebugMember, DebugMemberData, DebugMemberKind},
-    state::{PersistentState, TransientState},
+    stack_overflow::StackOverflow,
+    state::{PersistentState, TransientState, MEMORY_PAGE_SIZE},
     user_action::UserAction,
 };
@@ -0,0 +1,76 @@
+use std::fmt;
+
+use crosscut_compiler::CompilerOutput;
+use crosscut_runtime::{Effect, InstructionAddress, PushStackFrameError};
+
+/// # A stack overflow, and the recursion that caused it
+///
+/// The runtime only knows that it ran out of stack frames. Which functions
+/// were involved is reconstructed from the source map.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StackOverflow {
+    /// # The function that tried to create the stack frame that overflowed
+    pub function: String,
+
+    /// # The functions that make up the recursion, starting with the earliest
+    ///
+    /// Starts and ends with [`StackOverflow::function`]. Empty, if the
+    /// function isn't on the call stack more than once.
+    pub recursion: Vec<String>,
+}
+
+impl StackOverflow {
+    /// # Detect a stack overflow from the state of a stopped process
+    ///
+    /// Returns `None`, if the effect does not signal a stack overflow.
+    pub fn new(
+        effect: Option<&Effect>,
+        active_instructions: &[InstructionAddress],
+        code: &CompilerOutput,
+    ) -> Option<Self> {
+        let Some(Effect::PushStackFrame {
+            source: PushStackFrameError::Overflow,
+        }) = effect
+        else {
+            return None;
+        };
+
+        let functions = active_instructions
+            .iter()
+            .filter_map(|instruction| {
+                code.source_map.instruction_to_function(instruction)
+            })
+            .collect::<Vec<_>>();
+        let (function, callers) = functions.split_last()?;
+
+        let recursion = callers
+            .iter()
+            .rposition(|caller| caller == function)
+            .map(|start| &functions[start..])
+            .unwrap_or_default();
+
+        Some(Self {
+            function: function.display(&code.syntax_tree).to_string(),
+            recursion: recursion
+                .iter()
+                .map(|function| function.display(&code.syntax_tree).to_string())
+                .collect(),
+        })
+    }
+}
+
+impl fmt::Display for StackOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Stack overflow in {}", self.function)?;
+
+        if !self.recursion.is_empty() {
+            write!(
+                f,
+                ", caused by recursion: {}",
+                self.recursion.join(" -> ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,38 +1,127 @@
+use anyhow::anyhow;
 use crosscut_compiler::{
-    code::syntax::MemberLocation, CompilerOutput, Instructions,
+    code::syntax::MemberLocation, diagnostics::Diagnostic, CompilerOutput,
+    Instructions,
+};
+use crosscut_game_engine::{
+    command::Command, memory::Memory, snapshot::Snapshot,
+};
+use crosscut_protocol::{
+    code::InstructionsAtRuntime, host_state::HostState,
+    updates::UpdateFromHost, wire::DecodeError,
 };
-use crosscut_game_engine::{command::Command, memory::Memory};
-use crosscut_protocol::{host_state::HostState, updates::UpdateFromHost};
 use crosscut_runtime::{Effect, Instruction, Value};
 
 use super::{
-    ActiveFunctions, Breakpoints, DebugCode, DebugMemberKind, UserAction,
+    ActiveFunctions, Breakpoints, DebugCode, DebugMemberKind,
+    DebugNamedFunction, StackOverflow, UserAction,
 };
 
+/// # The number of bytes of memory that are displayed at once
+pub const MEMORY_PAGE_SIZE: usize = 256;
+
 #[derive(Clone, Debug, Default)]
 pub struct PersistentState {
     pub code: DebugCode,
+
+    /// # The latest code, if it has errors and was not sent to the runtime
+    ///
+    /// As long as this is available, the runtime keeps executing the last good
+    /// code from [`PersistentState::code`].
+    pub code_with_errors: Option<CompilerOutput>,
+
     pub breakpoints: Breakpoints,
+    pub instructions_at_runtime: InstructionsAtRuntime,
     pub host_state: Option<HostState>,
     pub memory: Option<Memory>,
+
+    /// # The page of memory that is displayed
+    ///
+    /// See [`MEMORY_PAGE_SIZE`].
+    pub memory_page: usize,
+
+    /// # The state of the game, as of the last time it was saved
+    pub saved_state: Option<Snapshot>,
+
+    /// # The seed of the game engine's random number generator
+    pub seed: Option<u64>,
+
+    /// # The error, if a message used another version of the protocol
+    ///
+    /// Debugger, host, and server can only understand each other, if they were
+    /// built from the same version of Crosscut. Until the user fixes that,
+    /// nothing is going to work.
+    pub protocol_mismatch: Option<DecodeError>,
 }
 
 impl PersistentState {
-    pub fn on_new_code(&mut self, code: CompilerOutput) -> Command {
+    /// # Handle new code from the compiler
+    ///
+    /// Returns the command that updates the code in the runtime, unless the
+    /// new code has errors. In that case, the last good code keeps running,
+    /// and the new code is only kept around to show the errors.
+    ///
+    /// If there is no good code yet, new code is always sent to the runtime,
+    /// errors or not.
+    pub fn on_new_code(&mut self, code: CompilerOutput) -> Option<Command> {
+        if !code.diagnostics.is_empty() && self.code.inner.is_some() {
+            self.code_with_errors = Some(code);
+            return None;
+        }
+
         let instructions = self.apply_breakpoints(&code);
         self.code.inner = Some(code);
-        Command::UpdateCode { instructions }
+        self.code_with_errors = None;
+
+        Some(self.instructions_at_runtime.update(instructions))
+    }
+
+    /// # Access the diagnostics of the latest code
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        self.code_with_errors
+            .as_ref()
+            .or(self.code.inner.as_ref())
+            .map(|code| code.diagnostics.as_slice())
+            .unwrap_or(&[])
     }
 
-    pub fn on_update_from_host(&mut self, update: UpdateFromHost) {
+    /// # Handle an update from the host
+    ///
+    /// Returns a command for the runtime, if the update requires one.
+    pub fn on_update_from_host(
+        &mut self,
+        update: UpdateFromHost,
+    ) -> Option<Command> {
         match update {
             UpdateFromHost::Memory { memory } => {
                 self.memory = Some(memory);
+                self.clamp_memory_page();
+            }
+            UpdateFromHost::ResendCode => {
+                return self.instructions_at_runtime.resend();
+            }
+            UpdateFromHost::Seed { seed } => {
+                self.seed = Some(seed);
+            }
+            UpdateFromHost::Snapshot { snapshot } => {
+                self.saved_state = Some(snapshot);
             }
             UpdateFromHost::State { state } => {
                 self.host_state = Some(state);
             }
         }
+
+        None
+    }
+
+    /// # Handle a message from the host or server that couldn't be decoded
+    ///
+    /// Malformed messages are otherwise ignored, but a protocol version
+    /// mismatch is remembered, so it can be shown to the user.
+    pub fn on_decode_error(&mut self, err: &DecodeError) {
+        if let DecodeError::VersionMismatch { .. } = err {
+            self.protocol_mismatch = Some(err.clone());
+        }
     }
 
     pub fn on_user_action(
@@ -44,26 +133,30 @@ impl PersistentState {
 
         match action {
             UserAction::BreakpointClear { expression } => {
+                self.expect_no_code_with_errors()?;
+
                 let code = self.code.get()?;
                 let address =
                     self.code.expression_to_instruction(&expression)?;
 
                 self.breakpoints.clear_durable(&address);
 
-                commands.push(Command::UpdateCode {
-                    instructions: self.apply_breakpoints(code),
-                });
+                let instructions = self.apply_breakpoints(code);
+                commands
+                    .push(self.instructions_at_runtime.update(instructions));
             }
             UserAction::BreakpointSet { expression } => {
+                self.expect_no_code_with_errors()?;
+
                 let code = self.code.get()?;
                 let address =
                     self.code.expression_to_instruction(&expression)?;
 
                 self.breakpoints.set_durable(address);
 
-                commands.push(Command::UpdateCode {
-                    instructions: self.apply_breakpoints(code),
-                });
+                let instructions = self.apply_breakpoints(code);
+                commands
+                    .push(self.instructions_at_runtime.update(instructions));
             }
             UserAction::Continue => {
                 let origin = &transient
@@ -82,9 +175,55 @@ impl PersistentState {
                     &mut commands,
                 )?;
             }
+            UserAction::LoadState => {
+                let snapshot = self
+                    .saved_state
+                    .clone()
+                    .ok_or_else(|| anyhow!("No state has been saved yet."))?;
+
+                commands.push(Command::RestoreState { snapshot });
+            }
             UserAction::Reset => {
                 commands.push(Command::Reset);
             }
+            UserAction::ReverseContinue => {
+                let code = self.code.get()?;
+
+                // Stop wherever continuing forward would have stopped: At a
+                // durable breakpoint, or at a `brk`.
+                let brk = code
+                    .instructions
+                    .to_runtime_instructions()
+                    .inner
+                    .iter()
+                    .filter_map(|(address, instruction)| {
+                        let Instruction::TriggerEffect {
+                            effect: Effect::Breakpoint,
+                        } = instruction
+                        else {
+                            return None;
+                        };
+
+                        Some(*address)
+                    });
+                let targets = self.breakpoints.durable().chain(brk).collect();
+
+                commands.push(Command::Rewind { targets });
+            }
+            UserAction::SaveState => {
+                commands.push(Command::SaveState);
+            }
+            UserAction::ShowMemoryPage { page } => {
+                self.memory_page = page;
+                self.clamp_memory_page();
+            }
+            UserAction::StepBack => {
+                let code = self.code.get()?;
+                let targets =
+                    code.source_map.expression_entry_points().collect();
+
+                commands.push(Command::Rewind { targets });
+            }
             UserAction::StepIn => {
                 let code = self.code.get()?;
 
@@ -256,19 +395,84 @@ impl PersistentState {
             &self.breakpoints,
             self.host_state.as_ref(),
         );
-        let operands = match &self.host_state {
+        let (operands, bindings, stack_overflow) = match &self.host_state {
             Some(HostState::Stopped {
-                current_operands, ..
-            }) => current_operands.clone(),
-            _ => Vec::new(),
+                effect,
+                active_instructions,
+                current_operands,
+                current_bindings,
+            }) => {
+                // The runtime only knows the slots of the bindings. Their names
+                // come from the source map.
+                let names = match (
+                    self.code.inner.as_ref(),
+                    active_instructions.last(),
+                ) {
+                    (Some(code), Some(instruction)) => {
+                        code.source_map.instruction_to_bindings(instruction)
+                    }
+                    _ => &[],
+                };
+                let bindings = names
+                    .iter()
+                    .cloned()
+                    .zip(current_bindings.iter().copied())
+                    .collect();
+
+                let stack_overflow =
+                    self.code.inner.as_ref().and_then(|code| {
+                        StackOverflow::new(
+                            effect.as_ref(),
+                            active_instructions,
+                            code,
+                        )
+                    });
+
+                (current_operands.clone(), bindings, stack_overflow)
+            }
+            _ => (Vec::new(), Vec::new(), None),
         };
 
+        let code_with_errors = self
+            .code_with_errors
+            .as_ref()
+            .map(DebugNamedFunction::all_inactive);
+
         TransientState {
             active_functions,
+            code_with_errors,
             operands,
+            bindings,
+            stack_overflow,
         }
     }
 
+    /// # Make sure the displayed page of memory exists
+    ///
+    /// If memory isn't available yet, the first page is displayed, once it is.
+    fn clamp_memory_page(&mut self) {
+        let num_pages = self
+            .memory
+            .as_ref()
+            .map_or(0, |memory| memory.inner.len().div_ceil(MEMORY_PAGE_SIZE));
+        self.memory_page = self.memory_page.min(num_pages.saturating_sub(1));
+    }
+
+    /// # Fail, if the displayed code is not the code that is executing
+    ///
+    /// Breakpoints are set by clicking expressions in the displayed code. While
+    /// the latest code has errors, that refers to expressions that the runtime
+    /// isn't executing.
+    fn expect_no_code_with_errors(&self) -> anyhow::Result<()> {
+        if self.code_with_errors.is_some() {
+            return Err(anyhow!(
+                "Can't change breakpoints while the code has errors."
+            ));
+        }
+
+        Ok(())
+    }
+
     fn step_or_continue(
         &mut self,
         origin: &MemberLocation,
@@ -329,7 +533,7 @@ impl PersistentState {
         // should be just that, redundant. It shouldn't lead to a different
         // result.
         commands.extend([
-            Command::UpdateCode { instructions },
+            self.instructions_at_runtime.update(instructions),
             Command::ClearBreakpointAndEvaluateNextInstruction,
         ]);
 
@@ -340,10 +544,9 @@ impl PersistentState {
 
         // And finally, we can provide the latest code to the runtime, then send
         // it on its way.
+        let instructions = self.apply_breakpoints(code);
         commands.extend([
-            Command::UpdateCode {
-                instructions: self.apply_breakpoints(code),
-            },
+            self.instructions_at_runtime.update(instructions),
             Command::ClearBreakpointAndContinue,
         ]);
 
@@ -369,5 +572,17 @@ impl PersistentState {
 #[derive(Clone, Debug)]
 pub struct TransientState {
     pub active_functions: ActiveFunctions,
+
+    /// # The functions of the latest code, if it has errors
+    ///
+    /// See [`PersistentState::code_with_errors`]. This is displayed instead of
+    /// the active functions, with the errors marked where they occur.
+    pub code_with_errors: Option<Vec<DebugNamedFunction>>,
     pub operands: Vec<Value>,
+
+    /// # The names and values of the bindings in the current stack frame
+    pub bindings: Vec<(String, Value)>,
+
+    /// # The stack overflow that stopped the process, if that happened
+    pub stack_overflow: Option<StackOverflow>,
 }
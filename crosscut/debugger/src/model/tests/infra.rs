@@ -7,7 +7,7 @@ use crosscut_compiler::{
 };
 use crosscut_game_engine::{
     command::Command, game_engine::GameEngine, host::GameEngineHost,
-    memory::Memory,
+    input::InputEvent,
 };
 use crosscut_protocol::updates::Updates;
 
@@ -25,9 +25,9 @@ pub fn debugger() -> TestDebugger {
 pub struct TestDebugger {
     current_time: f64,
     queued_commands: Vec<Command>,
-    memory: Memory,
     updates: Updates,
     game_engine: Option<GameEngine>,
+    pixels: Vec<u8>,
     persistent: PersistentState,
     transient: Option<TransientState>,
 }
@@ -38,15 +38,46 @@ impl TestDebugger {
         let output = compiler.compile(source, &GameEngineHost);
 
         let command = self.persistent.on_new_code(output);
-        self.queued_commands.push(command);
+        self.queued_commands.extend(command);
 
         self.update_transient_state();
 
         self
     }
 
+    /// # Replace the game's code, without the debugger knowing about it
+    ///
+    /// This simulates another client, for example a second debugger, updating
+    /// the code that the game runs.
+    pub fn replace_code_in_game(&mut self, source: &str) -> &mut Self {
+        let mut compiler = Compiler::default();
+        let output = compiler.compile(source, &GameEngineHost);
+
+        if let Some(game_engine) = &mut self.game_engine {
+            game_engine.on_command(Command::UpdateCode {
+                instructions: output.instructions,
+            });
+        }
+
+        self
+    }
+
     pub fn run_program(&mut self) -> &mut Self {
         self.game_engine = Some(GameEngine::new());
+        self.pixels = Vec::new();
+
+        self.process_commands();
+        self.process_updates();
+        self.update_transient_state();
+
+        self
+    }
+
+    /// # Provide an input event to the game, then run the next frame
+    pub fn provide_input(&mut self, event: InputEvent) -> &mut Self {
+        if let Some(game_engine) = &mut self.game_engine {
+            game_engine.on_input(event);
+        }
 
         self.process_commands();
         self.process_updates();
@@ -79,18 +110,32 @@ impl TestDebugger {
                 game_engine.on_command(command);
             }
 
-            let mut pixels = [];
-            game_engine.run_until_end_of_frame(self.current_time, &mut pixels);
+            game_engine
+                .run_until_end_of_frame(self.current_time, &mut self.pixels);
             self.current_time += 1.;
         }
     }
 
     fn process_updates(&mut self) {
-        if let Some(game_engine) = &self.game_engine {
-            self.updates
-                .queue_updates(&game_engine.runtime, &self.memory);
+        if let Some(game_engine) = &mut self.game_engine {
+            if let Some(snapshot) = game_engine.take_snapshot() {
+                self.updates.queue_snapshot(snapshot);
+            }
+            if game_engine.take_rejected_patch() {
+                self.updates.queue_rejected_patch();
+            }
+
+            self.updates.queue_updates(
+                &game_engine.runtime,
+                game_engine.memory(),
+                game_engine.seed(),
+            );
             for update in self.updates.take_queued_updates() {
-                self.persistent.on_update_from_host(update);
+                if let Some(command) =
+                    self.persistent.on_update_from_host(update)
+                {
+                    self.queued_commands.push(command);
+                }
             }
         }
     }
@@ -131,6 +176,10 @@ impl TestDebugger {
         expression
     }
 
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
     pub fn persistent_state(&self) -> &PersistentState {
         &self.persistent
     }
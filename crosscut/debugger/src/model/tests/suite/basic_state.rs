@@ -1,6 +1,17 @@
+use crosscut_game_engine::input::{InputEvent, Key};
+use crosscut_protocol::{
+    updates::UpdateFromHost,
+    wire::{self, DecodeError, PROTOCOL_VERSION},
+};
+use crosscut_runtime::Value;
+
 use crate::model::{
-    active_functions::ActiveFunctionsMessage, tests::infra::debugger,
-    ActiveFunctions,
+    active_functions::ActiveFunctionsMessage,
+    tests::infra::{
+        debugger, ActiveFunctionsExt, DebugBranchExt, DebugFunctionExt,
+        FunctionsExt,
+    },
+    ActiveFunctions, PersistentState, UserAction,
 };
 
 #[test]
@@ -32,3 +43,340 @@ fn no_process() {
         }
     );
 }
+
+#[test]
+fn keep_last_good_code_on_errors() {
+    // If the new code has errors, the debugger should keep the last good code
+    // running, but still make the errors available.
+
+    let mut debugger = debugger();
+    debugger.provide_source_code(
+        r"
+            main: fn
+                br size_x, size_y ->
+                    nop
+                end
+            end
+        ",
+    );
+    debugger.provide_source_code(
+        r"
+            main: fn
+                br size_x, size_y ->
+                    nop :
+                end
+            end
+        ",
+    );
+
+    let persistent = debugger.persistent_state();
+    assert!(persistent.code.get().unwrap().diagnostics.is_empty());
+    assert!(persistent.code_with_errors.is_some());
+    assert_eq!(persistent.diagnostics().len(), 1);
+}
+
+#[test]
+fn display_code_with_errors() {
+    // If the new code has errors, the debugger should display that code, with
+    // the errors marked where they occur.
+
+    let mut debugger = debugger();
+    debugger.provide_source_code(
+        r"
+            main: fn
+                br size_x, size_y ->
+                    nop
+                end
+            end
+        ",
+    );
+    debugger.provide_source_code(
+        r"
+            main: fn
+                br size_x, size_y ->
+                    nop
+                    does_not_exist
+                end
+            end
+        ",
+    );
+
+    let functions = debugger.transient_state().code_with_errors.unwrap();
+    let main = functions.with_name("main").only_branch();
+
+    assert!(main.expression(0).data.diagnostics.is_empty());
+    assert_eq!(
+        main.expression(1).data.diagnostics,
+        ["Unresolved identifier `does_not_exist`"],
+    );
+}
+
+#[test]
+fn refuse_to_change_breakpoints_while_code_has_errors() {
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+    debugger.provide_source_code(
+        r"
+            main: fn
+                br size_x, size_y ->
+                    does_not_exist
+                end
+            end
+        ",
+    );
+
+    let expression = debugger
+        .transient_state()
+        .code_with_errors
+        .unwrap()
+        .with_name("main")
+        .only_branch()
+        .expression(0)
+        .data
+        .location;
+    assert!(debugger
+        .on_user_action(UserAction::BreakpointSet { expression })
+        .is_err());
+}
+
+#[test]
+fn display_bindings_of_current_stack_frame() {
+    // The runtime only knows which slots bindings are stored in. The debugger
+    // should still display them by name.
+
+    let transient = debugger()
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program()
+        .transient_state();
+
+    let names = transient
+        .bindings
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["size_x", "size_y"]);
+}
+
+#[test]
+fn stop_process_that_overruns_its_frame() -> anyhow::Result<()> {
+    // A process that doesn't submit its frame, for example because it's stuck
+    // in an infinite loop, should be reported. It should still be possible to
+    // stop it.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        loop
+                    end
+                end
+
+                loop: fn
+                    br ->
+                        loop
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    assert_eq!(
+        debugger.transient_state().active_functions,
+        ActiveFunctions::Message {
+            message: ActiveFunctionsMessage::FrameOverrun
+        }
+    );
+
+    debugger.on_user_action(UserAction::Stop)?;
+    let names = debugger.transient_state().active_functions.names();
+    assert!(names.contains(&String::from("loop")));
+
+    Ok(())
+}
+
+#[test]
+fn restore_saved_state() -> anyhow::Result<()> {
+    // It should be possible to save the state of a process, and to restore it
+    // later, after the process has moved on.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        1
+                        brk
+                        2
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let saved = debugger.transient_state();
+    debugger.on_user_action(UserAction::SaveState)?;
+
+    debugger.on_user_action(UserAction::Continue)?;
+    assert_ne!(debugger.transient_state().operands, saved.operands);
+
+    debugger.on_user_action(UserAction::LoadState)?;
+    assert_eq!(debugger.transient_state().operands, saved.operands);
+    assert_eq!(
+        debugger.transient_state().active_functions,
+        saved.active_functions,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn fail_to_load_state_that_was_never_saved() {
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    assert!(debugger.on_user_action(UserAction::LoadState).is_err());
+}
+
+#[test]
+fn reproduce_random_numbers_after_reset() -> anyhow::Result<()> {
+    // The random numbers that a game receives only depend on the seed. After a
+    // reset, the game should receive the same ones again.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        read_random
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    assert_eq!(debugger.persistent_state().seed, Some(0));
+
+    let before = debugger.transient_state().operands;
+    assert_eq!(before.len(), 1);
+
+    debugger.on_user_action(UserAction::Reset)?;
+    assert_eq!(debugger.transient_state().operands, before);
+
+    Ok(())
+}
+
+#[test]
+fn resend_full_code_if_game_rejects_patch() -> anyhow::Result<()> {
+    // If the game's code has changed in a way the debugger doesn't know about,
+    // the next patch doesn't fit. The game must reject it, and the debugger
+    // must send the full code instead.
+
+    let code = |value: u8| {
+        format!(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        loop
+                    end
+                end
+
+                loop: fn
+                    br ->
+                        {value}
+                        nop # this is where the breakpoint will be set
+                        submit_frame
+                        loop
+                    end
+                end
+            "
+        )
+    };
+
+    let mut debugger = debugger();
+    debugger.provide_source_code(&code(1)).run_program();
+
+    let nop = debugger
+        .expect_code()
+        .function_by_name("loop")
+        .unwrap()
+        .into_located_function()
+        .find_single_branch()
+        .unwrap()
+        .expressions()
+        .nth(1)
+        .unwrap()
+        .location;
+
+    // Setting the breakpoint sends a patch that is based on the code the
+    // debugger knows about. The game rejects it and keeps running its own code.
+    debugger
+        .replace_code_in_game(&code(2))
+        .on_user_action(UserAction::BreakpointSet { expression: nop })?;
+    assert!(debugger.transient_state().operands.is_empty());
+
+    // Meanwhile, the debugger has sent the full code, breakpoint included.
+    debugger.provide_input(InputEvent::KeyDown(Key::Up));
+    assert_eq!(debugger.transient_state().operands[0], Value::from(1u8));
+
+    Ok(())
+}
+
+#[test]
+fn remember_protocol_version_mismatch() {
+    // A host that speaks another version of the protocol can't be debugged.
+    // The user needs to know about that, instead of wondering why nothing
+    // happens.
+
+    let mut state = PersistentState::default();
+
+    let err = UpdateFromHost::deserialize(vec![]).unwrap_err();
+    state.on_decode_error(&err);
+    assert_eq!(state.protocol_mismatch, None);
+
+    let mut update = wire::encode(&UpdateFromHost::ResendCode);
+    update[..2].copy_from_slice(&(PROTOCOL_VERSION + 1).to_le_bytes());
+
+    let err = UpdateFromHost::deserialize(update).unwrap_err();
+    state.on_decode_error(&err);
+    assert_eq!(
+        state.protocol_mismatch,
+        Some(DecodeError::VersionMismatch {
+            expected: PROTOCOL_VERSION,
+            actual: PROTOCOL_VERSION + 1,
+        }),
+    );
+}
@@ -395,3 +395,47 @@ fn instruction_on_call_stack_with_no_associated_expression() {
     //
     // [1] https://github.com/hannobraun/crosscut/issues/53
 }
+
+#[test]
+fn report_recursion_that_caused_stack_overflow() {
+    // If runaway recursion overflows the stack, the debugger should report the
+    // functions that are involved.
+
+    let transient = debugger()
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        f
+                    end
+                end
+
+                f: fn
+                    br ->
+                        g
+                        nop # prevent tail call elimination
+                    end
+                end
+
+                g: fn
+                    br ->
+                        f
+                        nop # prevent tail call elimination
+                    end
+                end
+            ",
+        )
+        .run_program()
+        .transient_state();
+
+    let Some(stack_overflow) = transient.stack_overflow else {
+        panic!("Expected stack overflow to be reported.");
+    };
+
+    let [first, second, third] = stack_overflow.recursion.as_slice() else {
+        panic!("Expected recursion to involve `f` and `g`.");
+    };
+    assert_eq!(first, &stack_overflow.function);
+    assert_eq!(third, &stack_overflow.function);
+    assert_ne!(first, second);
+}
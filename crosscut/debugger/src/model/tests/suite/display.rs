@@ -0,0 +1,184 @@
+use crosscut_game_engine::display::NUM_CHANNELS;
+use crosscut_runtime::Effect;
+
+use crate::model::tests::infra::{
+    debugger, ActiveFunctionsEntriesExt, ActiveFunctionsExt, DebugFunctionExt,
+    FunctionsExt,
+};
+
+#[test]
+fn draw_at_configured_resolution() {
+    // A game can set its own resolution. Any drawing then happens in logical
+    // pixels, which are scaled up to fill the frame buffer.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        2 2 128 set_resolution
+                        0 0 2 1 255 0 0 255 fill_rect
+                        1 1 0 255 0 255 set_pixel
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let pixel = |x: usize, y: usize| {
+        let i = (y * 256 + x) * NUM_CHANNELS;
+        debugger.pixels()[i..i + NUM_CHANNELS].to_vec()
+    };
+
+    assert_eq!(debugger.pixels().len(), 256 * 256 * NUM_CHANNELS);
+    assert_eq!(pixel(0, 0), [255, 0, 0, 255]);
+    assert_eq!(pixel(255, 127), [255, 0, 0, 255]);
+    assert_eq!(pixel(127, 128), [0, 0, 0, 0]);
+    assert_eq!(pixel(128, 128), [0, 255, 0, 255]);
+    assert_eq!(pixel(255, 255), [0, 255, 0, 255]);
+}
+
+#[test]
+fn size_frame_buffer_from_resolution() {
+    // The frame buffer is exactly as large as the resolution requires, and
+    // pixels can be addressed beyond what would fit into a `u8`.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        300 10 2 set_resolution
+                        299 9 0 255 0 255 set_pixel
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let pixel = |x: usize, y: usize| {
+        let i = (y * 600 + x) * NUM_CHANNELS;
+        debugger.pixels()[i..i + NUM_CHANNELS].to_vec()
+    };
+
+    assert_eq!(debugger.pixels().len(), 600 * 20 * NUM_CHANNELS);
+    assert_eq!(pixel(597, 19), [0, 0, 0, 0]);
+    assert_eq!(pixel(598, 18), [0, 255, 0, 255]);
+    assert_eq!(pixel(599, 19), [0, 255, 0, 255]);
+}
+
+#[test]
+fn blit_sprite_from_memory() {
+    // Sprites are read from memory. Transparent pixels don't overwrite what's
+    // already there.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        0 0 32 32 0 0 255 255 fill_rect
+
+                        255 0 store
+                        255 3 store
+
+                        0 0 0 2 1 blit
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let tile = |x: usize| {
+        let i = x * 8 * NUM_CHANNELS;
+        debugger.pixels()[i..i + NUM_CHANNELS].to_vec()
+    };
+
+    assert_eq!(tile(0), [255, 0, 0, 255]);
+    assert_eq!(tile(1), [0, 0, 255, 255]);
+}
+
+#[test]
+fn trigger_effect_when_drawing_out_of_bounds() {
+    // Drawing outside of the resolution should trigger an effect, not crash
+    // the game engine.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        16 16 16 set_resolution
+                        8 8 9 1 255 255 255 255 fill_rect
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let expression = debugger
+        .transient_state()
+        .active_functions
+        .expect_entries()
+        .expect_functions()
+        .expect_leaf("main")
+        .active_expression();
+    assert_eq!(expression.data.effect, Some(Effect::OperandOutOfBounds));
+}
+
+#[test]
+fn trigger_effect_on_resolution_that_does_not_fit() {
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        1024 1024 2 set_resolution
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let expression = debugger
+        .transient_state()
+        .active_functions
+        .expect_entries()
+        .expect_functions()
+        .expect_leaf("main")
+        .active_expression();
+    assert_eq!(expression.data.effect, Some(Effect::OperandOutOfBounds));
+}
+
+#[test]
+fn trigger_effect_on_resolution_with_zero_scale() {
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        32 32 0 set_resolution
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let expression = debugger
+        .transient_state()
+        .active_functions
+        .expect_entries()
+        .expect_functions()
+        .expect_leaf("main")
+        .active_expression();
+    assert_eq!(expression.data.effect, Some(Effect::OperandOutOfBounds));
+}
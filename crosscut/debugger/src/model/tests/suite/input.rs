@@ -0,0 +1,37 @@
+use crosscut_game_engine::input::{InputEvent, Key};
+use crosscut_runtime::Value;
+
+use crate::model::tests::infra::debugger;
+
+#[test]
+fn read_key_presses_and_releases() {
+    // A game can read key presses and releases as events, and also query which
+    // keys are currently held down.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        submit_frame
+                        1 is_key_held
+                        submit_frame
+                        1 is_key_held
+                        read_input
+                        read_input
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program()
+        .provide_input(InputEvent::KeyDown(Key::Up))
+        .provide_input(InputEvent::KeyUp(Key::Up));
+
+    // Operands are listed with the top of the stack first.
+    assert_eq!(
+        debugger.transient_state().operands,
+        [InputEvent::KEY_UP | 1, 1, 0, 1].map(Value::from),
+    );
+}
@@ -0,0 +1,207 @@
+use crosscut_runtime::{Effect, Value};
+
+use crate::model::{
+    tests::infra::{
+        debugger, ActiveFunctionsEntriesExt, ActiveFunctionsExt,
+        DebugFunctionExt, FunctionsExt,
+    },
+    UserAction, MEMORY_PAGE_SIZE,
+};
+
+#[test]
+fn store_and_load_s32() {
+    // Games should be able to store 32-bit values anywhere in memory, not just
+    // within the first 256 bytes.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        -12345
+                        1000
+                        store_s32
+                        1000
+                        load_s32
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    assert_eq!(debugger.transient_state().operands, [Value::from(-12345)]);
+
+    let memory = debugger.persistent_state().memory.clone().unwrap();
+    assert_eq!(memory.inner[1000..1004], (-12345i32).to_le_bytes());
+}
+
+#[test]
+fn store_and_load_u8_beyond_first_256_bytes() {
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        123
+                        1000
+                        store
+                        1000
+                        load
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    assert_eq!(debugger.transient_state().operands, [Value::from(123u8)]);
+
+    let memory = debugger.persistent_state().memory.clone().unwrap();
+    assert_eq!(memory.inner[1000], 123);
+}
+
+#[test]
+fn store_and_load_s16() {
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        -1234
+                        1000
+                        store_s16
+                        1000
+                        load_s16
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    assert_eq!(debugger.transient_state().operands, [Value::from(-1234)]);
+
+    let memory = debugger.persistent_state().memory.clone().unwrap();
+    assert_eq!(memory.inner[1000..1002], (-1234i16).to_le_bytes());
+}
+
+#[test]
+fn trigger_effect_on_s16_access_that_reaches_past_end_of_memory() {
+    // The first byte of the value is within bounds, but the second one is not.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        65535
+                        load_s16
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let expression = debugger
+        .transient_state()
+        .active_functions
+        .expect_entries()
+        .expect_functions()
+        .expect_leaf("main")
+        .active_expression();
+    assert_eq!(expression.data.effect, Some(Effect::OperandOutOfBounds));
+}
+
+#[test]
+fn trigger_effect_on_storing_s16_value_that_does_not_fit() {
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        32768
+                        1000
+                        store_s16
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let expression = debugger
+        .transient_state()
+        .active_functions
+        .expect_entries()
+        .expect_functions()
+        .expect_leaf("main")
+        .active_expression();
+    assert_eq!(expression.data.effect, Some(Effect::OperandOutOfBounds));
+}
+
+#[test]
+fn trigger_effect_on_out_of_bounds_access() {
+    // Accessing memory beyond its end should trigger an effect, not crash the
+    // game engine.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        -1
+                        load_s32
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let expression = debugger
+        .transient_state()
+        .active_functions
+        .expect_entries()
+        .expect_functions()
+        .expect_leaf("main")
+        .active_expression();
+    assert_eq!(expression.data.effect, Some(Effect::OperandOutOfBounds));
+}
+
+#[test]
+fn page_through_memory_up_to_last_page() -> anyhow::Result<()> {
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+    assert_eq!(debugger.persistent_state().memory_page, 0);
+
+    debugger.on_user_action(UserAction::ShowMemoryPage { page: 1 })?;
+    assert_eq!(debugger.persistent_state().memory_page, 1);
+
+    let num_bytes = debugger
+        .persistent_state()
+        .memory
+        .as_ref()
+        .unwrap()
+        .inner
+        .len();
+    let num_pages = num_bytes.div_ceil(MEMORY_PAGE_SIZE);
+    debugger.on_user_action(UserAction::ShowMemoryPage { page: num_pages })?;
+    assert_eq!(debugger.persistent_state().memory_page, num_pages - 1);
+
+    Ok(())
+}
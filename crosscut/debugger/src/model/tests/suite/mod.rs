@@ -1,3 +1,7 @@
 mod basic_state;
 mod breakpoints;
 mod call_stack;
+mod display;
+mod input;
+mod memory;
+mod time_travel;
@@ -0,0 +1,170 @@
+use crosscut_compiler::code::syntax::MemberLocation;
+use crosscut_runtime::Value;
+use itertools::Itertools;
+
+use crate::model::{
+    tests::infra::{
+        debugger, ActiveFunctionsEntriesExt, ActiveFunctionsExt,
+        DebugFunctionExt, FunctionsExt, TestDebugger,
+    },
+    UserAction,
+};
+
+#[test]
+fn step_back_to_previous_expression() -> anyhow::Result<()> {
+    // Stepping back should rewind the process to the expression it evaluated
+    // before the current one.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        brk
+                        nop
+                        nop
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let (brk, nop_a, nop_b) = debugger
+        .expect_code()
+        .function_by_name("main")
+        .unwrap()
+        .into_located_function()
+        .find_single_branch()
+        .unwrap()
+        .expressions()
+        .map(|expression| expression.location)
+        .collect_tuple()
+        .unwrap();
+
+    debugger.on_user_action(UserAction::StepOver)?;
+    debugger.on_user_action(UserAction::StepOver)?;
+    assert_eq!(active_expression(&mut debugger, "main"), nop_b);
+
+    debugger.on_user_action(UserAction::StepBack)?;
+    assert_eq!(active_expression(&mut debugger, "main"), nop_a);
+
+    debugger.on_user_action(UserAction::StepBack)?;
+    assert_eq!(active_expression(&mut debugger, "main"), brk);
+
+    // After stepping back, it should be possible to step forward again.
+    debugger.on_user_action(UserAction::StepOver)?;
+    assert_eq!(active_expression(&mut debugger, "main"), nop_a);
+
+    Ok(())
+}
+
+#[test]
+fn step_back_without_history() -> anyhow::Result<()> {
+    // If there is no previous expression to step back to, the process should
+    // stay where it is.
+
+    let mut debugger = debugger();
+    debugger
+        .provide_source_code(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        brk
+                    end
+                end
+            ",
+        )
+        .run_program();
+
+    let before = debugger.transient_state().active_functions;
+    debugger.on_user_action(UserAction::StepBack)?;
+    assert_eq!(debugger.transient_state().active_functions, before);
+
+    Ok(())
+}
+
+#[test]
+fn reverse_continue_to_breakpoint() -> anyhow::Result<()> {
+    // Continuing in reverse should rewind the process to the last time it
+    // passed a breakpoint.
+
+    let mut debugger = debugger();
+    debugger.provide_source_code(
+        r"
+            main: fn
+                br size_x, size_y ->
+                    1
+                    f
+                    2
+                    f
+                    brk
+                end
+            end
+
+            f: fn
+                br n ->
+                    nop
+                end
+            end
+        ",
+    );
+
+    let nop = debugger
+        .expect_code()
+        .function_by_name("f")
+        .unwrap()
+        .into_located_function()
+        .find_single_branch()
+        .unwrap()
+        .expressions()
+        .next()
+        .unwrap()
+        .location;
+    debugger.on_user_action(UserAction::BreakpointSet { expression: nop })?;
+
+    debugger.run_program();
+    assert_eq!(binding_n(&mut debugger), Value::from(1));
+
+    debugger.on_user_action(UserAction::Continue)?;
+    assert_eq!(binding_n(&mut debugger), Value::from(2));
+
+    debugger.on_user_action(UserAction::Continue)?;
+    assert_eq!(
+        debugger.transient_state().active_functions.names(),
+        ["main"],
+    );
+
+    debugger.on_user_action(UserAction::ReverseContinue)?;
+    assert_eq!(binding_n(&mut debugger), Value::from(2));
+
+    debugger.on_user_action(UserAction::ReverseContinue)?;
+    assert_eq!(binding_n(&mut debugger), Value::from(1));
+
+    Ok(())
+}
+
+fn active_expression(
+    debugger: &mut TestDebugger,
+    function: &str,
+) -> MemberLocation {
+    debugger
+        .transient_state()
+        .active_functions
+        .expect_entries()
+        .expect_functions()
+        .expect_leaf(function)
+        .active_expression()
+        .data
+        .location
+}
+
+fn binding_n(debugger: &mut TestDebugger) -> Value {
+    let bindings = debugger.transient_state().bindings;
+    let [(name, value)] = bindings.as_slice() else {
+        panic!("Expected exactly one binding. Got: {bindings:?}");
+    };
+    assert_eq!(name, "n");
+
+    *value
+}
@@ -5,7 +5,12 @@ pub enum UserAction {
     BreakpointClear { expression: MemberLocation },
     BreakpointSet { expression: MemberLocation },
     Continue,
+    LoadState,
     Reset,
+    ReverseContinue,
+    SaveState,
+    ShowMemoryPage { page: usize },
+    StepBack,
     StepIn,
     StepOut,
     StepOver,
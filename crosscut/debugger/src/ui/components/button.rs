@@ -14,6 +14,7 @@ pub fn Button(
     label: &'static str,
     action: UserAction,
     actions: ActionsTx,
+    #[prop(optional)] disabled: bool,
 ) -> impl IntoView {
     let on_click = move |_| {
         leptos::task::spawn_local(send_action(action.clone(), actions.clone()));
@@ -23,7 +24,8 @@ pub fn Button(
         <input
             type="button"
             value=label
-            class="m-1 px-1 bg-gray-300 font-bold"
+            class="m-1 px-1 bg-gray-300 font-bold disabled:text-gray-500"
+            disabled=disabled
             on:click=on_click />
     }
 }
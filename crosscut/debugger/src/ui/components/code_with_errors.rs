@@ -0,0 +1,41 @@
+use leptos::{
+    component,
+    prelude::{ClassAttribute, CollectView, ElementChild},
+    view, IntoView,
+};
+
+use crate::{
+    model::DebugNamedFunction,
+    ui::{
+        components::{function::NamedFunction, panel::Panel},
+        ActionsTx,
+    },
+};
+
+#[component]
+pub fn CodeWithErrors(
+    functions: Vec<DebugNamedFunction>,
+    actions: ActionsTx,
+) -> impl IntoView {
+    let functions = functions
+        .into_iter()
+        .map(|function| {
+            view! {
+                <NamedFunction
+                    function=function
+                    actions=actions.clone() />
+            }
+        })
+        .collect_view();
+
+    view! {
+        <Panel class="h-80">
+            <p class="italic text-gray-500">
+                "This code has errors and is not running."
+            </p>
+            <ol>
+                {functions}
+            </ol>
+        </Panel>
+    }
+}
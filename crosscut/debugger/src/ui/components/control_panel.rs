@@ -1,4 +1,8 @@
-use leptos::{component, view, IntoView};
+use leptos::{
+    component,
+    prelude::{ClassAttribute, ElementChild},
+    view, IntoView,
+};
 
 use crate::{
     model::UserAction,
@@ -9,7 +13,17 @@ use crate::{
 };
 
 #[component]
-pub fn ControlPanel(actions: ActionsTx) -> impl IntoView {
+pub fn ControlPanel(
+    seed: Option<u64>,
+    has_saved_state: bool,
+    actions: ActionsTx,
+) -> impl IntoView {
+    let seed = seed.map(|seed| {
+        view! {
+            <span class="ml-4">{format!("Seed: {seed}")}</span>
+        }
+    });
+
     view! {
         <Panel class="">
             <Button
@@ -20,10 +34,24 @@ pub fn ControlPanel(actions: ActionsTx) -> impl IntoView {
                 label="Stop"
                 action=UserAction::Stop
                 actions=actions.clone() />
+            <Button
+                label="Reverse Continue"
+                action=UserAction::ReverseContinue
+                actions=actions.clone() />
             <Button
                 label="Continue"
                 action=UserAction::Continue
-                actions=actions />
+                actions=actions.clone() />
+            <Button
+                label="Save state"
+                action=UserAction::SaveState
+                actions=actions.clone() />
+            <Button
+                label="Load state"
+                action=UserAction::LoadState
+                actions=actions
+                disabled=!has_saved_state />
+            {seed}
         </Panel>
     }
 }
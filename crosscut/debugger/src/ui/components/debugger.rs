@@ -1,6 +1,6 @@
 use leptos::{
     component,
-    prelude::{ElementChild, Get, ReadSignal},
+    prelude::{ClassAttribute, ElementChild, Get, IntoAny, ReadSignal},
     view, IntoView,
 };
 
@@ -8,8 +8,10 @@ use crate::{
     model::{PersistentState, TransientState},
     ui::{
         components::{
-            active_functions::ActiveFunctions, control_panel::ControlPanel,
-            memory_explorer::MemoryExplorer, stack_explorer::StackExplorer,
+            active_functions::ActiveFunctions,
+            code_with_errors::CodeWithErrors, control_panel::ControlPanel,
+            diagnostics::Diagnostics, memory_explorer::MemoryExplorer,
+            panel::Panel, stack_explorer::StackExplorer,
         },
         ActionsTx,
     },
@@ -23,24 +25,77 @@ pub fn Debugger(
     move || {
         let (persistent, transient) = state.get();
 
+        let protocol_mismatch =
+            persistent.protocol_mismatch.clone().map(|err| {
+                view! {
+                    <Panel class="">
+                        <p class="text-red-700">
+                            {err.to_string()}
+                        </p>
+                        <p>
+                            "Debugger, game, and server must be built from the \
+                            same version of Crosscut."
+                        </p>
+                    </Panel>
+                }
+            });
+        let diagnostics = persistent.diagnostics().to_vec();
+        let is_running_last_good_code = persistent.code_with_errors.is_some();
+        let diagnostics = (!diagnostics.is_empty()).then(|| {
+            view! {
+                <Diagnostics
+                    diagnostics=diagnostics
+                    is_running_last_good_code=is_running_last_good_code />
+            }
+        });
+        let code = match transient.code_with_errors {
+            Some(functions) => view! {
+                <CodeWithErrors
+                    functions=functions
+                    actions=actions.clone() />
+            }
+            .into_any(),
+            None => view! {
+                <ActiveFunctions
+                    active_functions=transient.active_functions
+                    actions=actions.clone() />
+            }
+            .into_any(),
+        };
         let stack_explorer = view! {
             <StackExplorer
-                current=transient.operands />
+                current=transient.operands
+                bindings=transient.bindings />
         };
+        let stack_overflow = transient.stack_overflow.map(|stack_overflow| {
+            view! {
+                <Panel class="">
+                    <p class="text-red-700">
+                        {stack_overflow.to_string()}
+                    </p>
+                </Panel>
+            }
+        });
+        let memory_page = persistent.memory_page;
         let memory_explorer = persistent.memory.map(|memory| {
             view! {
                 <MemoryExplorer
-                    memory=memory />
+                    memory=memory
+                    page=memory_page
+                    actions=actions.clone() />
             }
         });
 
         view! {
             <div>
                 <ControlPanel
+                    seed=persistent.seed
+                    has_saved_state=persistent.saved_state.is_some()
                     actions=actions.clone() />
-                <ActiveFunctions
-                    active_functions=transient.active_functions
-                    actions=actions.clone() />
+                {protocol_mismatch}
+                {diagnostics}
+                {stack_overflow}
+                {code}
                 {stack_explorer}
                 {memory_explorer}
             </div>
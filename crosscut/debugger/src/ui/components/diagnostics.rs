@@ -0,0 +1,51 @@
+use crosscut_compiler::diagnostics::Diagnostic;
+use leptos::{
+    component,
+    prelude::{ClassAttribute, CollectView, ElementChild},
+    view, IntoView,
+};
+
+use crate::ui::components::panel::Panel;
+
+#[component]
+pub fn Diagnostics(
+    diagnostics: Vec<Diagnostic>,
+    is_running_last_good_code: bool,
+) -> impl IntoView {
+    let note = is_running_last_good_code.then(|| {
+        view! {
+            <p class="italic text-gray-500">
+                "The last code without errors is still running."
+            </p>
+        }
+    });
+
+    let diagnostics = diagnostics
+        .into_iter()
+        .map(|diagnostic| {
+            let location =
+                format!("{}.capi:{}", diagnostic.module, diagnostic.span);
+
+            view! {
+                <li>
+                    <span class="mr-2 text-gray-500">
+                        {location}
+                    </span>
+                    <span class="text-red-700">
+                        {diagnostic.message}
+                    </span>
+                </li>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <Panel class="">
+            <p>"Errors:"</p>
+            {note}
+            <ol>
+                {diagnostics}
+            </ol>
+        </Panel>
+    }
+}
@@ -6,7 +6,7 @@ use leptos::{
     ev::MouseEvent,
     prelude::{
         AnyView, ClassAttribute, CollectView, CustomAttribute, ElementChild,
-        IntoAny, OnAttribute,
+        GlobalAttributes, IntoAny, OnAttribute,
     },
     view,
     wasm_bindgen::JsCast,
@@ -138,6 +138,16 @@ pub fn Member(member: DebugMember, actions: ActionsTx) -> impl IntoView {
                 None,
             )
         }
+        DebugMemberKind::Error { message } => (
+            view! {
+                <span class="px-0.5 bg-red-300">
+                    {message}
+                </span>
+            }
+            .into_any(),
+            None,
+            None,
+        ),
         DebugMemberKind::Function { function } => (
             view! {
                 <Function
@@ -190,6 +200,9 @@ fn make_single_member(
     if data.state.is_active() {
         class_inner.push_str(" font-bold");
     }
+    if !data.diagnostics.is_empty() {
+        class_inner.push_str(" underline decoration-wavy decoration-red-700");
+    }
 
     let data_expression = ron::to_string(&data.location).expect(
         "Expecting serialization of `ExpressionLocation` to always work.",
@@ -200,6 +213,10 @@ fn make_single_member(
         Some(
             view! {
                 <span>
+                    <Button
+                        label="Step Back"
+                        action=UserAction::StepBack
+                        actions=actions_tx.clone() />
                     <Button
                         label="Step In"
                         action=UserAction::StepIn
@@ -220,7 +237,18 @@ fn make_single_member(
         None
     };
 
-    let error = data.effect.map(|effect| format!("{:?}", effect));
+    let error = data.effect.map(|effect| match data.span {
+        Some(span) => format!("{effect:?} (at {span})"),
+        None => format!("{effect:?}"),
+    });
+    let diagnostics = (!data.diagnostics.is_empty()).then(|| {
+        view! {
+            <span class="mx-2 text-red-700">
+                {data.diagnostics.join(" ")}
+            </span>
+        }
+    });
+    let title = data.span.map(|span| format!("Line {span}"));
 
     let toggle_breakpoint = move |event: MouseEvent| {
         let event_target = event.target().unwrap();
@@ -258,6 +286,7 @@ fn make_single_member(
         view! {
             <span
                 class=class_inner
+                title=title
                 data-expression=data_expression
                 data-breakpoint=data_breakpoint
                 on:click=toggle_breakpoint>
@@ -268,8 +297,11 @@ fn make_single_member(
         actions,
         Some(
             view! {
-                <span class="mx-2 font-bold text-red-800">
-                    {error}
+                <span>
+                    <span class="mx-2 font-bold text-red-800">
+                        {error}
+                    </span>
+                    {diagnostics}
                 </span>
             }
             .into_any(),
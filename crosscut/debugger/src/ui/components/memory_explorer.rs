@@ -5,11 +5,30 @@ use leptos::{
     view, IntoView,
 };
 
-use crate::ui::components::panel::Panel;
+use crate::{
+    model::{UserAction, MEMORY_PAGE_SIZE},
+    ui::{
+        components::{button::Button, panel::Panel},
+        ActionsTx,
+    },
+};
 
 #[component]
-pub fn MemoryExplorer(memory: Memory) -> impl IntoView {
-    let mut values = memory.inner.into_iter().peekable();
+pub fn MemoryExplorer(
+    memory: Memory,
+    page: usize,
+    actions: ActionsTx,
+) -> impl IntoView {
+    let num_pages = memory.inner.len().div_ceil(MEMORY_PAGE_SIZE);
+    let start = page * MEMORY_PAGE_SIZE;
+
+    let mut values = memory
+        .inner
+        .into_iter()
+        .enumerate()
+        .skip(start)
+        .take(MEMORY_PAGE_SIZE)
+        .peekable();
     let values = values.by_ref();
 
     let mut lines = Vec::new();
@@ -28,9 +47,30 @@ pub fn MemoryExplorer(memory: Memory) -> impl IntoView {
         })
         .collect_view();
 
+    let previous = (page > 0).then(|| {
+        view! {
+            <Button
+                label="Previous page"
+                action=UserAction::ShowMemoryPage { page: page - 1 }
+                actions=actions.clone() />
+        }
+    });
+    let next = (page + 1 < num_pages).then(|| {
+        view! {
+            <Button
+                label="Next page"
+                action=UserAction::ShowMemoryPage { page: page + 1 }
+                actions=actions />
+        }
+    });
+
     view! {
         <Panel class="">
-            <p>"Memory:"</p>
+            <p>
+                {format!("Memory (page {} of {num_pages}):", page + 1)}
+                {previous}
+                {next}
+            </p>
             <ol>
                 {lines}
             </ol>
@@ -39,10 +79,12 @@ pub fn MemoryExplorer(memory: Memory) -> impl IntoView {
 }
 
 #[component]
-fn Line(line: Vec<u8>) -> impl IntoView {
+fn Line(line: Vec<(usize, u8)>) -> impl IntoView {
+    let address = line.first().map(|(address, _)| *address).unwrap_or(0);
+
     let values = line
         .into_iter()
-        .map(|value| {
+        .map(|(_, value)| {
             view! {
                 <Value value=value />
             }
@@ -51,7 +93,10 @@ fn Line(line: Vec<u8>) -> impl IntoView {
 
     view! {
         <li>
-            <ol>{values}</ol>
+            <span class="inline-block w-16 mr-2 text-gray-500">
+                {format!("{address:#06x}")}
+            </span>
+            <ol class="inline">{values}</ol>
         </li>
     }
 }
@@ -1,7 +1,9 @@
 pub mod active_functions;
 pub mod button;
+pub mod code_with_errors;
 pub mod control_panel;
 pub mod debugger;
+pub mod diagnostics;
 pub mod function;
 pub mod memory_explorer;
 pub mod panel;
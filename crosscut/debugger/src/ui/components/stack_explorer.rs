@@ -9,7 +9,10 @@ use crate::ui::components::panel::Panel;
 
 #[allow(unused_braces)] // working around a warning from the `view!` macro
 #[component]
-pub fn StackExplorer(current: Vec<Value>) -> impl IntoView {
+pub fn StackExplorer(
+    current: Vec<Value>,
+    bindings: Vec<(String, Value)>,
+) -> impl IntoView {
     view! {
         <Panel class="h-32">
             <div>
@@ -17,6 +20,10 @@ pub fn StackExplorer(current: Vec<Value>) -> impl IntoView {
                     "Current data stack:"
                 </p>
                 <Operands operands=current />
+                <p>
+                    "Current bindings:"
+                </p>
+                <Bindings bindings=bindings />
             </div>
         </Panel>
     }
@@ -39,3 +46,21 @@ pub fn Operands(operands: Vec<Value>) -> impl IntoView {
         </ol>
     }
 }
+
+#[component]
+pub fn Bindings(bindings: Vec<(String, Value)>) -> impl IntoView {
+    let bindings = bindings
+        .into_iter()
+        .map(|(name, value)| {
+            view! {
+                <li class="inline-block mr-2">{format!("{name}: {value}")}</li>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <ol>
+            {bindings}
+        </ol>
+    }
+}
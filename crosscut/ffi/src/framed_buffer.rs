@@ -13,17 +13,24 @@ impl<const SIZE: usize> FramedBuffer<SIZE> {
         }
     }
 
-    pub fn write_frame(&mut self, len: usize) -> &mut [u8] {
+    /// # Reserve a frame of the provided length, to write into
+    ///
+    /// Returns `None`, if the frame doesn't fit into the remaining space. That
+    /// space is only reclaimed, once all frames have been read.
+    pub fn write_frame(&mut self, len: usize) -> Option<&mut [u8]> {
         let next_free =
             self.frames.back().copied().unwrap_or_default().ends_before;
 
         let new_frame = BufferFrame {
             starts_at: next_free,
-            ends_before: next_free + len,
+            ends_before: next_free.checked_add(len)?,
         };
+        if new_frame.ends_before > SIZE {
+            return None;
+        }
         self.frames.push_back(new_frame);
 
-        &mut self.buffer[new_frame.starts_at..new_frame.ends_before]
+        Some(&mut self.buffer[new_frame.starts_at..new_frame.ends_before])
     }
 
     pub fn read_frame(&mut self) -> &[u8] {
@@ -43,3 +50,20 @@ struct BufferFrame {
     starts_at: usize,
     ends_before: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FramedBuffer;
+
+    #[test]
+    fn reject_frame_that_does_not_fit() {
+        let mut buffer = FramedBuffer::<4>::new();
+
+        buffer.write_frame(3).unwrap().copy_from_slice(&[1, 2, 3]);
+        assert!(buffer.write_frame(2).is_none());
+        assert!(buffer.write_frame(usize::MAX).is_none());
+
+        assert_eq!(buffer.read_frame(), [1, 2, 3]);
+        assert!(buffer.write_frame(4).is_some());
+    }
+}
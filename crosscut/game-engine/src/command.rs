@@ -1,10 +1,57 @@
-use crosscut_compiler::Instructions;
+use std::collections::BTreeSet;
 
+use crosscut_compiler::{Instructions, InstructionsPatch};
+use crosscut_runtime::InstructionAddress;
+
+use crate::snapshot::Snapshot;
+
+#[allow(clippy::large_enum_variant)] // haven't optimized this yet
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub enum Command {
     ClearBreakpointAndContinue,
     ClearBreakpointAndEvaluateNextInstruction,
+
+    /// # Apply changes to the game's code
+    ///
+    /// This is much smaller than [`Command::UpdateCode`], if only a few
+    /// instructions changed. The patch must have been created from the
+    /// instructions that the game engine currently has.
+    PatchInstructions {
+        patch: InstructionsPatch,
+    },
+
     Reset,
+
+    /// # Restore the game from a snapshot that was taken earlier
+    RestoreState {
+        snapshot: Snapshot,
+    },
+
+    /// # Rewind the game to the last evaluation of one of the targets
+    ///
+    /// The game is stopped there. If none of the targets has been evaluated
+    /// within the game engine's history, nothing happens.
+    Rewind {
+        targets: BTreeSet<InstructionAddress>,
+    },
+
+    /// # Take a snapshot of the game
+    ///
+    /// The game engine keeps the snapshot around, until the code embedding it
+    /// takes it via `GameEngine::take_snapshot`.
+    SaveState,
+
+    /// # Seed the game engine's random number generator
+    ///
+    /// Starting from the same seed, a game receives the same random numbers.
+    /// The generator also starts over from its seed, whenever the game is
+    /// reset.
+    SetSeed {
+        seed: u64,
+    },
+
     Stop,
-    UpdateCode { instructions: Instructions },
+    UpdateCode {
+        instructions: Instructions,
+    },
 }
@@ -1,27 +1,175 @@
-pub const TILES_PER_AXIS: u8 = 32;
+use crosscut_runtime::Effect;
+
+/// # The maximum number of pixels along each axis of the framebuffer
+///
+/// This only exists to keep a game from making the host allocate an absurd
+/// amount of memory.
+pub const MAX_PIXELS_PER_AXIS: u32 = 1024;
 
-pub const PIXELS_PER_TILE_AXIS: usize = 8;
-pub const PIXELS_PER_AXIS: usize =
-    TILES_PER_AXIS as usize * PIXELS_PER_TILE_AXIS;
-pub const NUM_PIXELS: usize = PIXELS_PER_AXIS * PIXELS_PER_AXIS;
 pub const NUM_CHANNELS: usize = 4;
-pub const NUM_PIXEL_BYTES: usize = NUM_PIXELS * NUM_CHANNELS;
-
-pub fn set_pixel(
-    tile_x: usize,
-    tile_y: usize,
-    color: [u8; 4],
-    pixels: &mut [u8],
-) {
-    for offset_y in 0..PIXELS_PER_TILE_AXIS {
-        for offset_x in 0..PIXELS_PER_TILE_AXIS {
-            let pixel_x =
-                (tile_x * PIXELS_PER_TILE_AXIS + offset_x) * NUM_CHANNELS;
-            let pixel_y =
-                (tile_y * PIXELS_PER_TILE_AXIS + offset_y) * NUM_CHANNELS;
-
-            let i = pixel_y * PIXELS_PER_AXIS + pixel_x;
-            pixels[i..i + NUM_CHANNELS].copy_from_slice(&color);
+
+/// # The resolution that a game renders at
+///
+/// A game draws in logical pixels. Each of those covers a square of
+/// `scale` x `scale` pixels in the framebuffer, which is sized to fit the
+/// logical resolution, multiplied by the scale. See [`Resolution::size`].
+///
+/// Drawing anything outside of the logical resolution triggers
+/// [`Effect::OperandOutOfBounds`].
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize,
+)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+    pub scale: u32,
+}
+
+impl Resolution {
+    pub fn new(width: u32, height: u32, scale: u32) -> Result<Self, Effect> {
+        // A scale of zero would make every logical pixel cover nothing.
+        if scale == 0 {
+            return Err(Effect::OperandOutOfBounds);
+        }
+
+        let fits = |size: u32| {
+            size > 0
+                && size
+                    .checked_mul(scale)
+                    .is_some_and(|size| size <= MAX_PIXELS_PER_AXIS)
+        };
+
+        if !fits(width) || !fits(height) {
+            return Err(Effect::OperandOutOfBounds);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            scale,
+        })
+    }
+
+    /// # The size of the framebuffer, in pixels
+    pub fn size(&self) -> [usize; 2] {
+        [self.width, self.height].map(|size| (size * self.scale) as usize)
+    }
+
+    /// # The number of bytes that the framebuffer takes up
+    pub fn num_pixel_bytes(&self) -> usize {
+        let [width, height] = self.size();
+        width * height * NUM_CHANNELS
+    }
+
+    /// # Set a single logical pixel to a color
+    pub fn set_pixel(
+        &self,
+        position: [u32; 2],
+        color: [u8; 4],
+        pixels: &mut [u8],
+    ) -> Result<(), Effect> {
+        self.check_bounds(position, [1, 1])?;
+        self.set_pixel_unchecked(position, color, pixels);
+        Ok(())
+    }
+
+    /// # Fill a rectangle of logical pixels with a color
+    pub fn fill_rect(
+        &self,
+        [x, y]: [u32; 2],
+        [width, height]: [u32; 2],
+        color: [u8; 4],
+        pixels: &mut [u8],
+    ) -> Result<(), Effect> {
+        self.check_bounds([x, y], [width, height])?;
+
+        for logical_y in y..y + height {
+            for logical_x in x..x + width {
+                self.set_pixel_unchecked([logical_x, logical_y], color, pixels);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// # Draw a sprite
+    ///
+    /// The sprite consists of `width` x `height` pixels, row by row, each of
+    /// which has four channels: red, green, blue, and alpha. Pixels whose alpha
+    /// channel is zero are transparent, and don't get drawn.
+    pub fn blit(
+        &self,
+        [x, y]: [u32; 2],
+        [width, height]: [u32; 2],
+        sprite: &[u8],
+        pixels: &mut [u8],
+    ) -> Result<(), Effect> {
+        self.check_bounds([x, y], [width, height])?;
+
+        let sprite_pixels = sprite.chunks_exact(NUM_CHANNELS);
+        let positions =
+            (y..y + height).flat_map(|y| (x..x + width).map(move |x| [x, y]));
+
+        for (position, color) in positions.zip(sprite_pixels) {
+            let [r, g, b, a] = [color[0], color[1], color[2], color[3]];
+
+            if a == 0 {
+                continue;
+            }
+
+            self.set_pixel_unchecked(position, [r, g, b, a], pixels);
+        }
+
+        Ok(())
+    }
+
+    fn check_bounds(
+        &self,
+        [x, y]: [u32; 2],
+        [width, height]: [u32; 2],
+    ) -> Result<(), Effect> {
+        let within = |position: u32, size: u32, limit: u32| {
+            position.checked_add(size).is_some_and(|end| end <= limit)
+        };
+
+        if !within(x, width, self.width) || !within(y, height, self.height) {
+            return Err(Effect::OperandOutOfBounds);
+        }
+
+        Ok(())
+    }
+
+    fn set_pixel_unchecked(
+        &self,
+        [x, y]: [u32; 2],
+        color: [u8; 4],
+        pixels: &mut [u8],
+    ) {
+        let scale = self.scale as usize;
+        let [pixels_per_row, _] = self.size();
+
+        for offset_y in 0..scale {
+            for offset_x in 0..scale {
+                let pixel_x = x as usize * scale + offset_x;
+                let pixel_y = y as usize * scale + offset_y;
+
+                let i = (pixel_y * pixels_per_row + pixel_x) * NUM_CHANNELS;
+                pixels[i..i + NUM_CHANNELS].copy_from_slice(&color);
+            }
+        }
+    }
+}
+
+impl Default for Resolution {
+    /// # The resolution that games render at, unless they set another one
+    ///
+    /// This used to be the only supported resolution, back when the game
+    /// engine only knew how to draw tiles.
+    fn default() -> Self {
+        Self {
+            width: 32,
+            height: 32,
+            scale: 8,
         }
     }
 }
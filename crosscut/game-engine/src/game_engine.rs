@@ -1,33 +1,99 @@
-use std::collections::VecDeque;
+use std::{collections::BTreeSet, mem, sync::Arc};
 
-use crosscut_compiler::Instructions;
-use crosscut_runtime::{Effect, Heap, Runtime, Value};
+use crosscut_compiler::{Instructions, PatchMismatch};
+use crosscut_runtime::{
+    Effect, InstructionAddress, Runtime, StackLimits, Value,
+};
 
 use crate::{
     command::Command,
-    display::{self, TILES_PER_AXIS},
+    display::{Resolution, NUM_CHANNELS},
+    history::{Event, History, Replay, Segment},
     host::GameEngineFunction,
+    input::{Input, InputEvent, Key},
     memory::Memory,
+    random::Random,
+    recording::{RecordedFrame, Recorder, Recording},
+    snapshot::Snapshot,
 };
 
+/// # The number of instructions a game may evaluate per frame
+///
+/// Snake currently needs around 130,000 instructions per frame, so this leaves
+/// plenty of room. It only exists to make sure that a game that doesn't submit
+/// a frame, for example because it's stuck in an infinite loop, doesn't freeze
+/// the host.
+pub const INSTRUCTIONS_PER_FRAME: u64 = 1_000_000;
+
+/// # The configuration of a [`GameEngine`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GameEngineConfig {
+    /// # The size of the game's memory, in bytes
+    pub memory_size: usize,
+
+    /// # The limits that the runtime's stack enforces
+    pub stack_limits: StackLimits,
+}
+
+impl Default for GameEngineConfig {
+    fn default() -> Self {
+        Self {
+            memory_size: Memory::DEFAULT_SIZE,
+            stack_limits: StackLimits::default(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct GameEngine {
     pub runtime: Runtime,
 
     arguments: [Value; 2],
     last_frame_start_s: Option<f64>,
-    instructions: Option<Instructions>,
-    heap: Heap,
+    instructions: Option<Arc<Instructions>>,
     memory: Memory,
-    input: VecDeque<u8>,
-    random: VecDeque<i32>,
+    input: Input,
+    random: Random,
+    resolution: Resolution,
+    snapshot: Option<Snapshot>,
+    rejected_patch: bool,
+    recording: Option<Recorder>,
+    history: History,
+    replay: Option<Replay>,
 }
 
 impl GameEngine {
     pub fn new() -> Self {
-        let arguments = [Value::from(TILES_PER_AXIS); 2];
+        Self::with_config(GameEngineConfig::default())
+    }
+
+    /// # Create a game engine whose memory has the provided size, in bytes
+    pub fn with_memory_size(size: usize) -> Self {
+        Self::with_config(GameEngineConfig {
+            memory_size: size,
+            ..GameEngineConfig::default()
+        })
+    }
 
-        let mut runtime = Runtime::default();
+    /// # Create a game engine whose stack enforces the provided limits
+    pub fn with_stack_limits(limits: StackLimits) -> Self {
+        Self::with_config(GameEngineConfig {
+            stack_limits: limits,
+            ..GameEngineConfig::default()
+        })
+    }
+
+    /// # Create a game engine with the provided configuration
+    pub fn with_config(config: GameEngineConfig) -> Self {
+        let GameEngineConfig {
+            memory_size,
+            stack_limits,
+        } = config;
+
+        let Resolution { width, height, .. } = Resolution::default();
+        let arguments = [Value::from(width), Value::from(height)];
+
+        let mut runtime = Runtime::with_stack_limits(stack_limits);
         runtime.reset(arguments);
 
         Self {
@@ -35,10 +101,15 @@ impl GameEngine {
             arguments,
             last_frame_start_s: None,
             instructions: None,
-            heap: Heap::default(),
-            memory: Memory::default(),
-            input: VecDeque::new(),
-            random: VecDeque::new(),
+            memory: Memory::with_size(memory_size),
+            input: Input::default(),
+            random: Random::default(),
+            resolution: Resolution::default(),
+            snapshot: None,
+            rejected_patch: false,
+            recording: None,
+            history: History::default(),
+            replay: None,
         }
     }
 
@@ -46,13 +117,113 @@ impl GameEngine {
         &self.memory
     }
 
-    pub fn on_input(&mut self, value: u8) {
-        self.input.push_back(value);
+    /// # The seed of the game engine's random number generator
+    ///
+    /// See [`Command::SetSeed`].
+    pub fn seed(&self) -> u64 {
+        self.random.seed()
+    }
+
+    /// # The resolution that the game currently renders at
+    ///
+    /// The framebuffer passed to [`GameEngine::run_frame`] is sized according
+    /// to this. See [`Resolution::size`].
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// # Take a snapshot of the game's current state
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            runtime: self.runtime.clone(),
+            memory: self.memory.clone(),
+            input: self.input.clone(),
+            random: self.random,
+            resolution: self.resolution,
+        }
+    }
+
+    /// # Restore the game's state from a snapshot
+    ///
+    /// The game's code is not part of the snapshot and stays as it is. See
+    /// [`Snapshot`].
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        let Snapshot {
+            runtime,
+            memory,
+            input,
+            random,
+            resolution,
+        } = snapshot;
+
+        self.runtime = runtime;
+        self.memory = memory;
+        self.input = input;
+        self.random = random;
+        self.resolution = resolution;
+    }
+
+    /// # Take the snapshot that was requested via [`Command::SaveState`]
+    pub fn take_snapshot(&mut self) -> Option<Snapshot> {
+        self.snapshot.take()
+    }
+
+    /// # Check whether a patch was rejected since the last call
+    ///
+    /// A patch sent via [`Command::PatchInstructions`] is rejected, if it
+    /// doesn't fit the instructions that the game engine has. The client that
+    /// sent it must then send the full instructions via
+    /// [`Command::UpdateCode`].
+    pub fn take_rejected_patch(&mut self) -> bool {
+        mem::take(&mut self.rejected_patch)
+    }
+
+    /// # Start recording the external inputs that the game consumes
+    ///
+    /// If a recording is already in progress, it is discarded.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Recorder::new(self.snapshot()));
+    }
+
+    /// # Stop recording and return the recording, if one was in progress
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recording.take().map(|recorder| recorder.finish())
+    }
+
+    /// # Replay a frame from a recording
+    ///
+    /// Provides the frame's recorded inputs to the game, then runs the frame.
+    /// If this is done for all frames of a recording, after restoring its
+    /// initial state, the game renders exactly the same frames as when it was
+    /// recorded.
+    pub fn replay_frame(&mut self, frame: RecordedFrame, pixels: &mut Vec<u8>) {
+        for event in frame.input {
+            self.on_input(event);
+        }
+
+        self.run_frame(pixels);
+    }
+
+    pub fn on_input(&mut self, event: InputEvent) {
+        if self.replay.is_none() {
+            self.history.record(Event::Input { event });
+        }
+        if let Some(recording) = &mut self.recording {
+            recording.on_input(event);
+        }
+
+        self.input.on_event(event);
     }
 
     pub fn on_command(&mut self, command: Command) {
+        if self.replay.is_none() && !matches!(command, Command::Rewind { .. }) {
+            self.history.record(Event::Command {
+                command: Box::new(command.clone()),
+            });
+        }
+
         if let Command::Reset = command {
-            self.memory = Memory::default();
+            self.memory.clear();
         }
 
         match command {
@@ -77,18 +248,51 @@ impl GameEngine {
                     // we don't have a good way to do so.
                 }
 
-                if let Some(instructions) = &self.instructions {
-                    self.runtime.evaluate_next_instruction(
-                        instructions.to_runtime_instructions(),
-                        &mut self.heap,
-                    );
+                if self.runtime.state().is_out_of_fuel() {
+                    // The process has been stopped during a frame overrun. We
+                    // still want to be able to step through it.
+                    self.runtime.refuel(Some(1));
+                }
+
+                if self.instructions.is_some() {
+                    self.evaluate_next_instruction();
                 } else {
                     // Same as above: This should only happen if the debugger is
                     // buggy.
                 }
             }
+            Command::PatchInstructions { patch } => {
+                let mut instructions =
+                    self.instructions.clone().unwrap_or_default();
+
+                match Arc::make_mut(&mut instructions).apply(&patch) {
+                    Ok(()) => {
+                        self.instructions = Some(instructions);
+                    }
+                    Err(PatchMismatch) => {
+                        // The client has a different idea of what our
+                        // instructions are. Keep running what we have, until
+                        // it sends the full instructions.
+                        self.rejected_patch = true;
+                    }
+                }
+            }
             Command::Reset => {
                 self.runtime.reset(self.arguments);
+                self.random.reset();
+                self.resolution = Resolution::default();
+            }
+            Command::RestoreState { snapshot } => {
+                self.restore(snapshot);
+            }
+            Command::Rewind { targets } => {
+                self.rewind(targets);
+            }
+            Command::SaveState => {
+                self.snapshot = Some(self.snapshot());
+            }
+            Command::SetSeed { seed } => {
+                self.random = Random::from_seed(seed);
             }
             Command::Stop => {
                 self.runtime
@@ -104,41 +308,15 @@ impl GameEngine {
                     .ignore();
             }
             Command::UpdateCode { instructions } => {
-                self.instructions = Some(instructions);
+                self.instructions = Some(Arc::new(instructions));
             }
         }
     }
 
-    /// # Top off the game engine's random numbers
-    ///
-    /// Whatever code embeds `GameEngine` is expected to call this in a loop
-    /// every frame, until this function returns `false`.
-    ///
-    /// ## Implementation Note
-    ///
-    /// It would make a lot more sense to expect a random seed in the
-    /// constructor, and have a PRNG to get more randomness as needed.
-    ///
-    /// But this works too, and it's fine for now. Ideally, I would like to
-    /// write the PRNG in Crosscut, and it's a bit too early for that. I'm not
-    /// in hurry to replace this with a Rust-based solution right now.
-    pub fn push_random(&mut self, value: i32) -> bool {
-        // If games grow complex enough to need more than this many random
-        // numbers per frame, we can increase this limit. But hopefully, we'll
-        // have a PRNG by then.
-        if self.random.len() >= 1024 {
-            return false;
-        }
-
-        self.random.push_back(value);
-
-        true
-    }
-
     pub fn run_until_end_of_frame(
         &mut self,
         current_time_s: f64,
-        pixels: &mut [u8],
+        pixels: &mut Vec<u8>,
     ) -> bool {
         // For now, we're targeting an unambitious 30 fps.
         let frame_time_s = 1. / 30.;
@@ -175,15 +353,50 @@ impl GameEngine {
             self.last_frame_start_s = Some(current_time_s);
         }
 
+        self.run_frame(pixels);
+
+        true
+    }
+
+    /// # Run the game until it has submitted a frame
+    ///
+    /// Unlike [`GameEngine::run_until_end_of_frame`], this does not check
+    /// whether it is time for another frame. This makes it suitable for
+    /// running a game as fast as possible, like when replaying a recording.
+    ///
+    /// If the framebuffer doesn't match the game's current resolution, it is
+    /// resized and cleared first.
+    pub fn run_frame(&mut self, pixels: &mut Vec<u8>) {
+        if let Some(recording) = &mut self.recording {
+            recording.end_frame();
+        }
+        if self.replay.is_none() {
+            // While the game is stopped, frames don't do anything. Starting new
+            // segments then would only push the interesting ones out of the
+            // history.
+            if self.runtime.effect().inspect().is_none() {
+                self.history
+                    .start_segment(self.snapshot(), self.instructions.clone());
+            }
+
+            self.history.record(Event::Frame);
+        }
+
+        // If the game doesn't submit a frame within its budget, we need to get
+        // control back, or the host would freeze. The game keeps running next
+        // frame, but until then, the runtime reports a frame overrun.
+        self.runtime.refuel(Some(INSTRUCTIONS_PER_FRAME));
+
+        if pixels.len() != self.resolution.num_pixel_bytes() {
+            clear_pixels(self.resolution, pixels);
+        }
+
         while self.runtime.state().is_running() {
-            let Some(instructions) = &self.instructions else {
-                return true;
-            };
+            if self.instructions.is_none() {
+                return;
+            }
 
-            self.runtime.evaluate_next_instruction(
-                instructions.to_runtime_instructions(),
-                &mut self.heap,
-            );
+            self.evaluate_next_instruction();
 
             if let Some(effect) = self.runtime.effect_mut().handle() {
                 match self.handle_effect(&effect, pixels) {
@@ -221,14 +434,148 @@ impl GameEngine {
                 }
             }
         }
+    }
 
-        true
+    /// # Rewind the game to the last evaluation of one of the targets
+    ///
+    /// Searches the history for the most recent point at which the game was
+    /// about to evaluate one of the target instructions. Restores the state of
+    /// the game to that point, and stops it there.
+    ///
+    /// If there is no such point in the history, the game stays as it is.
+    ///
+    /// ## Implementation Note
+    ///
+    /// The code is not rewound. The game keeps running the latest code, which
+    /// might differ from what it was running back then. Usually, the only
+    /// difference is the breakpoints that the debugger has set since.
+    fn rewind(&mut self, targets: BTreeSet<InstructionAddress>) {
+        let current = self.snapshot();
+        let instructions = self.instructions.clone();
+
+        // Replaying must not affect anything but the game itself.
+        let mut history = mem::take(&mut self.history);
+        let recording = self.recording.take();
+        let snapshot = self.snapshot.take();
+
+        let target = history.segments().iter().enumerate().rev().find_map(
+            |(index, segment)| {
+                let (replay, _) = self.replay_segment(
+                    segment,
+                    Replay::new(segment.end, targets.clone()),
+                );
+                replay.last_target.map(|step| (index, step))
+            },
+        );
+
+        if let Some((index, step)) = target {
+            let (_, num_events) = self.replay_segment(
+                &history.segments()[index],
+                Replay::new(Some(step), BTreeSet::new()),
+            );
+
+            // From here on, the game continues on a new timeline.
+            history.truncate(index, num_events, step);
+            history.start_segment(self.snapshot(), instructions.clone());
+        } else {
+            self.restore(current);
+        }
+
+        self.instructions = instructions;
+        self.history = history;
+        self.recording = recording;
+        self.snapshot = snapshot;
+    }
+
+    /// # Replay a segment of the history
+    ///
+    /// Returns the finished replay, and the number of events that were
+    /// replayed before it stopped.
+    fn replay_segment(
+        &mut self,
+        segment: &Segment,
+        replay: Replay,
+    ) -> (Replay, usize) {
+        self.restore(segment.start.clone());
+        self.instructions = segment.instructions.clone();
+        self.replay = Some(replay);
+
+        let mut pixels = Vec::new();
+        let mut num_events = 0;
+
+        for event in &segment.events {
+            if self
+                .replay
+                .as_ref()
+                .is_some_and(|replay| replay.has_stopped)
+            {
+                break;
+            }
+
+            num_events += 1;
+
+            match event {
+                Event::Command { command } => {
+                    self.on_command(*command.clone());
+                }
+                Event::Frame => {
+                    self.run_frame(&mut pixels);
+                }
+                Event::Input { event } => {
+                    self.on_input(*event);
+                }
+            }
+        }
+
+        let replay = self
+            .replay
+            .take()
+            .expect("Replay was set above and can't have been removed.");
+
+        (replay, num_events)
+    }
+
+    fn evaluate_next_instruction(&mut self) {
+        let Some(instructions) = &self.instructions else {
+            return;
+        };
+        if !self.runtime.state().is_running() {
+            return;
+        }
+
+        let address = self.runtime.evaluator().next_instruction;
+
+        if let Some(replay) = &mut self.replay {
+            if replay.stop_at == Some(replay.step) {
+                self.runtime
+                    .effect_mut()
+                    .trigger(Effect::Breakpoint)
+                    // The runtime is running, so there can't be an effect.
+                    .assert_triggered();
+                replay.has_stopped = true;
+                return;
+            }
+        }
+
+        self.runtime
+            .evaluate_next_instruction(instructions.to_runtime_instructions());
+
+        if let Some(replay) = &mut self.replay {
+            let hit_breakpoint =
+                self.runtime.effect().inspect() == Some(&Effect::Breakpoint);
+
+            if replay.targets.contains(&address) && !hit_breakpoint {
+                replay.last_target = Some(replay.step);
+            }
+
+            replay.step += 1;
+        }
     }
 
     fn handle_effect(
         &mut self,
         effect: &Effect,
-        pixels: &mut [u8],
+        pixels: &mut Vec<u8>,
     ) -> Result<EffectOutcome, Effect> {
         let host_effect = match effect {
             Effect::Host => {
@@ -254,31 +601,90 @@ impl GameEngine {
             GameEngineFunction::Load => {
                 let address = self.runtime.stack_mut().pop_operand()?;
 
-                let address = address.to_u8()?;
+                let address = usize::try_from(address.to_i32())?;
 
-                let address: usize = address.into();
-                let value = self.memory.inner[address];
+                let value = self.memory.load_u8(address)?;
 
-                self.runtime.stack_mut().push_operand(value);
+                self.runtime.stack_mut().push_operand(value)?;
             }
             GameEngineFunction::Store => {
                 let address = self.runtime.stack_mut().pop_operand()?;
                 let value = self.runtime.stack_mut().pop_operand()?;
 
-                let address = address.to_u8()?;
+                let address = usize::try_from(address.to_i32())?;
                 let value = value.to_u8()?;
 
-                let address: usize = address.into();
-                self.memory.inner[address] = value;
+                self.memory.store_u8(address, value)?;
+            }
+            GameEngineFunction::LoadS16 => {
+                let address = self.runtime.stack_mut().pop_operand()?;
+
+                let address = usize::try_from(address.to_i32())?;
+
+                let value = self.memory.load_s16(address)?;
+
+                self.runtime.stack_mut().push_operand(i32::from(value))?;
+            }
+            GameEngineFunction::StoreS16 => {
+                let address = self.runtime.stack_mut().pop_operand()?;
+                let value = self.runtime.stack_mut().pop_operand()?;
+
+                let address = usize::try_from(address.to_i32())?;
+                let value = i16::try_from(value.to_i32())?;
+
+                self.memory.store_s16(address, value)?;
+            }
+            GameEngineFunction::LoadS32 => {
+                let address = self.runtime.stack_mut().pop_operand()?;
+
+                let address = usize::try_from(address.to_i32())?;
+
+                let value = self.memory.load_s32(address)?;
+
+                self.runtime.stack_mut().push_operand(value)?;
+            }
+            GameEngineFunction::StoreS32 => {
+                let address = self.runtime.stack_mut().pop_operand()?;
+                let value = self.runtime.stack_mut().pop_operand()?;
+
+                let address = usize::try_from(address.to_i32())?;
+                let value = value.to_i32();
+
+                self.memory.store_s32(address, value)?;
             }
             GameEngineFunction::ReadInput => {
-                let input = self.input.pop_front().unwrap_or(0);
-                self.runtime.stack_mut().push_operand(input);
+                let input = self
+                    .input
+                    .next_event()
+                    .map(|event| event.encode())
+                    .unwrap_or(0);
+                self.runtime.stack_mut().push_operand(input)?;
+            }
+            GameEngineFunction::IsKeyHeld => {
+                let key = self.runtime.stack_mut().pop_operand()?;
+
+                let key = Key::try_from(key.to_u8()?)
+                    .map_err(|_| Effect::OperandOutOfBounds)?;
+
+                let is_held = self.input.is_held(key);
+
+                self.runtime.stack_mut().push_operand(u8::from(is_held))?;
             }
             GameEngineFunction::ReadRandom => {
-                // See `GameEngine::push_random` for context.
-                let random = self.random.pop_front().unwrap();
-                self.runtime.stack_mut().push_operand(random);
+                let random = self.random.next_i32();
+                self.runtime.stack_mut().push_operand(random)?;
+            }
+            GameEngineFunction::SetResolution => {
+                let scale = self.runtime.stack_mut().pop_operand()?;
+                let height = self.runtime.stack_mut().pop_operand()?;
+                let width = self.runtime.stack_mut().pop_operand()?;
+
+                let width = u32::try_from(width.to_i32())?;
+                let height = u32::try_from(height.to_i32())?;
+                let scale = u32::try_from(scale.to_i32())?;
+
+                self.resolution = Resolution::new(width, height, scale)?;
+                clear_pixels(self.resolution, pixels);
             }
             GameEngineFunction::SetPixel => {
                 let a = self.runtime.stack_mut().pop_operand()?;
@@ -288,21 +694,66 @@ impl GameEngine {
                 let y = self.runtime.stack_mut().pop_operand()?;
                 let x = self.runtime.stack_mut().pop_operand()?;
 
-                let x = x.to_u8()?;
-                let y = y.to_u8()?;
+                let x = u32::try_from(x.to_i32())?;
+                let y = u32::try_from(y.to_i32())?;
                 let r = r.to_u8()?;
                 let g = g.to_u8()?;
                 let b = b.to_u8()?;
                 let a = a.to_u8()?;
 
-                if x >= TILES_PER_AXIS {
-                    return Err(Effect::OperandOutOfBounds);
-                }
-                if y >= TILES_PER_AXIS {
-                    return Err(Effect::OperandOutOfBounds);
-                }
+                self.resolution.set_pixel([x, y], [r, g, b, a], pixels)?;
+            }
+            GameEngineFunction::FillRect => {
+                let a = self.runtime.stack_mut().pop_operand()?;
+                let b = self.runtime.stack_mut().pop_operand()?;
+                let g = self.runtime.stack_mut().pop_operand()?;
+                let r = self.runtime.stack_mut().pop_operand()?;
+                let height = self.runtime.stack_mut().pop_operand()?;
+                let width = self.runtime.stack_mut().pop_operand()?;
+                let y = self.runtime.stack_mut().pop_operand()?;
+                let x = self.runtime.stack_mut().pop_operand()?;
+
+                let x = u32::try_from(x.to_i32())?;
+                let y = u32::try_from(y.to_i32())?;
+                let width = u32::try_from(width.to_i32())?;
+                let height = u32::try_from(height.to_i32())?;
+                let r = r.to_u8()?;
+                let g = g.to_u8()?;
+                let b = b.to_u8()?;
+                let a = a.to_u8()?;
+
+                self.resolution.fill_rect(
+                    [x, y],
+                    [width, height],
+                    [r, g, b, a],
+                    pixels,
+                )?;
+            }
+            GameEngineFunction::Blit => {
+                let height = self.runtime.stack_mut().pop_operand()?;
+                let width = self.runtime.stack_mut().pop_operand()?;
+                let y = self.runtime.stack_mut().pop_operand()?;
+                let x = self.runtime.stack_mut().pop_operand()?;
+                let address = self.runtime.stack_mut().pop_operand()?;
 
-                display::set_pixel(x.into(), y.into(), [r, g, b, a], pixels);
+                let address = usize::try_from(address.to_i32())?;
+                let x = u32::try_from(x.to_i32())?;
+                let y = u32::try_from(y.to_i32())?;
+                let width = u32::try_from(width.to_i32())?;
+                let height = u32::try_from(height.to_i32())?;
+
+                let len = usize::try_from(width)?
+                    .checked_mul(usize::try_from(height)?)
+                    .and_then(|len| len.checked_mul(NUM_CHANNELS))
+                    .ok_or(Effect::OperandOutOfBounds)?;
+                let sprite = self.memory.load_bytes(address, len)?;
+
+                self.resolution.blit(
+                    [x, y],
+                    [width, height],
+                    sprite,
+                    pixels,
+                )?;
             }
         }
 
@@ -316,8 +767,37 @@ impl Default for GameEngine {
     }
 }
 
+/// # Replace the framebuffer with a blank one that fits the resolution
+fn clear_pixels(resolution: Resolution, pixels: &mut Vec<u8>) {
+    pixels.clear();
+    pixels.resize(resolution.num_pixel_bytes(), 0);
+}
+
 enum EffectOutcome {
     Handled,
     WasSubmit,
     Unhandled,
 }
+
+#[cfg(test)]
+mod tests {
+    use crosscut_runtime::StackLimits;
+
+    use crate::command::Command;
+
+    use super::GameEngine;
+
+    #[test]
+    fn keep_stack_limits_across_reset() {
+        let limits = StackLimits {
+            frames: 8,
+            operands: 16,
+        };
+
+        let mut game_engine = GameEngine::with_stack_limits(limits);
+        assert_eq!(game_engine.runtime.stack().limits(), limits);
+
+        game_engine.on_command(Command::Reset);
+        assert_eq!(game_engine.runtime.stack().limits(), limits);
+    }
+}
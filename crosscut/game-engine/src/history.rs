@@ -0,0 +1,145 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    sync::Arc,
+};
+
+use crosscut_compiler::Instructions;
+use crosscut_runtime::InstructionAddress;
+
+use crate::{command::Command, input::InputEvent, snapshot::Snapshot};
+
+/// # The number of segments that the history keeps
+///
+/// A new segment starts with every frame, so this keeps the last two seconds
+/// of gameplay, at 30 frames per second.
+pub const HISTORY_LENGTH: usize = 60;
+
+/// # The recent history of a game
+///
+/// Keeping a snapshot for every evaluated instruction would be prohibitively
+/// expensive. Instead, the history consists of segments. Each starts with a
+/// snapshot and records everything that was provided to the game engine from
+/// there on.
+///
+/// Since a game is deterministic, this is enough to reconstruct the state of
+/// the game at any instruction within a segment, by restoring its snapshot and
+/// replaying it.
+#[derive(Debug, Default)]
+pub struct History {
+    segments: VecDeque<Segment>,
+}
+
+impl History {
+    /// # Start a new segment from the provided state
+    ///
+    /// Discards the oldest segment, if the history is full.
+    pub fn start_segment(
+        &mut self,
+        start: Snapshot,
+        instructions: Option<Arc<Instructions>>,
+    ) {
+        if self.segments.len() >= HISTORY_LENGTH {
+            self.segments.pop_front();
+        }
+
+        self.segments.push_back(Segment {
+            start,
+            instructions,
+            events: Vec::new(),
+            end: None,
+        });
+    }
+
+    /// # Record an event in the current segment
+    ///
+    /// Does nothing, if no segment has been started yet. Without a snapshot to
+    /// start from, the event could not be replayed anyway.
+    pub fn record(&mut self, event: Event) {
+        if let Some(segment) = self.segments.back_mut() {
+            segment.events.push(event);
+        }
+    }
+
+    pub fn segments(&self) -> &VecDeque<Segment> {
+        &self.segments
+    }
+
+    /// # Discard everything after the provided point in the history
+    ///
+    /// Segments after the one at `index` are discarded. The one at `index`
+    /// ends after the provided number of events, the last of which is only
+    /// replayed up to the provided step.
+    pub fn truncate(&mut self, index: usize, num_events: usize, step: u64) {
+        self.segments.truncate(index + 1);
+
+        if let Some(segment) = self.segments.get_mut(index) {
+            segment.events.truncate(num_events);
+            segment.end = Some(step);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Segment {
+    /// # The state of the game when the segment started
+    pub start: Snapshot,
+
+    /// # The code that the game was running when the segment started
+    pub instructions: Option<Arc<Instructions>>,
+
+    /// # Everything that was provided to the game engine during the segment
+    pub events: Vec<Event>,
+
+    /// # The step at which the segment ends, if it doesn't end with an event
+    ///
+    /// This is set, if the game has been rewound to a point within the
+    /// segment. See [`Replay`].
+    pub end: Option<u64>,
+}
+
+#[derive(Debug)]
+pub enum Event {
+    Command { command: Box<Command> },
+    Frame,
+    Input { event: InputEvent },
+}
+
+/// # The replay of a segment
+///
+/// Counts the steps of the replay, each of which is an attempt to evaluate an
+/// instruction. Steps are counted from the start of the segment.
+#[derive(Debug)]
+pub struct Replay {
+    /// # The number of steps that have been taken so far
+    pub step: u64,
+
+    /// # The step before which the replay stops
+    pub stop_at: Option<u64>,
+
+    /// # The instructions that the replay looks for
+    pub targets: BTreeSet<InstructionAddress>,
+
+    /// # The last step that evaluated one of the targets
+    ///
+    /// A step that merely triggered a breakpoint at a target doesn't count.
+    /// That would not make any progress.
+    pub last_target: Option<u64>,
+
+    /// # Indicates whether the replay has stopped before its end
+    pub has_stopped: bool,
+}
+
+impl Replay {
+    pub fn new(
+        stop_at: Option<u64>,
+        targets: BTreeSet<InstructionAddress>,
+    ) -> Self {
+        Self {
+            step: 0,
+            stop_at,
+            targets,
+            last_target: None,
+            has_stopped: false,
+        }
+    }
+}
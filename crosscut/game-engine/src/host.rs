@@ -11,9 +11,17 @@ impl Host for GameEngineHost {
             &Halt,
             &Load,
             &Store,
+            &LoadS16,
+            &StoreS16,
+            &LoadS32,
+            &StoreS32,
             &ReadInput,
+            &IsKeyHeld,
             &ReadRandom,
+            &SetResolution,
             &SetPixel,
+            &FillRect,
+            &Blit,
             &SubmitFrame,
         ]
         .map(|function| function.function())
@@ -60,41 +68,134 @@ pub enum GameEngineFunction {
     /// none
     Halt,
 
-    /// # Load a value from a given memory address
+    /// # Load a byte from a given memory address
+    ///
+    /// If the address is out of bounds, an effect is triggered.
     ///
     /// ## Input
     ///
-    /// - `u8`: The address of the value to read.
+    /// - `s32`: The address of the value to read.
     ///
     /// ## Output
     ///
-    /// - `u8`: The value at the provided address address.
+    /// - `u8`: The value at the provided address.
     Load,
 
-    /// # Store a value at the given memory address
+    /// # Store a byte at the given memory address
+    ///
+    /// If the address is out of bounds, an effect is triggered.
     ///
     /// ## Input
     ///
     /// - `u8`: The value to store.
-    /// - `u8`: The address to store the value at.
+    /// - `s32`: The address to store the value at.
     ///
     /// ## Output
     ///
     /// none
     Store,
 
+    /// # Load a 16-bit value from a given memory address
+    ///
+    /// The value occupies the two bytes starting at the address, in
+    /// little-endian byte order. If any of them is out of bounds, an effect is
+    /// triggered.
+    ///
+    /// There is no 16-bit type, so the value is sign-extended to an `s32`.
+    ///
+    /// ## Input
+    ///
+    /// - `s32`: The address of the value to read.
+    ///
+    /// ## Output
+    ///
+    /// - `s32`: The value at the provided address.
+    LoadS16,
+
+    /// # Store a 16-bit value at the given memory address
+    ///
+    /// The value occupies the two bytes starting at the address, in
+    /// little-endian byte order. If any of them is out of bounds, or if the
+    /// value doesn't fit into 16 bits, an effect is triggered.
+    ///
+    /// ## Input
+    ///
+    /// - `s32`: The value to store.
+    /// - `s32`: The address to store the value at.
+    ///
+    /// ## Output
+    ///
+    /// none
+    StoreS16,
+
+    /// # Load a 32-bit value from a given memory address
+    ///
+    /// The value occupies the four bytes starting at the address, in
+    /// little-endian byte order. If any of them is out of bounds, an effect is
+    /// triggered.
+    ///
+    /// ## Input
+    ///
+    /// - `s32`: The address of the value to read.
+    ///
+    /// ## Output
+    ///
+    /// - `s32`: The value at the provided address.
+    LoadS32,
+
+    /// # Store a 32-bit value at the given memory address
+    ///
+    /// The value occupies the four bytes starting at the address, in
+    /// little-endian byte order. If any of them is out of bounds, an effect is
+    /// triggered.
+    ///
+    /// ## Input
+    ///
+    /// - `s32`: The value to store.
+    /// - `s32`: The address to store the value at.
+    ///
+    /// ## Output
+    ///
+    /// none
+    StoreS32,
+
     /// # Read the next input event from the buffer
     ///
+    /// A key press is represented by the code of the key that was pressed. A
+    /// key release is represented by the code of the key, plus `128`. See
+    /// [`Key`] for the available key codes.
+    ///
     /// ## Input
     ///
     /// none
     ///
     /// ## Output
     ///
-    /// - `u8`: A value representing the type of input event.
+    /// - `u8`: The input event, or `0`, if no event is available.
+    ///
+    /// [`Key`]: crate::input::Key
     ReadInput,
 
-    /// # Read a random value from the buffer
+    /// # Check whether a key is currently held down
+    ///
+    /// If the provided value is not a valid key code, an effect is triggered.
+    /// See [`Key`] for the available key codes.
+    ///
+    /// ## Input
+    ///
+    /// - `u8`: The code of the key.
+    ///
+    /// ## Output
+    ///
+    /// - `u8`: `1`, if the key is held down; `0` otherwise.
+    ///
+    /// [`Key`]: crate::input::Key
+    IsKeyHeld,
+
+    /// # Read a random value
+    ///
+    /// The value comes from the game engine's random number generator. See
+    /// `Command::SetSeed`.
     ///
     /// ## Input
     ///
@@ -105,12 +206,35 @@ pub enum GameEngineFunction {
     /// - `s32`: The random value.
     ReadRandom,
 
+    /// # Set the resolution that the game renders at
+    ///
+    /// Each logical pixel covers a square of `scale` x `scale` pixels in the
+    /// frame buffer, which is resized to fit. Width and height, multiplied by
+    /// the scale, must not exceed 1024. Otherwise, an effect is triggered.
+    ///
+    /// This clears the frame buffer. Until a game calls this, it renders at a
+    /// resolution of 32x32, with a scale of 8.
+    ///
+    /// ## Input
+    ///
+    /// - `s32`: The width, in logical pixels.
+    /// - `s32`: The height, in logical pixels.
+    /// - `s32`: The scale.
+    ///
+    /// ## Output
+    ///
+    /// none
+    SetResolution,
+
     /// # Set a pixel in the frame buffer
     ///
+    /// The coordinates are in logical pixels. See
+    /// [`GameEngineFunction::SetResolution`].
+    ///
     /// ## Input
     ///
-    /// - `u8`: The x-coordinate of the pixel.
-    /// - `u8`: The y-coordinate of the pixel.
+    /// - `s32`: The x-coordinate of the pixel.
+    /// - `s32`: The y-coordinate of the pixel.
     /// - `u8`: The red channel value of the pixel.
     /// - `u8`: The green channel value of the pixel.
     /// - `u8`: The blue channel value of the pixel.
@@ -121,6 +245,48 @@ pub enum GameEngineFunction {
     /// none
     SetPixel,
 
+    /// # Fill a rectangle in the frame buffer with a color
+    ///
+    /// The coordinates are in logical pixels. If the rectangle doesn't fit
+    /// within the resolution, an effect is triggered.
+    ///
+    /// ## Input
+    ///
+    /// - `s32`: The x-coordinate of the rectangle's top-left corner.
+    /// - `s32`: The y-coordinate of the rectangle's top-left corner.
+    /// - `s32`: The width of the rectangle.
+    /// - `s32`: The height of the rectangle.
+    /// - `u8`: The red channel value of the color.
+    /// - `u8`: The green channel value of the color.
+    /// - `u8`: The blue channel value of the color.
+    /// - `u8`: The alpha channel value of the color.
+    ///
+    /// ## Output
+    ///
+    /// none
+    FillRect,
+
+    /// # Copy a sprite from memory into the frame buffer
+    ///
+    /// The sprite is stored row by row, with four bytes per pixel: red, green,
+    /// blue, and alpha. Pixels with an alpha value of zero are transparent.
+    ///
+    /// If the sprite doesn't fit within the resolution, or reaches beyond the
+    /// end of memory, an effect is triggered.
+    ///
+    /// ## Input
+    ///
+    /// - `s32`: The memory address of the sprite.
+    /// - `s32`: The x-coordinate to draw the sprite's top-left corner at.
+    /// - `s32`: The y-coordinate to draw the sprite's top-left corner at.
+    /// - `s32`: The width of the sprite.
+    /// - `s32`: The height of the sprite.
+    ///
+    /// ## Output
+    ///
+    /// none
+    Blit,
+
     /// # Submit the current frame, causing the game engine to display it
     ///
     /// This must be called regularly, or the game engine will freeze.
@@ -150,21 +316,35 @@ impl GameEngineFunction {
             Self::Halt => "halt",
             Self::Load => "load",
             Self::Store => "store",
+            Self::LoadS16 => "load_s16",
+            Self::StoreS16 => "store_s16",
+            Self::LoadS32 => "load_s32",
+            Self::StoreS32 => "store_s32",
             Self::ReadInput => "read_input",
+            Self::IsKeyHeld => "is_key_held",
             Self::ReadRandom => "read_random",
+            Self::SetResolution => "set_resolution",
             Self::SetPixel => "set_pixel",
+            Self::FillRect => "fill_rect",
+            Self::Blit => "blit",
             Self::SubmitFrame => "submit_frame",
         };
         let number = (*self).into();
         let signature = match self {
             Self::Halt => ([], []).into(),
-            Self::Load => ([Number], [Number]).into(),
-            Self::Store => ([Number, Number], []).into(),
-            Self::ReadInput => ([], [Number]).into(),
-            Self::ReadRandom => ([], [Number]).into(),
-            Self::SetPixel => {
-                ([Number, Number, Number, Number, Number, Number], []).into()
-            }
+            Self::Load => ([S32], [U8]).into(),
+            Self::Store => ([U8, S32], []).into(),
+            Self::LoadS16 => ([S32], [S32]).into(),
+            Self::StoreS16 => ([S32, S32], []).into(),
+            Self::LoadS32 => ([S32], [S32]).into(),
+            Self::StoreS32 => ([S32, S32], []).into(),
+            Self::ReadInput => ([], [U8]).into(),
+            Self::IsKeyHeld => ([U8], [U8]).into(),
+            Self::ReadRandom => ([], [S32]).into(),
+            Self::SetResolution => ([S32, S32, S32], []).into(),
+            Self::SetPixel => ([S32, S32, U8, U8, U8, U8], []).into(),
+            Self::FillRect => ([S32, S32, S32, S32, U8, U8, U8, U8], []).into(),
+            Self::Blit => ([S32, S32, S32, S32, S32], []).into(),
             Self::SubmitFrame => ([], []).into(),
         };
 
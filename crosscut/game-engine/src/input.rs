@@ -0,0 +1,102 @@
+use std::collections::{BTreeSet, VecDeque};
+
+/// # The keys that a host can report to a game
+///
+/// Each key is identified by a code, which is what games deal with:
+///
+/// | Code | Key         | Alternative keys in the browser |
+/// |------|-------------|---------------------------------|
+/// | `1`  | Up          | `W`                             |
+/// | `2`  | Left        | `A`                             |
+/// | `3`  | Down        | `S`                             |
+/// | `4`  | Right       | `D`                             |
+/// | `5`  | Space       |                                 |
+/// | `6`  | Enter       |                                 |
+/// | `7`  | Escape      |                                 |
+///
+/// Code `0` never refers to a key. `GameEngineFunction::ReadInput` returns it,
+/// if no input event is available.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    num_enum::IntoPrimitive,
+    num_enum::TryFromPrimitive,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[repr(u8)]
+pub enum Key {
+    Up = 1,
+    Left,
+    Down,
+    Right,
+    Space,
+    Enter,
+    Escape,
+}
+
+/// # An input event, as reported by the host
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize,
+)]
+pub enum InputEvent {
+    KeyDown(Key),
+    KeyUp(Key),
+}
+
+impl InputEvent {
+    /// # The bit that marks an encoded event as a key release
+    pub const KEY_UP: u8 = 0x80;
+
+    /// # Encode the event into the value that games read
+    ///
+    /// A key press is represented by the code of the key. A key release is
+    /// represented by the code of the key, with [`InputEvent::KEY_UP`] set.
+    pub fn encode(&self) -> u8 {
+        match self {
+            Self::KeyDown(key) => u8::from(*key),
+            Self::KeyUp(key) => u8::from(*key) | Self::KEY_UP,
+        }
+    }
+}
+
+/// # The input that the host has provided to a game
+///
+/// Keeps the events that the game has not read yet, as well as the keys that
+/// are currently held down.
+#[derive(
+    Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize,
+)]
+pub struct Input {
+    events: VecDeque<InputEvent>,
+    held: BTreeSet<Key>,
+}
+
+impl Input {
+    pub fn on_event(&mut self, event: InputEvent) {
+        match event {
+            InputEvent::KeyDown(key) => {
+                self.held.insert(key);
+            }
+            InputEvent::KeyUp(key) => {
+                self.held.remove(&key);
+            }
+        }
+
+        self.events.push_back(event);
+    }
+
+    /// # Take the oldest event that the game has not read yet
+    pub fn next_event(&mut self) -> Option<InputEvent> {
+        self.events.pop_front()
+    }
+
+    pub fn is_held(&self, key: Key) -> bool {
+        self.held.contains(&key)
+    }
+}
@@ -1,5 +1,10 @@
 pub mod command;
 pub mod display;
 pub mod game_engine;
+pub mod history;
 pub mod host;
+pub mod input;
 pub mod memory;
+pub mod random;
+pub mod recording;
+pub mod snapshot;
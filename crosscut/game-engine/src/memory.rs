@@ -1,12 +1,110 @@
+use crosscut_runtime::Effect;
+
 /// Linear memory that games can access
+///
+/// Addresses are byte offsets into the memory. Any access that would reach
+/// beyond its end triggers [`Effect::OperandOutOfBounds`].
+///
+/// Multi-byte values are stored in little-endian byte order.
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct Memory {
-    #[serde(with = "serde_big_array::BigArray")]
-    pub inner: [u8; 256],
+    pub inner: Vec<u8>,
+}
+
+impl Memory {
+    /// # The size of the memory, unless configured otherwise
+    pub const DEFAULT_SIZE: usize = 64 * 1024;
+
+    /// # Create a zeroed memory of the provided size, in bytes
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            inner: vec![0; size],
+        }
+    }
+
+    /// # Set all bytes to zero, without changing the size of the memory
+    pub fn clear(&mut self) {
+        self.inner.fill(0);
+    }
+
+    pub fn load_u8(&self, address: usize) -> Result<u8, Effect> {
+        let [value] = self.load(address)?;
+        Ok(value)
+    }
+
+    pub fn store_u8(
+        &mut self,
+        address: usize,
+        value: u8,
+    ) -> Result<(), Effect> {
+        self.store(address, [value])
+    }
+
+    pub fn load_s16(&self, address: usize) -> Result<i16, Effect> {
+        let bytes = self.load(address)?;
+        Ok(i16::from_le_bytes(bytes))
+    }
+
+    pub fn store_s16(
+        &mut self,
+        address: usize,
+        value: i16,
+    ) -> Result<(), Effect> {
+        self.store(address, value.to_le_bytes())
+    }
+
+    pub fn load_s32(&self, address: usize) -> Result<i32, Effect> {
+        let bytes = self.load(address)?;
+        Ok(i32::from_le_bytes(bytes))
+    }
+
+    pub fn store_s32(
+        &mut self,
+        address: usize,
+        value: i32,
+    ) -> Result<(), Effect> {
+        self.store(address, value.to_le_bytes())
+    }
+
+    /// # Access a range of bytes, starting at the provided address
+    pub fn load_bytes(
+        &self,
+        address: usize,
+        len: usize,
+    ) -> Result<&[u8], Effect> {
+        address
+            .checked_add(len)
+            .and_then(|end| self.inner.get(address..end))
+            .ok_or(Effect::OperandOutOfBounds)
+    }
+
+    fn load<const N: usize>(&self, address: usize) -> Result<[u8; N], Effect> {
+        let bytes = self.load_bytes(address, N)?;
+
+        let mut value = [0; N];
+        value.copy_from_slice(bytes);
+
+        Ok(value)
+    }
+
+    fn store<const N: usize>(
+        &mut self,
+        address: usize,
+        value: [u8; N],
+    ) -> Result<(), Effect> {
+        let bytes = address
+            .checked_add(N)
+            .and_then(|end| self.inner.get_mut(address..end))
+            .ok_or(Effect::OperandOutOfBounds)?;
+
+        bytes.copy_from_slice(&value);
+
+        Ok(())
+    }
 }
 
 impl Default for Memory {
     fn default() -> Self {
-        Self { inner: [0; 256] }
+        Self::with_size(Self::DEFAULT_SIZE)
     }
 }
@@ -0,0 +1,50 @@
+/// # The game engine's pseudo-random number generator
+///
+/// Games get their random numbers from here. Since the generator is fully
+/// determined by its seed, so is any run of a game, given the same input.
+///
+/// ## Implementation Note
+///
+/// This is SplitMix64. It is tiny, fast, and passes the usual statistical
+/// tests. It is not suitable for cryptography, but games don't need that.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize,
+)]
+pub struct Random {
+    seed: u64,
+    state: u64,
+}
+
+impl Random {
+    pub fn from_seed(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    /// # The seed that the generator was created from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// # Start over from the seed
+    pub fn reset(&mut self) {
+        self.state = self.seed;
+    }
+
+    pub fn next_i32(&mut self) -> i32 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        // The upper bits are of higher quality. Truncating to them is intended.
+        (z >> 32) as u32 as i32
+    }
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Self::from_seed(0)
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{input::InputEvent, snapshot::Snapshot};
+
+/// # A recording of all external inputs that a game consumed
+///
+/// Besides its code, a game only depends on the input that its host provides.
+/// Its random numbers are determined by the seed, which is part of the initial
+/// state. Starting from that state, replaying the input frame by frame results
+/// in exactly the same frames being rendered.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Recording {
+    /// # The state of the game when the recording started
+    pub initial_state: Snapshot,
+
+    /// # The external inputs that were provided before each frame
+    pub frames: Vec<RecordedFrame>,
+}
+
+#[derive(
+    Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize,
+)]
+pub struct RecordedFrame {
+    pub input: Vec<InputEvent>,
+}
+
+#[derive(Debug)]
+pub struct Recorder {
+    recording: Recording,
+    next_frame: RecordedFrame,
+}
+
+impl Recorder {
+    pub fn new(initial_state: Snapshot) -> Self {
+        Self {
+            recording: Recording {
+                initial_state,
+                frames: Vec::new(),
+            },
+            next_frame: RecordedFrame::default(),
+        }
+    }
+
+    pub fn on_input(&mut self, event: InputEvent) {
+        self.next_frame.input.push(event);
+    }
+
+    /// # Record the inputs since the previous frame as belonging to a frame
+    ///
+    /// Must be called right before a frame is run.
+    pub fn end_frame(&mut self) {
+        let frame = std::mem::take(&mut self.next_frame);
+        self.recording.frames.push(frame);
+    }
+
+    /// # Finish the recording
+    ///
+    /// Inputs that were provided after the last frame are not part of the
+    /// recording, as no frame consumed them yet.
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crosscut_compiler::Compiler;
+
+    use crate::{
+        command::Command,
+        game_engine::GameEngine,
+        host::GameEngineHost,
+        input::{InputEvent, Key},
+    };
+
+    #[test]
+    fn replay_recording_to_same_frames_and_state() {
+        let source = include_str!("../../../games/snake/main.capi");
+        let output = Compiler::default().compile(source, &GameEngineHost);
+        assert_eq!(output.diagnostics, []);
+        let instructions = output.instructions;
+
+        let mut game_engine = GameEngine::new();
+        game_engine.on_command(Command::UpdateCode {
+            instructions: instructions.clone(),
+        });
+        game_engine.on_command(Command::SetSeed { seed: 3 });
+        game_engine.start_recording();
+
+        let input = [
+            (5, InputEvent::KeyDown(Key::Up)),
+            (6, InputEvent::KeyUp(Key::Up)),
+            (20, InputEvent::KeyDown(Key::Left)),
+            (21, InputEvent::KeyUp(Key::Left)),
+        ];
+
+        let mut pixels = Vec::new();
+        let mut frames = Vec::new();
+        for frame in 0..50 {
+            for (_, event) in input.iter().filter(|(f, _)| *f == frame) {
+                game_engine.on_input(*event);
+            }
+
+            game_engine.run_frame(&mut pixels);
+            frames.push(pixels.clone());
+        }
+
+        assert_eq!(game_engine.runtime.effect().inspect(), None);
+
+        let recording = game_engine.stop_recording().unwrap();
+        let snapshot = game_engine.snapshot();
+        assert_ne!(
+            snapshot.random, recording.initial_state.random,
+            "Expected the game to consume random numbers.",
+        );
+
+        let mut game_engine = GameEngine::new();
+        game_engine.on_command(Command::UpdateCode { instructions });
+        game_engine.restore(recording.initial_state);
+
+        let mut pixels = Vec::new();
+        for (i, frame) in recording.frames.into_iter().enumerate() {
+            game_engine.replay_frame(frame, &mut pixels);
+            assert_eq!(pixels, frames[i], "Frame {i} differs.");
+        }
+
+        assert_eq!(game_engine.snapshot(), snapshot);
+    }
+}
@@ -0,0 +1,24 @@
+use crosscut_runtime::Runtime;
+
+use crate::{
+    display::Resolution, input::Input, memory::Memory, random::Random,
+};
+
+/// # A snapshot of the full state of a game
+///
+/// Contains everything that determines how a game continues: the runtime,
+/// including any closures on its stack; the game's memory; the input that the
+/// game has not consumed yet, and which keys are held; the state of the random
+/// number generator; and the resolution that the game renders at.
+///
+/// A snapshot does not contain the game's code. It is only meaningful, if it
+/// is restored into a game engine that runs the same code that it was taken
+/// from.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Snapshot {
+    pub runtime: Runtime,
+    pub memory: Memory,
+    pub input: Input,
+    pub random: Random,
+    pub resolution: Resolution,
+}
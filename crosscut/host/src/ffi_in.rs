@@ -1,10 +1,13 @@
 use std::sync::Mutex;
 
 use crosscut_ffi::{framed_buffer::FramedBuffer, shared::Shared};
-use crosscut_game_engine::display::NUM_PIXEL_BYTES;
+use crosscut_game_engine::{
+    command::Command,
+    input::{InputEvent, Key},
+};
 use crosscut_protocol::{COMMANDS_BUFFER_SIZE, UPDATES_BUFFER_SIZE};
 
-use crate::host::Host;
+use crate::{ffi_out::print, host::Host};
 
 pub static STATE: Mutex<Option<Host>> = Mutex::new(None);
 
@@ -12,8 +15,7 @@ static UPDATES: Shared<FramedBuffer<UPDATES_BUFFER_SIZE>> =
     Shared::new(FramedBuffer::new());
 static COMMANDS: Shared<FramedBuffer<COMMANDS_BUFFER_SIZE>> =
     Shared::new(FramedBuffer::new());
-static PIXELS: Shared<[u8; NUM_PIXEL_BYTES]> =
-    Shared::new([0; NUM_PIXEL_BYTES]);
+static PIXELS: Shared<Vec<u8>> = Shared::new(Vec::new());
 
 /// This is a workaround for not being able to return a tuple from
 /// `updates_read`. That should work in principle (see [1]), but Rust warns
@@ -55,15 +57,24 @@ pub fn updates_read_len() -> usize {
 
 static LAST_COMMAND_WRITE: Mutex<Option<(usize, usize)>> = Mutex::new(None);
 
+/// # Reserve space for a command in the commands buffer
+///
+/// Returns `false`, if the command doesn't fit. In that case, the host must not
+/// write the command, nor call `on_command`.
 #[no_mangle]
-pub fn commands_write(len: usize) {
+pub fn commands_write(len: usize) -> bool {
     // Sound, because the reference is dropped before we give back control to
     // the host.
     let buffer = unsafe { COMMANDS.access() };
-    let command = buffer.write_frame(len);
+    let Some(command) = buffer.write_frame(len) else {
+        print(&format!("Dropping command that doesn't fit ({len} bytes)"));
+        return false;
+    };
 
     *LAST_COMMAND_WRITE.lock().unwrap() =
         Some((command.as_ptr() as usize, command.len()));
+
+    true
 }
 
 #[no_mangle]
@@ -80,33 +91,74 @@ pub fn commands_write_len() -> usize {
 
 #[no_mangle]
 pub fn pixels_ptr() -> usize {
-    &PIXELS as *const _ as usize
+    // Sound, because the reference is dropped before we give back control to
+    // the host.
+    let pixels = unsafe { PIXELS.access() };
+    pixels.as_ptr() as usize
 }
 
 #[no_mangle]
 pub fn pixels_len() -> usize {
-    NUM_PIXEL_BYTES
+    // Sound, because the reference is dropped before we give back control to
+    // the host.
+    let pixels = unsafe { PIXELS.access() };
+    pixels.len()
+}
+
+#[no_mangle]
+pub fn pixels_width() -> usize {
+    let [width, _] = pixels_size();
+    width
+}
+
+#[no_mangle]
+pub fn pixels_height() -> usize {
+    let [_, height] = pixels_size();
+    height
+}
+
+fn pixels_size() -> [usize; 2] {
+    let mut state = STATE.lock().unwrap();
+    let state = state.get_or_insert_with(Default::default);
+
+    state.frame_size
 }
 
 #[no_mangle]
-pub fn push_random(random: f64) -> bool {
+pub fn set_seed(random: f64) {
     let mut state = STATE.lock().unwrap();
     let state = state.get_or_insert_with(Default::default);
 
-    let min: f64 = i32::MIN.into();
-    let max: f64 = i32::MAX.into();
+    // JavaScript numbers are only precise up to 53 bits. That is plenty for a
+    // seed.
+    let max = (1u64 << 53) as f64;
+    let seed = (random * max).floor() as u64;
 
-    let random = min + random * (max - min);
+    state.game_engine.on_command(Command::SetSeed { seed });
+}
 
-    state.game_engine.push_random(random.floor() as _)
+#[no_mangle]
+pub fn on_key_down(key_code: u8) {
+    on_key(key_code, InputEvent::KeyDown);
 }
 
 #[no_mangle]
-pub fn on_key(key_code: u8) {
+pub fn on_key_up(key_code: u8) {
+    on_key(key_code, InputEvent::KeyUp);
+}
+
+fn on_key(key_code: u8, event: fn(Key) -> InputEvent) {
     let mut state = STATE.lock().unwrap();
     let state = state.get_or_insert_with(Default::default);
 
-    state.game_engine.on_input(key_code);
+    // The JavaScript side only reports keys that we know about, so this
+    // shouldn't fail. If it does anyway, there's nothing sensible to do with
+    // the key.
+    let Ok(key) = Key::try_from(key_code) else {
+        return;
+    };
+
+    state.game_engine.on_input(event(key));
 }
 
 #[no_mangle]
@@ -139,6 +191,13 @@ pub fn on_frame(current_time_ms: f64) {
         // Sound, because the reference is dropped before we call the method
         // again or we give back control to the host.
         let buffer = unsafe { UPDATES.access() };
-        buffer.write_frame(update.len()).copy_from_slice(&update);
+        let Some(frame) = buffer.write_frame(update.len()) else {
+            print(&format!(
+                "Dropping update that doesn't fit ({} bytes)",
+                update.len(),
+            ));
+            continue;
+        };
+        frame.copy_from_slice(&update);
     }
 }
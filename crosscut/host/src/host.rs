@@ -1,17 +1,25 @@
 use std::panic;
 
-use crosscut_game_engine::{command::Command, game_engine::GameEngine};
+use crosscut_game_engine::{
+    command::Command, display::Resolution, game_engine::GameEngine,
+};
 use crosscut_protocol::{
     command::{CommandExt, SerializedCommandToRuntime},
     updates::Updates,
 };
 
-use crate::ffi_out::on_panic;
+use crate::ffi_out::{on_panic, print};
 
 pub struct Host {
     pub game_engine: GameEngine,
     pub commands: Vec<SerializedCommandToRuntime>,
     pub updates: Updates,
+
+    /// # The size of the last frame that was rendered, in pixels
+    ///
+    /// The game can change its resolution at any time, but the JavaScript host
+    /// needs to know how to interpret the frame buffer that it has.
+    pub frame_size: [usize; 2],
 }
 
 impl Host {
@@ -24,21 +32,40 @@ impl Host {
             game_engine: GameEngine::new(),
             commands: Vec::new(),
             updates: Updates::default(),
+            frame_size: Resolution::default().size(),
         }
     }
 
-    pub fn update(&mut self, current_time_ms: f64, pixels: &mut [u8]) {
+    pub fn update(&mut self, current_time_ms: f64, pixels: &mut Vec<u8>) {
         for command in self.commands.drain(..) {
-            let command = Command::deserialize(command);
+            let command = match Command::deserialize(command) {
+                Ok(command) => command,
+                Err(err) => {
+                    print(&format!("Ignoring malformed command: {err}"));
+                    continue;
+                }
+            };
             self.game_engine.on_command(command);
         }
 
-        self.game_engine
-            .run_until_end_of_frame(current_time_ms / 1000.0, pixels);
+        if let Some(snapshot) = self.game_engine.take_snapshot() {
+            self.updates.queue_snapshot(snapshot);
+        }
+        if self.game_engine.take_rejected_patch() {
+            self.updates.queue_rejected_patch();
+        }
+
+        if self
+            .game_engine
+            .run_until_end_of_frame(current_time_ms / 1000.0, pixels)
+        {
+            self.frame_size = self.game_engine.resolution().size();
+        }
 
         self.updates.queue_updates(
             &self.game_engine.runtime,
             self.game_engine.memory(),
+            self.game_engine.seed(),
         );
     }
 }
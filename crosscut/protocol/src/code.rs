@@ -0,0 +1,426 @@
+use std::{collections::HashMap, mem};
+
+use crosscut_compiler::{CompilerOutput, Instructions, InstructionsPatch};
+use crosscut_game_engine::command::Command;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    wire::{self, DecodeError},
+    Versioned,
+};
+
+/// # An update to the game's code, as served by the development server
+#[allow(clippy::large_enum_variant)] // haven't optimized this yet
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub enum CodeUpdate {
+    /// # The full code
+    ///
+    /// Sent, if the client doesn't have any code yet, or if the server no
+    /// longer knows the version of the code that the client has.
+    Full { code: Versioned<CompilerOutput> },
+
+    /// # The changes since the version of the code that the client has
+    Delta {
+        base: u64,
+        timestamp: u64,
+        delta: CompilerOutputDelta,
+    },
+}
+
+impl CodeUpdate {
+    /// # Create the update that brings a client from `base` to `latest`
+    pub fn new(
+        base: Option<&Versioned<CompilerOutput>>,
+        latest: &Versioned<CompilerOutput>,
+    ) -> Self {
+        match base {
+            Some(base) => Self::Delta {
+                base: base.timestamp,
+                timestamp: latest.timestamp,
+                delta: CompilerOutputDelta::new(&base.inner, &latest.inner),
+            },
+            None => Self::Full {
+                code: Versioned {
+                    timestamp: latest.timestamp,
+                    inner: latest.inner.clone(),
+                },
+            },
+        }
+    }
+
+    /// # Apply the update to the code that the client currently has
+    pub fn apply(
+        self,
+        current: Option<&Versioned<CompilerOutput>>,
+    ) -> Result<Versioned<CompilerOutput>, CodeUpdateError> {
+        match self {
+            Self::Full { code } => Ok(code),
+            Self::Delta {
+                base,
+                timestamp,
+                delta,
+            } => {
+                let current = current
+                    .filter(|current| current.timestamp == base)
+                    .ok_or(CodeUpdateError::WrongBase {
+                        base,
+                        actual: current.map(|current| current.timestamp),
+                    })?;
+
+                Ok(Versioned {
+                    timestamp,
+                    inner: delta.apply(&current.inner)?,
+                })
+            }
+        }
+    }
+}
+
+/// # The code that a client has received from the development server
+///
+/// Used by the debugger and the native host to request updates from the
+/// server, and to apply them.
+#[derive(Debug, Default)]
+pub struct CodeAtClient {
+    inner: Option<Versioned<CompilerOutput>>,
+}
+
+impl CodeAtClient {
+    /// # The path to request the next update from
+    ///
+    /// If the client has no code, the server responds with the latest code
+    /// right away. Otherwise, it waits for code that is newer.
+    pub fn next_request(&self) -> String {
+        match &self.inner {
+            Some(code) => format!("/code/{}", code.timestamp),
+            None => "/code".to_string(),
+        }
+    }
+
+    /// # Apply an update from the server
+    ///
+    /// If the update doesn't fit the code that the client has, that code can't
+    /// be trusted anymore. It is dropped, so the next request is for the full
+    /// code.
+    pub fn apply(
+        &mut self,
+        update: CodeUpdate,
+    ) -> Result<&CompilerOutput, CodeUpdateError> {
+        match update.apply(self.inner.as_ref()) {
+            Ok(code) => Ok(&self.inner.insert(code).inner),
+            Err(err) => {
+                self.inner = None;
+                Err(err)
+            }
+        }
+    }
+}
+
+/// # The instructions that a runtime has, as far as its client knows
+///
+/// Used by the debugger and the native host to only send the changes to the
+/// runtime, whenever the instructions change.
+#[derive(Clone, Debug, Default)]
+pub struct InstructionsAtRuntime {
+    inner: Option<Instructions>,
+}
+
+impl InstructionsAtRuntime {
+    /// # Create the command that updates the runtime's instructions
+    ///
+    /// The command must be sent to the runtime, or the client's idea of what
+    /// the runtime has will be wrong from here on.
+    pub fn update(&mut self, instructions: Instructions) -> Command {
+        let command = match &self.inner {
+            Some(current) => Command::PatchInstructions {
+                patch: current.diff(&instructions),
+            },
+            None => Command::UpdateCode {
+                instructions: instructions.clone(),
+            },
+        };
+
+        self.inner = Some(instructions);
+
+        command
+    }
+
+    /// # Create the command that sends the full instructions to the runtime
+    ///
+    /// Needed, if the runtime might not have the instructions that patches are
+    /// based on. See `UpdateFromHost::ResendCode`.
+    pub fn resend(&self) -> Option<Command> {
+        let instructions = self.inner.clone()?;
+        Some(Command::UpdateCode { instructions })
+    }
+}
+
+/// # The changes between two versions of [`CompilerOutput`]
+///
+/// The instructions are compared instruction by instruction. Everything else
+/// is compared in its encoded form. Since a change to the code usually affects
+/// only small regions of each part of the compiler output, this tends to be
+/// much smaller than the full compiler output.
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct CompilerOutputDelta {
+    syntax_tree: BytesDelta,
+    functions: BytesDelta,
+    function_calls: BytesDelta,
+    dependencies: BytesDelta,
+    types: BytesDelta,
+    instructions: InstructionsPatch,
+    source_map: BytesDelta,
+    diagnostics: BytesDelta,
+}
+
+impl CompilerOutputDelta {
+    pub fn new(old: &CompilerOutput, new: &CompilerOutput) -> Self {
+        Self {
+            syntax_tree: BytesDelta::new(&old.syntax_tree, &new.syntax_tree),
+            functions: BytesDelta::new(&old.functions, &new.functions),
+            function_calls: BytesDelta::new(
+                &old.function_calls,
+                &new.function_calls,
+            ),
+            dependencies: BytesDelta::new(&old.dependencies, &new.dependencies),
+            types: BytesDelta::new(&old.types, &new.types),
+            instructions: old.instructions.diff(&new.instructions),
+            source_map: BytesDelta::new(&old.source_map, &new.source_map),
+            diagnostics: BytesDelta::new(&old.diagnostics, &new.diagnostics),
+        }
+    }
+
+    pub fn apply(
+        &self,
+        base: &CompilerOutput,
+    ) -> Result<CompilerOutput, CodeUpdateError> {
+        let mut instructions = base.instructions.clone();
+        instructions
+            .apply(&self.instructions)
+            .map_err(|_| CodeUpdateError::Mismatch)?;
+
+        Ok(CompilerOutput {
+            syntax_tree: self.syntax_tree.apply(&base.syntax_tree)?,
+            functions: self.functions.apply(&base.functions)?,
+            function_calls: self.function_calls.apply(&base.function_calls)?,
+            dependencies: self.dependencies.apply(&base.dependencies)?,
+            types: self.types.apply(&base.types)?,
+            instructions,
+            source_map: self.source_map.apply(&base.source_map)?,
+            diagnostics: self.diagnostics.apply(&base.diagnostics)?,
+        })
+    }
+}
+
+/// # The changes between the encoded forms of two values
+///
+/// Describes the new value as a sequence of operations, which either copy bytes
+/// from the old value, or insert bytes that are not found there.
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct BytesDelta {
+    operations: Vec<DeltaOperation>,
+}
+
+impl BytesDelta {
+    /// # The size of the blocks that are looked up in the old value
+    ///
+    /// Smaller blocks find more matches, but cost more to look up. Matches are
+    /// extended beyond the block they were found in, so this doesn't limit how
+    /// long a copied range can become.
+    const BLOCK_SIZE: usize = 16;
+
+    fn new(old: &impl Serialize, new: &impl Serialize) -> Self {
+        let old = wire::encode(old);
+        let new = wire::encode(new);
+
+        let mut blocks = HashMap::new();
+        for (i, block) in old.chunks_exact(Self::BLOCK_SIZE).enumerate() {
+            blocks.entry(block).or_insert(i * Self::BLOCK_SIZE);
+        }
+
+        let mut operations = Vec::new();
+        let mut inserted = Vec::new();
+        let mut pos = 0;
+
+        while pos < new.len() {
+            let Some(&offset) = new
+                .get(pos..pos + Self::BLOCK_SIZE)
+                .and_then(|block| blocks.get(block))
+            else {
+                inserted.push(new[pos]);
+                pos += 1;
+                continue;
+            };
+
+            // We found a matching block. Let's see how far the match goes, in
+            // both directions.
+            let mut len = Self::BLOCK_SIZE;
+            while old.get(offset + len).is_some_and(|&byte| {
+                new.get(pos + len).is_some_and(|&new| byte == new)
+            }) {
+                len += 1;
+            }
+
+            let mut before = 0;
+            while before < inserted.len()
+                && before < offset
+                && old[offset - before - 1] == new[pos - before - 1]
+            {
+                before += 1;
+            }
+            inserted.truncate(inserted.len() - before);
+
+            if !inserted.is_empty() {
+                operations.push(DeltaOperation::Insert {
+                    bytes: mem::take(&mut inserted),
+                });
+            }
+            operations.push(DeltaOperation::Copy {
+                offset: offset - before,
+                len: before + len,
+            });
+
+            pos += len;
+        }
+
+        if !inserted.is_empty() {
+            operations.push(DeltaOperation::Insert { bytes: inserted });
+        }
+
+        Self { operations }
+    }
+
+    fn apply<T>(&self, base: &T) -> Result<T, CodeUpdateError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let base = wire::encode(base);
+        let mut bytes = Vec::new();
+
+        for operation in &self.operations {
+            match operation {
+                DeltaOperation::Copy { offset, len } => {
+                    let copied = offset
+                        .checked_add(*len)
+                        .and_then(|end| base.get(*offset..end))
+                        .ok_or(CodeUpdateError::Mismatch)?;
+                    bytes.extend_from_slice(copied);
+                }
+                DeltaOperation::Insert { bytes: inserted } => {
+                    bytes.extend_from_slice(inserted);
+                }
+            }
+        }
+
+        Ok(wire::decode(&bytes)?)
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+enum DeltaOperation {
+    Copy { offset: usize, len: usize },
+    Insert { bytes: Vec<u8> },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CodeUpdateError {
+    #[error("Update is based on version {base}, but the code is {actual:?}")]
+    WrongBase { base: u64, actual: Option<u64> },
+
+    #[error("Update doesn't fit the code it is applied to")]
+    Mismatch,
+
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+}
+
+#[cfg(test)]
+mod tests {
+    use crosscut_compiler::{host::NoHost, Compiler, CompilerOutput};
+
+    use crate::{wire, Versioned};
+
+    use super::{CodeAtClient, CodeUpdate, CodeUpdateError};
+
+    #[test]
+    fn apply_delta() {
+        let mut compiler = Compiler::default();
+        let old = compile(&mut compiler, 1, "1 2 add_s32");
+        let new = compile(&mut compiler, 2, "1 3 add_s32");
+
+        let update = CodeUpdate::new(Some(&old), &new);
+        let update: CodeUpdate = wire::decode(&wire::encode(&update)).unwrap();
+        let CodeUpdate::Delta { .. } = update else {
+            panic!("Expected delta.");
+        };
+
+        let applied = update.apply(Some(&old)).unwrap();
+        assert_eq!(wire::encode(&applied), wire::encode(&new));
+    }
+
+    #[test]
+    fn reject_delta_for_other_version() {
+        let mut compiler = Compiler::default();
+        let old = compile(&mut compiler, 1, "1 2 add_s32");
+        let new = compile(&mut compiler, 2, "1 3 add_s32");
+
+        let update = CodeUpdate::new(Some(&old), &new);
+        assert!(matches!(
+            update.apply(Some(&new)),
+            Err(CodeUpdateError::WrongBase {
+                base: 1,
+                actual: Some(2)
+            }),
+        ));
+    }
+
+    #[test]
+    fn request_full_code_after_stale_delta() {
+        let mut compiler = Compiler::default();
+        let v1 = compile(&mut compiler, 1, "1 2 add_s32");
+        let v2 = compile(&mut compiler, 2, "1 3 add_s32");
+        let v3 = compile(&mut compiler, 3, "1 4 add_s32");
+
+        let mut client = CodeAtClient::default();
+        assert_eq!(client.next_request(), "/code");
+
+        client.apply(CodeUpdate::new(None, &v1)).unwrap();
+        assert_eq!(client.next_request(), "/code/1");
+
+        // The client missed version 2, so this delta doesn't apply. It must
+        // not keep waiting for code newer than what it has.
+        assert!(matches!(
+            client.apply(CodeUpdate::new(Some(&v2), &v3)),
+            Err(CodeUpdateError::WrongBase {
+                base: 2,
+                actual: Some(1),
+            }),
+        ));
+        assert_eq!(client.next_request(), "/code");
+
+        let code = client.apply(CodeUpdate::new(None, &v3)).unwrap();
+        assert_eq!(wire::encode(code), wire::encode(&v3.inner));
+        assert_eq!(client.next_request(), "/code/3");
+    }
+
+    fn compile(
+        compiler: &mut Compiler,
+        timestamp: u64,
+        body: &str,
+    ) -> Versioned<CompilerOutput> {
+        let source = format!(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        {body}
+                    end
+                end
+            "
+        );
+
+        Versioned {
+            timestamp,
+            inner: compiler.compile(&source, &NoHost),
+        }
+    }
+}
@@ -1,18 +1,23 @@
 use crosscut_game_engine::command::Command;
 
-pub trait CommandExt {
-    fn deserialize(bytes: SerializedCommandToRuntime) -> Self;
+use crate::wire::{self, DecodeError};
+
+pub trait CommandExt: Sized {
+    fn deserialize(
+        bytes: SerializedCommandToRuntime,
+    ) -> Result<Self, DecodeError>;
     fn serialize(&self) -> SerializedCommandToRuntime;
 }
 
 impl CommandExt for Command {
-    fn deserialize(bytes: SerializedCommandToRuntime) -> Self {
-        let string = std::str::from_utf8(&bytes).unwrap();
-        ron::from_str(string).unwrap()
+    fn deserialize(
+        bytes: SerializedCommandToRuntime,
+    ) -> Result<Self, DecodeError> {
+        wire::decode(&bytes)
     }
 
     fn serialize(&self) -> SerializedCommandToRuntime {
-        ron::to_string(self).unwrap().into_bytes()
+        wire::encode(self)
     }
 }
 
@@ -9,6 +9,12 @@ pub enum HostState {
     /// # The process has finished
     Finished,
 
+    /// # The process has not finished its frame within its instruction budget
+    ///
+    /// It is going to continue running next frame. Until then, it can be
+    /// stopped.
+    FrameOverrun,
+
     /// # The process is currently stopped
     Stopped {
         /// # The triggered effect
@@ -19,5 +25,8 @@ pub enum HostState {
 
         /// # The operands in the current stack frame
         current_operands: Vec<Value>,
+
+        /// # The bindings in the current stack frame, indexed by slot
+        current_bindings: Vec<Value>,
     },
 }
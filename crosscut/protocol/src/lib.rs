@@ -1,19 +1,24 @@
+pub mod code;
 pub mod command;
 pub mod host_state;
 pub mod updates;
+pub mod wire;
 
 /// The size of the updates buffer
 ///
-/// This is a ridiculous 1 MiB large. It should be possible to make this much
-/// smaller, but for now, we're using a very space-inefficient serialization
-/// format.
+/// The largest updates are the ones that contain the game's memory, which is
+/// 64 KiB large by default, or a snapshot, which contains the memory plus the
+/// stack. Several of those can be queued during the same frame.
+///
+/// The size of neither is bounded in a way that is practical to compute here,
+/// so this is generous. An update that doesn't fit is dropped and logged.
 pub const UPDATES_BUFFER_SIZE: usize = 1024 * 1024;
 
 /// The size of the commands buffer
 ///
-/// This is a ridiculous 1 MiB large. It should be possible to make this much
-/// smaller, but for now, we're using a very space-inefficient serialization
-/// format.
+/// The largest commands are the ones that restore a snapshot, and the ones that
+/// contain the game's code. Like [`UPDATES_BUFFER_SIZE`], this is generous, and
+/// a command that doesn't fit is dropped and logged.
 pub const COMMANDS_BUFFER_SIZE: usize = 1024 * 1024;
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
@@ -21,20 +26,3 @@ pub struct Versioned<T> {
     pub timestamp: u64,
     pub inner: T,
 }
-
-pub fn ron_options() -> ron::Options {
-    ron::Options::default()
-        // The default recursion limit (as of this writing) is `128`. After some
-        // recent changes, this started resulting in a
-        // `ron::Error::ExceededRecursionLimit`.
-        //
-        // I don't think there's a deep reason for that. What we're sending is
-        // just _very_ unoptimized, and another layer of abstraction that I
-        // added recently pushed us over the edge.
-        //
-        // Let's not worry about it too much for now. Once the traffic between
-        // the various components in a development setup becomes relevant, we'll
-        // need to replace RON with something more space-efficient anyway. And
-        // worry more about what we're sending in the first place.
-        .with_recursion_limit(256)
-}
@@ -1,18 +1,30 @@
-use crosscut_game_engine::memory::Memory;
+use crosscut_game_engine::{memory::Memory, snapshot::Snapshot};
 use crosscut_runtime::{Runtime, RuntimeState};
 
-use crate::host_state::HostState;
+use crate::{
+    host_state::HostState,
+    wire::{self, DecodeError},
+};
 
 #[derive(Debug, Default)]
 pub struct Updates {
-    latest_memory: Option<Memory>,
+    memory_at_client: Option<Memory>,
     runtime_at_client: Option<Runtime>,
+    seed_at_client: Option<u64>,
     queue: Vec<UpdateFromHost>,
 }
 
 impl Updates {
-    pub fn queue_updates(&mut self, runtime: &Runtime, memory: &Memory) {
-        self.latest_memory = Some(memory.clone());
+    pub fn queue_updates(
+        &mut self,
+        runtime: &Runtime,
+        memory: &Memory,
+        seed: u64,
+    ) {
+        if self.seed_at_client != Some(seed) {
+            self.seed_at_client = Some(seed);
+            self.queue.push(UpdateFromHost::Seed { seed });
+        }
 
         if self.update_is_necessary(runtime) {
             self.runtime_at_client = Some(runtime.clone());
@@ -20,6 +32,7 @@ impl Updates {
             let state = match runtime.state() {
                 RuntimeState::Running => HostState::Running,
                 RuntimeState::Finished => HostState::Finished,
+                RuntimeState::OutOfFuel => HostState::FrameOverrun,
                 RuntimeState::Stopped => HostState::Stopped {
                     effect: runtime.effect().inspect().copied(),
                     active_instructions: runtime
@@ -32,17 +45,35 @@ impl Updates {
                         .rev()
                         .copied()
                         .collect::<Vec<_>>(),
+                    current_bindings: runtime.stack().bindings().to_vec(),
                 },
             };
 
             self.queue.push(UpdateFromHost::State { state });
 
-            if let Some(memory) = self.latest_memory.take() {
-                self.queue.push(UpdateFromHost::Memory { memory });
+            // The memory can be large. Most steps through the code don't
+            // change it, so only send it, if the client doesn't have it yet.
+            if self.memory_at_client.as_ref() != Some(memory) {
+                self.memory_at_client = Some(memory.clone());
+                self.queue.push(UpdateFromHost::Memory {
+                    memory: memory.clone(),
+                });
             }
         }
     }
 
+    /// # Queue a snapshot that the game engine took on request
+    pub fn queue_snapshot(&mut self, snapshot: Snapshot) {
+        self.queue.push(UpdateFromHost::Snapshot { snapshot });
+    }
+
+    /// # Queue the notice that the game engine rejected a patch
+    ///
+    /// See [`UpdateFromHost::ResendCode`].
+    pub fn queue_rejected_patch(&mut self) {
+        self.queue.push(UpdateFromHost::ResendCode);
+    }
+
     pub fn take_queued_updates(
         &mut self,
     ) -> impl Iterator<Item = UpdateFromHost> + '_ {
@@ -62,6 +93,12 @@ impl Updates {
                 // would result in too many updates.
                 return false;
             }
+            if runtime_at_client.state().is_out_of_fuel()
+                && runtime.state().is_out_of_fuel()
+            {
+                // Same goes for a program that keeps overrunning its frames.
+                return false;
+            }
         }
 
         self.runtime_at_client.as_ref() != Some(runtime)
@@ -71,19 +108,82 @@ impl Updates {
 #[allow(clippy::large_enum_variant)] // haven't optimized this yet
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub enum UpdateFromHost {
-    State { state: HostState },
-    Memory { memory: Memory },
+    State {
+        state: HostState,
+    },
+    Memory {
+        memory: Memory,
+    },
+    Seed {
+        seed: u64,
+    },
+    Snapshot {
+        snapshot: Snapshot,
+    },
+
+    /// # The client needs to send the full instructions
+    ///
+    /// The game engine sends this, if a patch didn't fit the instructions that
+    /// it has. It keeps running those in the meantime.
+    ///
+    /// The relay sends this to a debugger that attaches to a game, or that
+    /// might have missed messages. The debugger can't know which instructions
+    /// the game has in those cases.
+    ResendCode,
 }
 
 impl UpdateFromHost {
-    pub fn deserialize(bytes: SerializedUpdate) -> Self {
-        let string = std::str::from_utf8(&bytes).unwrap();
-        ron::from_str(string).unwrap()
+    pub fn deserialize(bytes: SerializedUpdate) -> Result<Self, DecodeError> {
+        wire::decode(&bytes)
     }
 
     pub fn serialize(&self) -> SerializedUpdate {
-        ron::to_string(self).unwrap().into_bytes()
+        wire::encode(self)
     }
 }
 
 pub type SerializedUpdate = Vec<u8>;
+
+#[cfg(test)]
+mod tests {
+    use crosscut_game_engine::memory::Memory;
+    use crosscut_runtime::{Effect, Runtime, Value};
+
+    use super::{UpdateFromHost, Updates};
+
+    #[test]
+    fn only_send_memory_if_it_changed() {
+        let mut updates = Updates::default();
+        let mut memory = Memory::with_size(16);
+
+        // The runtime must be stopped and change between calls, or no update
+        // is necessary.
+        let mut runtimes = [1, 2, 3].map(|argument| {
+            let mut runtime = Runtime::default();
+            runtime.reset([Value::from(argument)]);
+            runtime
+                .effect_mut()
+                .trigger(Effect::Breakpoint)
+                .assert_triggered();
+            runtime
+        });
+        let mut queue_updates = |memory: &Memory| {
+            let [runtime, ..] = &runtimes;
+            updates.queue_updates(runtime, memory, 0);
+            runtimes.rotate_left(1);
+
+            updates
+                .take_queued_updates()
+                .filter(|update| {
+                    matches!(update, UpdateFromHost::Memory { .. })
+                })
+                .count()
+        };
+
+        assert_eq!(queue_updates(&memory), 1);
+        assert_eq!(queue_updates(&memory), 0);
+
+        memory.store_u8(3, 1).unwrap();
+        assert_eq!(queue_updates(&memory), 1);
+    }
+}
@@ -0,0 +1,199 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+/// # The version of the wire protocol
+///
+/// Every message starts with this version, encoded as a little-endian `u16`.
+/// This acts as a handshake between debugger, host, and server: A peer that
+/// receives a message with another version rejects it, instead of
+/// misinterpreting it.
+///
+/// This must be incremented whenever a change is made to any type that is sent
+/// over the wire, including the types from other crates that those contain.
+pub const PROTOCOL_VERSION: u16 = 1;
+
+const HEADER_SIZE: usize = size_of::<u16>();
+
+/// # Encode a message
+///
+/// The payload is encoded using [postcard], a compact binary format.
+///
+/// [postcard]: https://docs.rs/postcard
+pub fn encode(message: &impl Serialize) -> Vec<u8> {
+    let bytes = PROTOCOL_VERSION.to_le_bytes().to_vec();
+
+    postcard::to_extend(message, bytes).expect(
+        "Encoding a message into a `Vec` should not fail, unless it contains \
+        types that postcard doesn't support. That would be a bug.",
+    )
+}
+
+/// # Decode a message that was encoded by [`encode`]
+pub fn decode<T>(bytes: &[u8]) -> Result<T, DecodeError>
+where
+    T: DeserializeOwned,
+{
+    let Some((header, payload)) = bytes.split_at_checked(HEADER_SIZE) else {
+        return Err(DecodeError::MissingHeader);
+    };
+
+    let version = u16::from_le_bytes([header[0], header[1]]);
+    if version != PROTOCOL_VERSION {
+        return Err(DecodeError::VersionMismatch {
+            expected: PROTOCOL_VERSION,
+            actual: version,
+        });
+    }
+
+    let (message, rest) = postcard::take_from_bytes(payload)?;
+    if !rest.is_empty() {
+        return Err(DecodeError::TrailingBytes { num: rest.len() });
+    }
+
+    Ok(message)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum DecodeError {
+    #[error("Message is too short to contain the protocol version")]
+    MissingHeader,
+
+    #[error("Expected protocol version {expected}, but message has {actual}")]
+    VersionMismatch { expected: u16, actual: u16 },
+
+    #[error("Malformed message: {0}")]
+    Malformed(#[from] postcard::Error),
+
+    #[error("Message has {num} unexpected bytes after its end")]
+    TrailingBytes { num: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crosscut_compiler::{host::NoHost, Compiler, CompilerOutput};
+    use crosscut_game_engine::{
+        command::Command, game_engine::GameEngine, memory::Memory,
+    };
+    use crosscut_runtime::{Effect, InstructionAddress, Value};
+
+    use crate::{host_state::HostState, updates::UpdateFromHost, Versioned};
+
+    use super::{decode, encode, DecodeError, PROTOCOL_VERSION};
+
+    #[test]
+    fn round_trip_commands() {
+        let output = compile();
+
+        let commands = [
+            Command::ClearBreakpointAndContinue,
+            Command::Reset,
+            Command::RestoreState {
+                snapshot: GameEngine::new().snapshot(),
+            },
+            Command::Rewind {
+                targets: BTreeSet::from([InstructionAddress { index: 3 }]),
+            },
+            Command::SetSeed { seed: u64::MAX },
+            Command::UpdateCode {
+                instructions: output.instructions,
+            },
+        ];
+
+        for command in commands {
+            let decoded: Command = decode(&encode(&command)).unwrap();
+
+            // `Command` doesn't implement `PartialEq`, so compare the encoded
+            // form instead.
+            assert_eq!(encode(&decoded), encode(&command));
+        }
+    }
+
+    #[test]
+    fn round_trip_updates() {
+        let updates = [
+            UpdateFromHost::State {
+                state: HostState::Stopped {
+                    effect: Some(Effect::OperandOutOfBounds),
+                    active_instructions: vec![InstructionAddress { index: 1 }],
+                    current_operands: vec![Value::from(-1)],
+                    current_bindings: vec![Value::from(u32::MAX)],
+                },
+            },
+            UpdateFromHost::Memory {
+                memory: Memory::with_size(16),
+            },
+            UpdateFromHost::Seed { seed: 12345 },
+        ];
+
+        for update in updates {
+            let decoded: UpdateFromHost = decode(&encode(&update)).unwrap();
+            assert_eq!(encode(&decoded), encode(&update));
+        }
+    }
+
+    #[test]
+    fn round_trip_code() {
+        let code = Versioned {
+            timestamp: 1,
+            inner: compile(),
+        };
+
+        let decoded: Versioned<CompilerOutput> =
+            decode(&encode(&code)).unwrap();
+        assert_eq!(encode(&decoded), encode(&code));
+    }
+
+    #[test]
+    fn reject_other_protocol_version() {
+        let mut bytes = encode(&Command::Reset);
+        bytes[..2].copy_from_slice(&(PROTOCOL_VERSION + 1).to_le_bytes());
+
+        assert_eq!(
+            decode::<Command>(&bytes).unwrap_err(),
+            DecodeError::VersionMismatch {
+                expected: PROTOCOL_VERSION,
+                actual: PROTOCOL_VERSION + 1,
+            },
+        );
+    }
+
+    #[test]
+    fn reject_malformed_messages() {
+        let bytes = encode(&Command::SetSeed { seed: u64::MAX });
+
+        assert_eq!(
+            decode::<Command>(&bytes[..1]).unwrap_err(),
+            DecodeError::MissingHeader,
+        );
+        assert!(matches!(
+            decode::<Command>(&bytes[..bytes.len() - 1]).unwrap_err(),
+            DecodeError::Malformed(_),
+        ));
+        assert!(matches!(
+            decode::<Command>(&[&bytes[..2], &[u8::MAX]].concat()).unwrap_err(),
+            DecodeError::Malformed(_),
+        ));
+
+        let mut bytes = bytes;
+        bytes.push(0);
+        assert_eq!(
+            decode::<Command>(&bytes).unwrap_err(),
+            DecodeError::TrailingBytes { num: 1 },
+        );
+    }
+
+    fn compile() -> CompilerOutput {
+        let mut compiler = Compiler::default();
+        compiler.compile(
+            r"
+                main: fn
+                    br size_x, size_y ->
+                        1 2 add_s32
+                    end
+                end
+            ",
+            &NoHost,
+        )
+    }
+}
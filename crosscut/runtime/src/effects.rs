@@ -1,7 +1,8 @@
 use core::num::TryFromIntError;
 
 use crate::{
-    operands::PopOperandError, stack::PushStackFrameError,
+    operands::PopOperandError,
+    stack::{PushOperandError, PushStackFrameError},
     value::IntegerOverflow,
 };
 
@@ -62,6 +63,7 @@ impl TriggeredEffect {
     serde::Deserialize,
     serde::Serialize,
     thiserror::Error,
+    udigest::Digestable,
 )]
 pub enum Effect {
     #[error("Breakpoint")]
@@ -100,6 +102,12 @@ pub enum Effect {
         source: PopOperandError,
     },
 
+    #[error(transparent)]
+    PushOperand {
+        #[from]
+        source: PushOperandError,
+    },
+
     #[error(transparent)]
     PushStackFrame {
         #[from]
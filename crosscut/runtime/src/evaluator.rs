@@ -1,9 +1,4 @@
-use alloc::vec::Vec;
-
-use crate::{
-    function::Pattern, Effect, Function, Heap, Instruction, InstructionAddress,
-    Instructions, Stack,
-};
+use crate::{Effect, Instruction, InstructionAddress, Instructions, Stack};
 
 #[derive(
     Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize,
@@ -33,11 +28,7 @@ impl Evaluator {
             .chain([self.next_instruction])
     }
 
-    pub fn step(
-        &mut self,
-        instructions: Instructions,
-        heap: &mut Heap,
-    ) -> Result<(), Effect> {
+    pub fn step(&mut self, instructions: Instructions) -> Result<(), Effect> {
         if self.stack.no_frames_left() {
             return Ok(());
         }
@@ -50,7 +41,7 @@ impl Evaluator {
         self.next_instruction = evaluate_instruction(
             current_instruction,
             next_instruction,
-            heap,
+            instructions,
             &mut self.stack,
         )?;
 
@@ -61,7 +52,7 @@ impl Evaluator {
 fn evaluate_instruction(
     current_instruction: &Instruction,
     next_instruction: InstructionAddress,
-    heap: &mut Heap,
+    instructions: Instructions,
     stack: &mut Stack,
 ) -> Result<InstructionAddress, Effect> {
     match current_instruction {
@@ -76,7 +67,7 @@ fn evaluate_instruction(
                 return Err(Effect::IntegerOverflow);
             };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::AddS32 => {
             let b = stack.pop_operand()?;
@@ -89,7 +80,7 @@ fn evaluate_instruction(
                 return Err(Effect::IntegerOverflow);
             };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::AddU8 => {
             let b = stack.pop_operand()?;
@@ -102,7 +93,7 @@ fn evaluate_instruction(
                 return Err(Effect::IntegerOverflow);
             };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::AddU8Wrap => {
             let b = stack.pop_operand()?;
@@ -112,22 +103,16 @@ fn evaluate_instruction(
             let b = b.to_u8()?;
 
             let c = a.wrapping_add(b);
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
-        Instruction::Bind { name } => {
+        Instruction::Bind { slot } => {
             let value = stack.pop_operand()?;
-            stack.define_binding(name.clone(), value);
+            stack.define_binding(*slot as usize, value);
         }
-        Instruction::BindingEvaluate { name } => {
-            let Some(bindings) = stack.bindings() else {
-                unreachable!(
-                    "Can't access bindings, but we're currently executing. An \
-                    active stack frame, and therefore bindings, must exist."
-                );
-            };
-            let Some(value) = bindings.get(name).copied() else {
+        Instruction::BindingEvaluate { slot } => {
+            let Some(value) = stack.binding(*slot as usize) else {
                 unreachable!(
-                    "Can't find binding `{name}`, but instruction that \
+                    "Can't find binding in slot `{slot}`, but instruction that \
                     evaluates bindings should only be generated for bindings \
                     that exist.\n\
                     \n\
@@ -136,53 +121,35 @@ fn evaluate_instruction(
                     stack,
                 );
             };
-            stack.push_operand(value);
+            stack.push_operand(value)?;
         }
         Instruction::CallFunction {
-            callee: function,
+            callee,
             is_tail_call,
         } => {
-            for branch in &function.branches {
-                let mut used_operands = Vec::new();
-                let mut argument_operands = Vec::new();
-                let mut bound_arguments = Vec::new();
-
-                let mut member_matches = true;
-                for parameter in branch.parameters.iter().rev() {
-                    let operand = stack.pop_operand()?;
-                    used_operands.push(operand);
-
-                    match parameter {
-                        Pattern::Identifier { name } => {
-                            bound_arguments.push((name.clone(), operand));
-                            argument_operands.push(operand);
-                        }
-                        Pattern::Literal { value } => {
-                            member_matches &= *value == operand;
-                        }
-                    }
-                }
-
-                if member_matches {
-                    for value in argument_operands.into_iter().rev() {
-                        stack.push_operand(value);
-                    }
-
-                    if *is_tail_call {
-                        stack.reuse_frame();
-                    } else {
-                        stack.push_frame(next_instruction)?;
-                    }
-
-                    return Ok(branch.start);
-                } else {
-                    for value in used_operands.into_iter().rev() {
-                        stack.push_operand(value);
-                    }
-                }
+            if *is_tail_call {
+                stack.reuse_frame();
+            } else {
+                stack.push_frame(next_instruction)?;
             }
 
-            return Err(Effect::NoMatch);
+            return Ok(*callee);
+        }
+        Instruction::ConvertS8ToS32 => {
+            let v = stack.pop_operand()?;
+
+            let v = v.to_i8()?;
+            let v: i32 = v.into();
+
+            stack.push_operand(v)?;
+        }
+        Instruction::ConvertS8ToU8Wrap => {
+            let v = stack.pop_operand()?;
+
+            let v = v.to_i8()?;
+            let [v] = v.to_le_bytes();
+
+            stack.push_operand(u8::from_le_bytes([v]))?;
         }
         Instruction::ConvertS32ToS8 => {
             let v = stack.pop_operand()?;
@@ -190,7 +157,31 @@ fn evaluate_instruction(
             let v = v.to_i32();
             let v: i8 = v.try_into()?;
 
-            stack.push_operand(v);
+            stack.push_operand(v)?;
+        }
+        Instruction::ConvertS32ToU8 => {
+            let v = stack.pop_operand()?;
+
+            let v = v.to_i32();
+            let v: u8 = v.try_into()?;
+
+            stack.push_operand(v)?;
+        }
+        Instruction::ConvertU8ToS8Wrap => {
+            let v = stack.pop_operand()?;
+
+            let v = v.to_u8()?;
+            let [v] = v.to_le_bytes();
+
+            stack.push_operand(i8::from_le_bytes([v]))?;
+        }
+        Instruction::ConvertU8ToS32 => {
+            let v = stack.pop_operand()?;
+
+            let v = v.to_u8()?;
+            let v: i32 = v.into();
+
+            stack.push_operand(v)?;
         }
         Instruction::Copy => {
             let offset_from_top = stack.pop_operand()?.to_usize();
@@ -201,7 +192,7 @@ fn evaluate_instruction(
                 return Err(Effect::InvalidArgument);
             };
 
-            stack.push_operand(value);
+            stack.push_operand(value)?;
         }
         Instruction::DivS32 => {
             let b = stack.pop_operand()?;
@@ -218,7 +209,7 @@ fn evaluate_instruction(
                 return Err(Effect::IntegerOverflow);
             };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::DivU8 => {
             let b = stack.pop_operand()?;
@@ -235,7 +226,7 @@ fn evaluate_instruction(
                 return Err(Effect::IntegerOverflow);
             };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::Drop => {
             stack.pop_operand()?;
@@ -246,70 +237,24 @@ fn evaluate_instruction(
 
             let c = if a.0 == b.0 { 1 } else { 0 };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::Eval { is_tail_call } => {
-            // This duplicates code from other places, which is unfortunate,
-            // but works for now.
-            //
-            // See implementation note on `Instruction::Eval` for context on
-            // this.
-
-            let function = {
-                let index = stack.pop_operand()?;
-                let index = index.to_u32();
-
-                heap.closures
-                    .remove(&index)
-                    .ok_or(Effect::InvalidFunction)?
+            let function = InstructionAddress {
+                index: stack.pop_operand()?.to_u32(),
             };
 
-            for branch in &function.branches {
-                let mut used_operands = Vec::new();
-                let mut argument_operands = Vec::new();
-                let mut bound_arguments = Vec::new();
-
-                let mut member_matches = true;
-                for parameter in branch.parameters.iter().rev() {
-                    let operand = stack.pop_operand()?;
-                    used_operands.push(operand);
-
-                    match parameter {
-                        Pattern::Identifier { name } => {
-                            bound_arguments.push((name.clone(), operand));
-                            argument_operands.push(operand);
-                        }
-                        Pattern::Literal { value } => {
-                            member_matches &= *value == operand;
-                        }
-                    }
-                }
-
-                if member_matches {
-                    for value in argument_operands.into_iter().rev() {
-                        stack.push_operand(value);
-                    }
-
-                    if *is_tail_call {
-                        stack.reuse_frame();
-                    } else {
-                        stack.push_frame(next_instruction)?;
-                    }
-
-                    stack
-                        .bindings_mut()
-                        .expect("Currently executing; stack frame must exist")
-                        .extend(function.environment);
-
-                    return Ok(branch.start);
-                } else {
-                    for value in used_operands.into_iter().rev() {
-                        stack.push_operand(value);
-                    }
-                }
+            if instructions.get(&function).is_none() {
+                return Err(Effect::InvalidFunction);
             }
 
-            return Err(Effect::NoMatch);
+            if *is_tail_call {
+                stack.reuse_frame();
+            } else {
+                stack.push_frame(next_instruction)?;
+            }
+
+            return Ok(function);
         }
         Instruction::GreaterS8 => {
             let b = stack.pop_operand()?;
@@ -320,7 +265,7 @@ fn evaluate_instruction(
 
             let c = if a > b { 1 } else { 0 };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::GreaterS32 => {
             let b = stack.pop_operand()?;
@@ -331,7 +276,7 @@ fn evaluate_instruction(
 
             let c = if a > b { 1 } else { 0 };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::GreaterU8 => {
             let b = stack.pop_operand()?;
@@ -342,7 +287,14 @@ fn evaluate_instruction(
 
             let c = if a > b { 1 } else { 0 };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
+        }
+        Instruction::JumpIfZero { target } => {
+            let condition = stack.pop_operand()?;
+
+            if condition.0 == [0; 4] {
+                return Ok(*target);
+            }
         }
         Instruction::LogicalAnd => {
             let b = stack.pop_operand()?;
@@ -350,58 +302,13 @@ fn evaluate_instruction(
 
             let c = if a.0 == [0; 4] || b.0 == [0; 4] { 0 } else { 1 };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::LogicalNot => {
             let a = stack.pop_operand()?;
 
             let b = if a.0 == [0; 4] { 1 } else { 0 };
-            stack.push_operand(b);
-        }
-        Instruction::MakeAnonymousFunction {
-            branches,
-            environment,
-        } => {
-            let Some(bindings) = stack.bindings() else {
-                unreachable!(
-                    "We're currently executing. A stack frame, and thus \
-                    bindings, must exist."
-                );
-            };
-
-            let environment = environment
-                .iter()
-                .cloned()
-                .map(|name| {
-                    let Some(value) = bindings.get(&name).cloned() else {
-                        unreachable!(
-                            "Binding `{name}`, from the environment of an \
-                            anonymous function, does not exist.\n\
-                            \n\
-                            A binding that is part of a function's \
-                            environment, must exist in the parent scope of \
-                            that function.",
-                        );
-                    };
-
-                    (name, value)
-                })
-                .collect();
-
-            let index = {
-                let index = heap.next_closure;
-                heap.next_closure += 1;
-                index
-            };
-            heap.closures.insert(
-                index,
-                Function {
-                    branches: branches.clone(),
-                    environment,
-                },
-            );
-
-            stack.push_operand(index);
+            stack.push_operand(b)?;
         }
         Instruction::MulS32 => {
             let b = stack.pop_operand()?;
@@ -414,7 +321,7 @@ fn evaluate_instruction(
                 return Err(Effect::IntegerOverflow);
             };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::MulU8Wrap => {
             let b = stack.pop_operand()?;
@@ -424,7 +331,7 @@ fn evaluate_instruction(
             let b = b.to_u8()?;
 
             let c = a.wrapping_mul(b);
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::NegS32 => {
             let a = stack.pop_operand()?;
@@ -436,13 +343,13 @@ fn evaluate_instruction(
             }
             let b = -a;
 
-            stack.push_operand(b);
+            stack.push_operand(b)?;
         }
         Instruction::Nop => {
             // "no operation"
         }
         Instruction::Push { value } => {
-            stack.push_operand(*value);
+            stack.push_operand(*value)?;
         }
         Instruction::RemainderS32 => {
             let b = stack.pop_operand()?;
@@ -456,7 +363,14 @@ fn evaluate_instruction(
             }
             let c = a % b;
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
+        }
+        Instruction::Remove => {
+            let offset_from_top = stack.pop_operand()?.to_usize();
+
+            if stack.remove_operand(offset_from_top).is_none() {
+                return Err(Effect::InvalidArgument);
+            }
         }
         Instruction::Return => {
             if let Some(return_address) = stack.pop_frame() {
@@ -474,7 +388,7 @@ fn evaluate_instruction(
                 return Err(Effect::IntegerOverflow);
             };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::SubU8 => {
             let b = stack.pop_operand()?;
@@ -487,7 +401,7 @@ fn evaluate_instruction(
                 return Err(Effect::IntegerOverflow);
             };
 
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::SubU8Wrap => {
             let b = stack.pop_operand()?;
@@ -497,7 +411,7 @@ fn evaluate_instruction(
             let b = b.to_u8()?;
 
             let c = a.wrapping_sub(b);
-            stack.push_operand(c);
+            stack.push_operand(c)?;
         }
         Instruction::TriggerEffect { effect } => {
             return Err(*effect);
@@ -1,9 +1,9 @@
-use alloc::{collections::BTreeSet, string::String, vec::Vec};
 use core::fmt;
 
-use crate::{Branch, Effect, Function, Value};
+use crate::{Effect, Value};
 
 /// # The instructions that the runtime executes
+#[derive(Clone, Copy)]
 pub struct Instructions<'r> {
     pub inner: &'r [(InstructionAddress, Instruction)],
 }
@@ -28,6 +28,7 @@ impl Instructions<'_> {
     PartialOrd,
     serde::Deserialize,
     serde::Serialize,
+    udigest::Digestable,
 )]
 pub struct InstructionAddress {
     pub index: u32,
@@ -59,7 +60,15 @@ impl fmt::Display for InstructionAddress {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    udigest::Digestable,
+)]
 pub enum Instruction {
     /// # Add two signed 8-bit integers, triggering an error on overflow
     AddS8,
@@ -73,86 +82,59 @@ pub enum Instruction {
     /// # Add two unsigned 8-bit integers, wrapping on overflow
     AddU8Wrap,
 
-    /// # Bind a value to a name
-    ///
-    /// ## Implementation Note
+    /// # Pop a value and bind it to a slot in the current stack frame
     ///
-    /// This is one of those high-level instructions that I'd like to get rid
-    /// of, eventually. There's no need to know about the names of values at
-    /// runtime.
-    ///
-    /// The compiler should just keep track of all names and the respective
-    /// value's locations on the stack at compile-time. This requires changes to
-    /// the instruction set, either in the form of an instruction that can copy
-    /// any value to the top of the stack, or new means to specify which values
-    /// a given instruction targets.
+    /// The compiler assigns each binding a slot that is unique within its
+    /// branch. The names of the bindings are not known at runtime, but can be
+    /// recovered from the source map.
     Bind {
-        name: String,
+        slot: u32,
     },
 
-    /// # Push the value that is bound to the provided name to the stack
+    /// # Push the value that is bound to the provided slot to the stack
     ///
     /// ## Implementation Note
     ///
-    /// This is one of those high-level instructions that I'd like to get rid
-    /// of, eventually. There's no need to know about the names of values at
-    /// runtime.
-    ///
-    /// The compiler should just keep track of all names and the respective
-    /// value's locations on the stack at compile-time. This requires changes to
-    /// the instruction set, either in the form of an instruction that can copy
-    /// any value to the top of the stack, or new means to specify which values
-    /// a given instruction targets.
+    /// Bindings are still stored separately from the operands. The compiler
+    /// could track where each value is on the stack at compile-time, and copy
+    /// it from there, but that would require it to also track the stack
+    /// effects of every expression.
     BindingEvaluate {
-        name: String,
+        slot: u32,
     },
 
-    /// # Call a function, selecting the right branch via pattern matching
-    ///
-    /// ## Implementation Note
-    ///
-    /// This instruction is overly complex. I think ideally, there would be two
-    /// simple instructions instead:
+    /// # Call a function
     ///
-    /// - `PushFrame`, to push a new stack frame, and do nothing else with it.
-    /// - `ReuseFrame`, to prepare the existing stack frame for reuse. (If
-    ///   bindings were just regular values whose addresses were managed by the
-    ///   compiler, instead of being a special thing handled by the stack at
-    ///   runtime, then we probably wouldn't need this at all, and could reuse
-    ///   any stack frame without preparation. That's a different story,
-    ///   however.)
+    /// Creates a new stack frame, unless this is a tail call, then jumps to the
+    /// provided address. That is the start of the function's first branch.
     ///
-    /// Those would just take a single address as an argument, and jump there
-    /// directly. (And maybe even pushing a frame and jumping to an instructions
-    /// could and should be decoupled eventually.)
-    ///
-    /// Pattern matching could move into the branches themselves. The compiler
-    /// could generate the necessary code as required, using the following
-    /// approach:
-    ///
-    /// - If there are no literal patterns among the arguments, just generate
-    ///   nothing.
-    /// - If there are literal patterns, generate code that compares them
-    ///   against the call stack.
-    ///   - If all patterns match, remove the respective operands from the
-    ///     stack and continue with the rest of the branch as normal. (Although
-    ///     probably the next step would be the compiler-generated code that
-    ///     handles the arguments.
-    ///   - If the patterns don't match, leave the operands as-is and jump
-    ///     directly to the next branch, which continues with its own pattern
-    ///     matching.
-    ///
-    /// This would require some new instructions for doing the comparison and
-    /// jumping to another address, but that seems like the right direction to
-    /// go in anyway.
+    /// Selecting the branch to execute is not the job of this instruction. Each
+    /// branch starts with compiler-generated code that matches the arguments
+    /// against its parameters, and jumps to the next branch, if they don't
+    /// match.
     CallFunction {
-        callee: Function,
+        callee: InstructionAddress,
         is_tail_call: bool,
     },
 
+    /// # Convert a signed 8-bit number to a signed 32-bit number
+    ConvertS8ToS32,
+
+    /// # Convert a signed 8-bit number to an unsigned 8-bit number, wrapping
+    ConvertS8ToU8Wrap,
+
     /// # Convert a signed 32-bit number to a signed 8-bit number
     ConvertS32ToS8,
 
+    /// # Convert a signed 32-bit number to an unsigned 8-bit number
+    ConvertS32ToU8,
+
+    /// # Convert an unsigned 8-bit number to a signed 8-bit number, wrapping
+    ConvertU8ToS8Wrap,
+
+    /// # Convert an unsigned 8-bit number to a signed 32-bit number
+    ConvertU8ToS32,
+
     /// # Copy a value on the stack to the top of the stack
     ///
     /// The value to copy is identified by an offset from the top of the stack,
@@ -173,21 +155,16 @@ pub enum Instruction {
 
     /// # Evaluate an anonymous function
     ///
-    /// The top value on the stack is interpreted as the index of the anonymous
-    /// function. If it doesn't identify an anonymous function (because the
-    /// anonymous function was evaluated previously, or such an index never
-    /// existed), an error is triggered.
-    ///
-    /// ## Implementation Note
+    /// The top value on the stack is interpreted as the address of the
+    /// anonymous function's entry point. This instruction calls the anonymous
+    /// function by jumping there, creating a new stack frame, unless this is a
+    /// tail call.
     ///
-    /// This instruction is too high-level, and it's partially redundant with
-    /// other high-level instructions. The duplicated code in their
-    /// implementations within `Evaluator` is supporting evidence of this.
+    /// The values that the anonymous function captured from its environment,
+    /// if any, are right underneath the address. The code at the entry point
+    /// takes care of them, along with the function's arguments.
     ///
-    /// Like other instructions, it needs to be replaced by smaller, more
-    /// low-level ones. This requires the compiler to become smarter. Which is
-    /// the direction I'd like things to go into anyway, but it hasn't fully
-    /// happened yet.
+    /// If the address doesn't point to an instruction, an error is triggered.
     Eval {
         is_tail_call: bool,
     },
@@ -201,37 +178,20 @@ pub enum Instruction {
     /// # Determine if the first of two unsigned 8-bit numbers is greater
     GreaterU8,
 
+    /// # Jump to the provided address, if the top value on the stack is zero
+    ///
+    /// The value is consumed either way. If it is not zero, execution continues
+    /// with the next instruction.
+    JumpIfZero {
+        target: InstructionAddress,
+    },
+
     /// # Logical and
     LogicalAnd,
 
     /// # Logical not
     LogicalNot,
 
-    /// # Create an anonymous function
-    ///
-    /// ## Implementation Note
-    ///
-    /// Anonymous functions and their environment are currently allocated in a
-    /// special map within the evaluator. This instruction is required to make
-    /// that work.
-    ///
-    /// But this situation is undesirable. Anonymous functions should not be
-    /// boxed. They should just be regular values, with everything needed to
-    /// make them work allocated on the stack.
-    ///
-    /// Right now, this is not feasible, because the language is untyped and
-    /// every value is a 32-bit word. Once we have static typing and the ability
-    /// to create composite values, anonymous functions should be represented
-    /// using those.
-    ///
-    /// When this is the case, special handling at runtime will no longer be
-    /// required. Then all logic specific to anonymous functions can live in the
-    /// compiler, and this instruction can be removed.
-    MakeAnonymousFunction {
-        branches: Vec<Branch>,
-        environment: BTreeSet<String>,
-    },
-
     /// # Multiply two signed 32-bit numbers, triggering an error on overflow
     MulS32,
 
@@ -251,6 +211,12 @@ pub enum Instruction {
     /// # Compute the remainder of the division of two signed 32-bit numbers
     RemainderS32,
 
+    /// # Remove a value from the stack
+    ///
+    /// The value to remove is identified by an offset from the top of the
+    /// stack, which this instruction expects as an argument.
+    Remove,
+
     Return,
 
     /// # Subtract two signed 32-bit numbers, triggering an error on overflow
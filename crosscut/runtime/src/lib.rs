@@ -4,8 +4,6 @@ extern crate alloc;
 
 mod effects;
 mod evaluator;
-mod function;
-mod heap;
 mod instructions;
 mod operands;
 mod runtime;
@@ -14,11 +12,9 @@ mod value;
 
 pub use self::{
     effects::{Effect, TriggerResult, TriggeredEffect},
-    function::{Branch, Function, Pattern},
-    heap::Heap,
     instructions::{Instruction, InstructionAddress, Instructions},
     operands::{Operands, PopOperandError},
     runtime::{Runtime, RuntimeState},
-    stack::Stack,
+    stack::{PushOperandError, PushStackFrameError, Stack, StackLimits},
     value::Value,
 };
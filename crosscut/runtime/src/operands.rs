@@ -37,6 +37,7 @@ impl Operands {
     serde::Deserialize,
     serde::Serialize,
     thiserror::Error,
+    udigest::Digestable,
 )]
 pub enum PopOperandError {
     #[error("Missing operand")]
@@ -1,5 +1,6 @@
 use crate::{
-    evaluator::Evaluator, Heap, Instructions, Stack, TriggeredEffect, Value,
+    evaluator::Evaluator, Instructions, Stack, StackLimits, TriggeredEffect,
+    Value,
 };
 
 #[derive(
@@ -8,19 +9,54 @@ use crate::{
 pub struct Runtime {
     effect: TriggeredEffect,
     evaluator: Evaluator,
+
+    /// # The number of instructions the runtime may still evaluate
+    ///
+    /// `None` means there is no limit.
+    fuel: Option<u64>,
 }
 
 impl Runtime {
+    /// # Create a runtime whose stack enforces the provided limits
+    pub fn with_stack_limits(limits: StackLimits) -> Self {
+        Self {
+            effect: TriggeredEffect::default(),
+            evaluator: Evaluator {
+                stack: Stack::with_limits(limits),
+                next_instruction: Default::default(),
+            },
+            fuel: None,
+        }
+    }
+
     pub fn state(&self) -> RuntimeState {
         if self.effect.inspect().is_some() {
             RuntimeState::Stopped
         } else if self.evaluator.stack.no_frames_left() {
             RuntimeState::Finished
+        } else if self.fuel == Some(0) {
+            RuntimeState::OutOfFuel
         } else {
             RuntimeState::Running
         }
     }
 
+    /// # Limit how many more instructions the runtime may evaluate
+    ///
+    /// Once the fuel has run out, the runtime stops evaluating instructions,
+    /// until it is refueled. This gives the host the opportunity to regain
+    /// control from a process that doesn't yield on its own, for example
+    /// because of an infinite loop.
+    ///
+    /// Pass `None` to remove the limit.
+    pub fn refuel(&mut self, fuel: Option<u64>) {
+        self.fuel = fuel;
+    }
+
+    pub fn fuel(&self) -> Option<u64> {
+        self.fuel
+    }
+
     pub fn effect(&self) -> &TriggeredEffect {
         &self.effect
     }
@@ -41,24 +77,36 @@ impl Runtime {
         &mut self.evaluator.stack
     }
 
+    /// # Reset the runtime, keeping its stack limits
+    ///
+    /// The provided arguments are passed to the `main` function. If they
+    /// exceed the operand limit, the runtime is stopped with the respective
+    /// effect.
     pub fn reset(&mut self, arguments: impl IntoIterator<Item = Value>) {
-        *self = Self::default();
+        *self = Self::with_stack_limits(self.stack().limits());
 
         for argument in arguments {
-            self.evaluator.stack.push_operand(argument);
+            if let Err(effect) = self.evaluator.stack.push_operand(argument) {
+                self.effect
+                    .trigger(effect)
+                    // We just reset the runtime, so there can't be an effect
+                    // yet.
+                    .assert_triggered();
+                break;
+            }
         }
     }
 
-    pub fn evaluate_next_instruction(
-        &mut self,
-        instructions: Instructions,
-        heap: &mut Heap,
-    ) {
+    pub fn evaluate_next_instruction(&mut self, instructions: Instructions) {
         if !self.state().is_running() {
             return;
         }
 
-        if let Err(effect) = self.evaluator.step(instructions, heap) {
+        if let Some(fuel) = &mut self.fuel {
+            *fuel -= 1;
+        }
+
+        if let Err(effect) = self.evaluator.step(instructions) {
             self.effect
                 .trigger(effect)
                 // If there already was an effect, we would have left the
@@ -102,10 +150,16 @@ impl Runtime {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum RuntimeState {
     Running,
     Finished,
     Stopped,
+
+    /// # The runtime has run out of fuel
+    ///
+    /// See [`Runtime::refuel`].
+    OutOfFuel,
 }
 
 impl RuntimeState {
@@ -116,4 +170,8 @@ impl RuntimeState {
     pub fn has_finished(&self) -> bool {
         matches!(self, Self::Finished)
     }
+
+    pub fn is_out_of_fuel(&self) -> bool {
+        matches!(self, Self::OutOfFuel)
+    }
 }
@@ -1,22 +1,44 @@
-use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use alloc::{vec, vec::Vec};
 
 use crate::{operands::PopOperandError, InstructionAddress, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct Stack {
     inner: Vec<StackElement>,
+
+    /// # The bindings of each stack frame
+    ///
+    /// Kept separately from the other elements, so the bindings of the current
+    /// stack frame can be accessed without searching for them.
+    bindings: Vec<Bindings>,
+
+    /// # The number of operands on the stack
+    ///
+    /// Tracked separately, so the operand limit can be checked without counting
+    /// them every time.
+    num_operands: usize,
+
+    limits: StackLimits,
 }
 
 impl Stack {
     pub fn new() -> Self {
+        Self::with_limits(StackLimits::default())
+    }
+
+    pub fn with_limits(limits: StackLimits) -> Self {
         Self {
-            inner: vec![
-                StackElement::StartMarker,
-                StackElement::Bindings(Bindings::new()),
-            ],
+            inner: vec![StackElement::StartMarker],
+            bindings: vec![Bindings::new()],
+            num_operands: 0,
+            limits,
         }
     }
 
+    pub fn limits(&self) -> StackLimits {
+        self.limits
+    }
+
     /// # Determine wether any stack frames are left
     ///
     /// The stack starts out with an initial stack frame on initialization. If
@@ -35,21 +57,19 @@ impl Stack {
         self.inner.is_empty()
     }
 
-    pub fn bindings(&self) -> Option<&Bindings> {
-        self.inner.iter().rev().find_map(|element| match element {
-            StackElement::Bindings(bindings) => Some(bindings),
-            _ => None,
-        })
+    /// # Access the bindings of the current stack frame, indexed by slot
+    ///
+    /// The runtime doesn't know the names of bindings. The compiler keeps track
+    /// of those.
+    pub fn bindings(&self) -> &[Value] {
+        self.bindings.last().map(Vec::as_slice).unwrap_or(&[])
     }
 
-    pub fn bindings_mut(&mut self) -> Option<&mut Bindings> {
-        self.inner
-            .iter_mut()
-            .rev()
-            .find_map(|element| match element {
-                StackElement::Bindings(bindings) => Some(bindings),
-                _ => None,
-            })
+    /// # Access the value of a binding in the current stack frame
+    ///
+    /// Returns `None`, if no value has been bound to the provided slot.
+    pub fn binding(&self, slot: usize) -> Option<Value> {
+        self.bindings().get(slot).copied()
     }
 
     /// # Iterate over the operands on the stack, from the base
@@ -74,9 +94,9 @@ impl Stack {
         return_address: InstructionAddress,
     ) -> Result<(), PushStackFrameError> {
         // Not a tail call. This means we need to create a new stack frame.
-        // Let's first check if we can even do that.
-        const STACK_LIMIT: usize = 32;
-        if self.inner.len() >= STACK_LIMIT {
+        // Let's first check if we can even do that. Every stack frame has its
+        // own bindings, so those tell us how many there are.
+        if self.bindings.len() >= self.limits.frames {
             return Err(PushStackFrameError::Overflow);
         }
 
@@ -84,8 +104,8 @@ impl Stack {
         // initial one is created with the stack), start with a return address.
         self.inner.push(StackElement::ReturnAddress(return_address));
 
-        // And all stack frames need a map of bindings.
-        self.inner.push(StackElement::Bindings(Bindings::new()));
+        // And all stack frames need bindings.
+        self.bindings.push(Bindings::new());
 
         Ok(())
     }
@@ -103,7 +123,7 @@ impl Stack {
         //
         // But we need to handle bindings.
 
-        let Some(bindings) = self.bindings_mut() else {
+        let Some(bindings) = self.bindings.last_mut() else {
             panic!(
                 "Trying to access bindings, but none are available. This \
                 implies that no stack frame is available.\n\
@@ -133,15 +153,14 @@ impl Stack {
             index -= 1;
 
             match self.inner[index] {
-                StackElement::Bindings(_) => {
-                    self.inner.remove(index);
-                }
                 StackElement::ReturnAddress(address) => {
                     self.inner.remove(index);
+                    self.bindings.pop();
                     break Some(address);
                 }
                 StackElement::StartMarker => {
                     self.inner.remove(index);
+                    self.bindings.pop();
                     break None;
                 }
                 _ => {}
@@ -149,14 +168,30 @@ impl Stack {
         }
     }
 
-    pub fn define_binding(&mut self, name: String, value: impl Into<Value>) {
-        self.bindings_mut()
-            .expect("Expected stack frame to exist")
-            .insert(name, value.into());
+    pub fn define_binding(&mut self, slot: usize, value: impl Into<Value>) {
+        let bindings = self
+            .bindings
+            .last_mut()
+            .expect("Expected stack frame to exist");
+
+        if bindings.len() <= slot {
+            bindings.resize(slot + 1, Value([0; 4]));
+        }
+        bindings[slot] = value.into();
     }
 
-    pub fn push_operand(&mut self, operand: impl Into<Value>) {
+    pub fn push_operand(
+        &mut self,
+        operand: impl Into<Value>,
+    ) -> Result<(), PushOperandError> {
+        if self.num_operands >= self.limits.operands {
+            return Err(PushOperandError::Overflow);
+        }
+
         self.inner.push(StackElement::Operand(operand.into()));
+        self.num_operands += 1;
+
+        Ok(())
     }
 
     pub fn pop_operand(&mut self) -> Result<Value, PopOperandError> {
@@ -166,6 +201,7 @@ impl Stack {
 
             if let StackElement::Operand(value) = self.inner[index] {
                 self.inner.remove(index);
+                self.num_operands -= 1;
                 return Ok(value);
             }
         }
@@ -173,6 +209,26 @@ impl Stack {
         Err(PopOperandError::MissingOperand)
     }
 
+    /// # Remove an operand, identified by its offset from the top
+    ///
+    /// Returns `None`, if there are not enough operands on the stack.
+    pub fn remove_operand(&mut self, offset_from_top: usize) -> Option<Value> {
+        let (index, _) = self
+            .inner
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, element)| matches!(element, StackElement::Operand(_)))
+            .nth(offset_from_top)?;
+
+        let StackElement::Operand(value) = self.inner.remove(index) else {
+            unreachable!("Only operands have been considered for removal.");
+        };
+        self.num_operands -= 1;
+
+        Some(value)
+    }
+
     pub fn into_inner(self) -> Vec<StackElement> {
         self.inner
     }
@@ -197,22 +253,6 @@ impl Default for Stack {
 /// longer need to track this kind of type information at runtime.
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum StackElement {
-    /// # The bindings in the current stack frame
-    ///
-    /// There should be one of these per stack frame, and it's expected that
-    /// this is the first element after the return address.
-    ///
-    /// ## Implementation Note
-    ///
-    /// Having a map with the names and values of bindings at runtime is
-    /// unnecessary. We need the value, sure, but the compiler can track the
-    /// names and locations of bindings at compile-time.
-    ///
-    /// At some point it will learn to do that, and generate the right
-    /// instructions to access them. Then we won't need to look up bindings by
-    /// name at runtime.
-    Bindings(Bindings),
-
     /// An operand
     Operand(Value),
 
@@ -236,7 +276,35 @@ pub enum StackElement {
     StartMarker,
 }
 
-pub type Bindings = BTreeMap<String, Value>;
+/// # The bindings of a stack frame, indexed by the slot the compiler assigned
+pub type Bindings = Vec<Value>;
+
+/// # The limits that the stack enforces
+///
+/// Exceeding them triggers an effect, instead of letting a runaway process
+/// consume all available memory.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize,
+)]
+pub struct StackLimits {
+    /// # The maximum number of stack frames
+    ///
+    /// This limits how deep non-tail recursion can go. Tail calls reuse the
+    /// current stack frame, so they don't count towards this limit.
+    pub frames: usize,
+
+    /// # The maximum number of operands, across all stack frames
+    pub operands: usize,
+}
+
+impl Default for StackLimits {
+    fn default() -> Self {
+        Self {
+            frames: 1024,
+            operands: 4096,
+        }
+    }
+}
 
 #[derive(
     Clone,
@@ -247,6 +315,7 @@ pub type Bindings = BTreeMap<String, Value>;
     serde::Deserialize,
     serde::Serialize,
     thiserror::Error,
+    udigest::Digestable,
 )]
 pub enum PushStackFrameError {
     #[error("Reached recursion limit")]
@@ -255,3 +324,19 @@ pub enum PushStackFrameError {
     #[error("Evaluator is already finished")]
     Finished,
 }
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    thiserror::Error,
+    udigest::Digestable,
+)]
+pub enum PushOperandError {
+    #[error("Reached operand limit")]
+    Overflow,
+}
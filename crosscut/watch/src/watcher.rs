@@ -15,7 +15,19 @@ pub struct Watcher {
 }
 
 impl Watcher {
+    /// # Watch for changes to any file within the provided path
     pub fn new(path: &Path) -> anyhow::Result<Self> {
+        Self::with_filter(path, |_| true)
+    }
+
+    /// # Watch for changes to files that match the provided filter
+    ///
+    /// Changes are only reported, if they affect at least one path for which
+    /// the filter returns `true`.
+    pub fn with_filter(
+        path: &Path,
+        filter: impl Fn(&Path) -> bool + Send + 'static,
+    ) -> anyhow::Result<Self> {
         let (tx, rx) = watch::channel(());
 
         let mut watcher = notify::recommended_watcher(move |event| {
@@ -27,6 +39,12 @@ impl Watcher {
                     // We're not interested in read access to any files.
                     return;
                 }
+                Ok(Event { paths, .. })
+                    if !paths.iter().any(|path| filter(path)) =>
+                {
+                    // None of the files we're interested in have changed.
+                    return;
+                }
                 Err(err) => {
                     error!("Error watching for changes: {err}");
                     return;